@@ -1,21 +1,37 @@
 use std::borrow::Cow;
-
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use tosca::device::{DeviceDescription, DeviceKind};
+#[cfg(feature = "stream")]
+use tosca::events::EVENTS_STREAM_ROUTE;
+use tosca::events::Events as ToscaEvents;
+use tosca::hazards::Hazard;
 use tosca::parameters::ParametersValues;
+use tosca::response::{InfoResponse, ResponseKind};
+
+use futures_util::{StreamExt, future, stream};
 
+use tokio::sync::broadcast;
 use tokio::sync::mpsc::{self, Receiver};
 
 use tracing::{error, warn};
 
-use crate::device::{Device, Devices};
-use crate::discovery::Discovery;
+use crate::device::{Device, Devices, NetworkInformation, build_device_address};
+use crate::discovery::{Discovery, device_from_description};
 use crate::error::{Error, ErrorKind};
-use crate::events::{EventPayload, EventsRunner};
+use crate::events::{EventPayload, EventSubscriberConfig, EventsRunner};
+use crate::lifecycle::{ControllerEvent, Lifecycle};
 use crate::policy::Policy;
-use crate::request::Request;
-use crate::response::Response;
+use crate::request::{InFlightRequests, Request, RequestTelemetry};
+use crate::response::{Response, decode_json};
 
 // TODO: Use the MAC address as id.
 
+// Maximum number of info requests sent at the same time.
+const MAX_CONCURRENT_INFO_REQUESTS: usize = 4;
+
 fn sender_error(error: impl Into<Cow<'static, str>>) -> Error {
     Error::new(ErrorKind::Sender, error)
 }
@@ -25,20 +41,129 @@ fn sender_error(error: impl Into<Cow<'static, str>>) -> Error {
 pub struct RequestSender<'controller> {
     controller: &'controller Controller,
     request: &'controller Request,
+    id: usize,
     skip: bool,
+    timeout: Option<Duration>,
 }
 
 impl RequestSender<'_> {
+    /// Sets the maximum amount of time to wait for a device response.
+    ///
+    /// When the timeout expires, an [`ErrorKind::Timeout`] error is returned.
+    /// By default, no timeout is applied.
+    #[must_use]
+    #[inline]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Sends a request to a device and returns a [`Response`].
     ///
     /// # Errors
     ///
     /// Network failures or timeouts may prevent the request from being sent
     /// and affect the returned response as well.
+    ///
+    /// When request coalescing is enabled on the [`Controller`], concurrent
+    /// identical idempotent requests share a single network request.
     pub async fn send(&self) -> Result<Response, Error> {
-        self.request
-            .retrieve_response(self.skip, || async { self.request.plain_send().await })
-            .await
+        match &self.controller.in_flight_requests {
+            Some(in_flight_requests) if self.request.idempotent => {
+                self.request
+                    .retrieve_response(self.skip, || {
+                        self.track_reachability(
+                            in_flight_requests.plain_send(self.request, self.timeout),
+                        )
+                    })
+                    .await
+            }
+            _ => {
+                self.request
+                    .retrieve_response(self.skip, || {
+                        self.track_reachability(self.request.plain_send(self.timeout))
+                    })
+                    .await
+            }
+        }
+    }
+
+    /// Sends a request to a device and returns a [`Response`], retrying
+    /// in case of temporary network failures.
+    ///
+    /// The request is sent at most `attempts` times. The delay between two
+    /// attempts starts at `base_delay` and doubles after each failure.
+    ///
+    /// A request which is **not** idempotent is retried only when the
+    /// connection to the device fails, so that the device never
+    /// receives it twice.
+    ///
+    /// # Errors
+    ///
+    /// Errors which cannot be retried are returned immediately, while the
+    /// last error is returned when all attempts fail.
+    pub async fn send_with_retry(
+        &self,
+        attempts: usize,
+        base_delay: Duration,
+    ) -> Result<Response, Error> {
+        let mut delay = base_delay;
+        let mut attempt = 1;
+        loop {
+            match self.send().await {
+                Err(error) if attempt < attempts && self.is_retryable(&error) => {
+                    warn!("Attempt {attempt} of {attempts} failed, retrying in {delay:?}: {error}");
+                    tokio::time::sleep(delay).await;
+                    delay = delay.saturating_mul(2);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    // Checks whether a request can be sent again after the given error.
+    //
+    // A non-idempotent request might have already been received by the
+    // device, unless the connection has failed.
+    const fn is_retryable(&self, error: &Error) -> bool {
+        if self.request.idempotent {
+            error.is_transient()
+        } else {
+            error.is_connect()
+        }
+    }
+
+    /// Sends a request to a device and returns a [`Response`] together with
+    /// the [`RequestTelemetry`] collected while sending it.
+    ///
+    /// When the request is skipped, the telemetry is left empty.
+    ///
+    /// # Errors
+    ///
+    /// Network failures or timeouts may prevent the request from being sent
+    /// and affect the returned response as well.
+    pub async fn send_with_telemetry(&self) -> Result<(Response, RequestTelemetry), Error> {
+        let start = Instant::now();
+        let mut telemetry = RequestTelemetry::default();
+
+        let response = self
+            .request
+            .retrieve_response(self.skip, || async {
+                let response = self
+                    .track_reachability(self.request.plain_send(self.timeout))
+                    .await?;
+                telemetry.first_byte = start.elapsed();
+                telemetry.response_size = response.content_length();
+                Ok(response)
+            })
+            .await?;
+
+        if !self.skip {
+            telemetry.total = start.elapsed();
+        }
+
+        Ok((response, telemetry))
     }
 
     /// Sends a request to a device with the given [`ParametersValues`]
@@ -58,11 +183,33 @@ impl RequestSender<'_> {
         }
 
         self.request
-            .retrieve_response(self.skip, || async {
-                self.request.create_response(parameters).await
+            .retrieve_response(self.skip, || {
+                self.track_reachability(self.request.create_response(parameters, self.timeout))
             })
             .await
     }
+
+    // Reports whether the device has answered the request, ignoring
+    // the errors raised before sending it.
+    async fn track_reachability(
+        &self,
+        response: impl Future<Output = Result<reqwest::Response, Error>>,
+    ) -> Result<reqwest::Response, Error> {
+        let response = response.await;
+        match &response {
+            Ok(_) => self
+                .controller
+                .lifecycle
+                .reachability_changed(self.id, true),
+            Err(e) if e.kind() == ErrorKind::Request => {
+                self.controller
+                    .lifecycle
+                    .reachability_changed(self.id, false);
+            }
+            Err(_) => {}
+        }
+        response
+    }
 }
 
 /// A device sender.
@@ -91,7 +238,15 @@ impl DeviceSender<'_> {
             ))
         })?;
 
-        let skip = if request.hazards.is_empty() {
+        // The route rules take precedence over the hazard rules.
+        let policy = &self.controller.privacy_policy;
+        let skip = if !policy.is_route_allowed(self.id, route) {
+            warn!("The {route} is skipped because it is not in the device allowed routes");
+            true
+        } else if policy.is_route_blocked(self.id, route) {
+            warn!("The {route} is skipped because it is in the device blocked routes");
+            true
+        } else if request.hazards.is_empty() {
             false
         } else {
             self.evaluate_privacy_policy(request, route)
@@ -100,10 +255,35 @@ impl DeviceSender<'_> {
         Ok(RequestSender {
             controller: self.controller,
             request,
+            id: self.id,
             skip,
+            timeout: None,
         })
     }
 
+    /// Opens the device events stream, yielding [`ToscaEvents`] each time
+    /// the device publishes them.
+    ///
+    /// Events are received as Server-Sent Events over `HTTP`, hence
+    /// without an event broker, from the device
+    /// [`EVENTS_STREAM_ROUTE`](tosca::events::EVENTS_STREAM_ROUTE) route.
+    /// The device firmware must have the events stream enabled.
+    ///
+    /// The stream never ends: when the connection is lost, an error is
+    /// yielded and the stream is reopened on the next poll.
+    /// Drop the stream to close the connection.
+    #[cfg(feature = "stream")]
+    pub fn open_event_stream(
+        &self,
+    ) -> impl futures_util::Stream<Item = Result<ToscaEvents, Error>> + Send + use<> {
+        crate::sse::event_stream(format!(
+            "{}{}{}",
+            self.device.network_info().last_reachable_address,
+            self.device.description().main_route,
+            EVENTS_STREAM_ROUTE
+        ))
+    }
+
     fn evaluate_privacy_policy(&self, request: &Request, route: &str) -> bool {
         let mut skip = false;
 
@@ -133,6 +313,32 @@ impl DeviceSender<'_> {
             skip = true;
         }
 
+        let global_denied_hazards = self
+            .controller
+            .privacy_policy
+            .global_denied_hazards(&request.hazards);
+
+        let local_denied_hazards = self
+            .controller
+            .privacy_policy
+            .local_denied_hazards(self.id, &request.hazards);
+
+        if !global_denied_hazards.is_empty() {
+            warn!(
+                "The {route} is skipped because it contains hazards which are not globally allowed: {:?}",
+                global_denied_hazards
+            );
+            skip = true;
+        }
+
+        if !local_denied_hazards.is_empty() {
+            warn!(
+                "The {route} is skipped because it contains hazards which are not allowed for the device: {:?}",
+                local_denied_hazards
+            );
+            skip = true;
+        }
+
         skip
     }
 }
@@ -151,6 +357,9 @@ pub struct Controller {
     discovery: Discovery,
     devices: Devices,
     privacy_policy: Policy,
+    in_flight_requests: Option<InFlightRequests>,
+    lifecycle: Lifecycle,
+    event_subscriber_config: EventSubscriberConfig,
 }
 
 impl Controller {
@@ -162,6 +371,9 @@ impl Controller {
             discovery,
             devices: Devices::new(),
             privacy_policy: Policy::init(),
+            in_flight_requests: None,
+            lifecycle: Lifecycle::new(),
+            event_subscriber_config: EventSubscriberConfig::new(),
         }
     }
 
@@ -176,6 +388,9 @@ impl Controller {
             discovery,
             devices,
             privacy_policy: Policy::init(),
+            in_flight_requests: None,
+            lifecycle: Lifecycle::new(),
+            event_subscriber_config: EventSubscriberConfig::new(),
         }
     }
 
@@ -187,6 +402,33 @@ impl Controller {
         self
     }
 
+    /// Enables request coalescing while constructing a [`Controller`].
+    ///
+    /// Concurrent identical idempotent requests to the same device share
+    /// a single in-flight request, and all callers receive the same response.
+    /// Non-idempotent requests are always sent separately.
+    #[must_use]
+    #[inline]
+    pub fn coalesce_requests(mut self) -> Self {
+        self.in_flight_requests = Some(InFlightRequests::default());
+        self
+    }
+
+    /// Defines an [`EventSubscriberConfig`] while constructing a
+    /// [`Controller`].
+    ///
+    /// The configuration is used by the event receiver tasks started with
+    /// [`Self::start_event_receivers`] and [`Self::subscribe_device_events`].
+    #[must_use]
+    #[inline]
+    pub fn event_subscriber_config(
+        mut self,
+        event_subscriber_config: EventSubscriberConfig,
+    ) -> Self {
+        self.event_subscriber_config = event_subscriber_config;
+        self
+    }
+
     /// Changes the [`Policy`].
     #[inline]
     pub fn change_policy(&mut self, privacy_policy: Policy) {
@@ -212,10 +454,31 @@ impl Controller {
     /// being sent and affect the returned response as well.
     #[inline]
     pub async fn discover(&mut self) -> Result<(), Error> {
-        self.devices = self.discovery.discover().await?;
+        let devices = self.discovery.discover().await?;
+        self.replace_devices(devices);
         Ok(())
     }
 
+    /// Returns a stream of the [`ControllerEvent`]s produced by the
+    /// [`Controller`].
+    ///
+    /// The stream multiplexes the lifecycle of all devices:
+    ///
+    /// - Devices discovered or departed, reported by [`Self::discover`]
+    /// - Devices becoming reachable or unreachable, reported each time
+    ///   a request is sent to a device and its outcome changes
+    /// - Events received by the tasks started with
+    ///   [`Self::start_event_receivers`]
+    ///
+    /// Only the events produced after this call are yielded. A stream
+    /// consumed too slowly skips the oldest events, and it ends once
+    /// the [`Controller`] has been shut down and dropped.
+    pub fn lifecycle_stream(
+        &self,
+    ) -> impl futures_util::Stream<Item = ControllerEvent> + Send + use<> {
+        self.lifecycle.subscribe()
+    }
+
     /// Starts asynchronous event receiver tasks for all [`Device`]s that
     /// support events.
     ///
@@ -232,6 +495,9 @@ impl Controller {
     ///
     /// When the [`Receiver`] is dropped, all tasks terminate automatically.
     ///
+    /// The tasks subscribe to the broker topics according to the
+    /// [`EventSubscriberConfig`] of the [`Controller`].
+    ///
     /// # Errors
     ///
     /// - No event receiver tasks has started
@@ -254,7 +520,14 @@ impl Controller {
                 continue;
             };
 
-            let _handle = EventsRunner::run_global_subscriber(events, id, tx.clone()).await?;
+            let _handle = EventsRunner::run_global_subscriber(
+                events,
+                id,
+                tx.clone(),
+                self.lifecycle.sender(),
+                &self.event_subscriber_config,
+            )
+            .await?;
 
             started_count += 1;
         }
@@ -269,6 +542,114 @@ impl Controller {
         Ok(rx)
     }
 
+    /// Starts an asynchronous event receiver task dedicated to the
+    /// [`Device`] with the given identifier.
+    ///
+    /// Unlike [`Self::start_event_receivers`], which multiplexes all devices
+    /// into a single channel, the returned [`broadcast::Receiver`] only
+    /// yields the events of the given device.
+    ///
+    /// The `capacity` parameter specifies how many events the channel can
+    /// hold. When the channel is full, the oldest events are dropped
+    /// and the receiver is notified of the skipped ones.
+    ///
+    /// # Errors
+    ///
+    /// - The device with the given identifier does not exist
+    /// - The device does not support events
+    /// - The event receiver task of the device has already been started
+    /// - An error occurred while subscribing to the broker topic of the device
+    pub async fn subscribe_device_events(
+        &mut self,
+        id: usize,
+        capacity: usize,
+    ) -> Result<broadcast::Receiver<ToscaEvents>, Error> {
+        let device = self.devices.0.get_mut(id).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Events,
+                format!("No device with identifier `{id}` found"),
+            )
+        })?;
+        device
+            .start_event_receiver_with_config(id, capacity, &self.event_subscriber_config)
+            .await
+    }
+
+    /// Registers the device listening on the given address, without
+    /// running a discovery.
+    ///
+    /// The device description is retrieved from the device address and
+    /// the device is appended to [`Devices`], using its address as name.
+    ///
+    /// Returns the identifier of the registered device.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the device cannot be contacted or if its
+    /// description is not valid.
+    pub async fn add_device_by_address(&mut self, address: SocketAddr) -> Result<usize, Error> {
+        let complete_address = build_device_address("http", &address.ip(), address.port());
+
+        let response = reqwest::Client::new()
+            .get(&complete_address)
+            .header("Connection", "close")
+            .send()
+            .await?;
+        let device_desc: DeviceDescription = decode_json(&response.bytes().await?)?;
+
+        let network_info = NetworkInformation::new(
+            address.to_string(),
+            HashSet::from([address.ip()]),
+            address.port(),
+            HashMap::from([("scheme".into(), "http".into())]),
+            complete_address,
+        );
+
+        let device = device_from_description(device_desc, network_info);
+        let id = self.devices.len();
+        self.lifecycle
+            .device_added(id, device.network_info().name.clone());
+        self.devices.add(device);
+
+        Ok(id)
+    }
+
+    /// Retrieves again the description of the [`Device`] with the given
+    /// identifier, updating its routes and events while preserving its
+    /// identifier and network information.
+    ///
+    /// Useful when a device changes its routes, for example after
+    /// a firmware update, without running a new discovery.
+    ///
+    /// A running event receiver of the device is stopped, so it must be
+    /// started again.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the device does not exist, if it cannot be
+    /// contacted, or if its description is not valid.
+    pub async fn refresh_device(&mut self, id: usize) -> Result<(), Error> {
+        let device = self.devices.get(id).ok_or_else(|| {
+            sender_error(format!(
+                "Error in retrieving the device with identifier {id}."
+            ))
+        })?;
+        let network_info = device.network_info().clone();
+
+        let response = reqwest::Client::new()
+            .get(&network_info.last_reachable_address)
+            .header("Connection", "close")
+            .send()
+            .await?;
+        let device_desc: DeviceDescription = decode_json(&response.bytes().await?)?;
+
+        let device = device_from_description(device_desc, network_info);
+        let previous_device = std::mem::replace(&mut self.devices.0[id], device);
+        stop_event_task(previous_device).await;
+
+        Ok(())
+    }
+
     /// Returns an immutable reference to [`Devices`].
     #[must_use]
     pub const fn devices(&self) -> &Devices {
@@ -281,6 +662,38 @@ impl Controller {
         &mut self.devices
     }
 
+    /// Returns an iterator over the [`Device`]s of the given [`DeviceKind`],
+    /// together with their identifiers.
+    ///
+    /// The identifiers are the ones of the whole [`Devices`] collection,
+    /// so they can be passed to [`Self::device`].
+    pub fn devices_of_kind(&self, kind: DeviceKind) -> impl Iterator<Item = (usize, &Device)> {
+        self.devices
+            .iter()
+            .enumerate()
+            .filter(move |(_, device)| device.description().kind.matches(&kind))
+    }
+
+    /// Returns, for each [`Hazard`] found in the device routes, the number
+    /// of [`Device`]s presenting it.
+    ///
+    /// A hazard declared by more routes of the same device is counted once
+    /// for that device.
+    #[must_use]
+    pub fn hazard_report(&self) -> HashMap<Hazard, usize> {
+        let mut report = HashMap::new();
+        for device in &self.devices {
+            let hazards = device
+                .routes()
+                .flat_map(|(_, _, hazards)| hazards.iter().copied())
+                .collect::<HashSet<Hazard>>();
+            for hazard in hazards {
+                *report.entry(hazard).or_insert(0) += 1;
+            }
+        }
+        report
+    }
+
     /// Builds a [`DeviceSender`] for the [`Device`] with the given identifier.
     ///
     /// # Errors
@@ -304,6 +717,135 @@ impl Controller {
         })
     }
 
+    /// Builds a [`DeviceSender`] for the [`Device`] with the given name.
+    ///
+    /// The name is compared, case-insensitively, against both the full
+    /// `mDNS` device name and its instance part, which is the label before
+    /// the first dot (e.g. `light` for `light._tosca._tcp.local.`).
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if no devices match the given name or if more
+    /// than one device matches it.
+    pub fn device_by_name(&self, name: &str) -> Result<DeviceSender<'_>, Error> {
+        let mut matches = self.devices.iter().enumerate().filter(|(_, device)| {
+            let full_name = device.network_info().name.as_str();
+            let instance_name = full_name.split('.').next().unwrap_or(full_name);
+            full_name.eq_ignore_ascii_case(name) || instance_name.eq_ignore_ascii_case(name)
+        });
+
+        let (id, device) = matches.next().ok_or_else(|| {
+            sender_error(format!(
+                "Error in retrieving the device with name `{name}`."
+            ))
+        })?;
+
+        if matches.next().is_some() {
+            return Err(sender_error(format!(
+                "More than one device matches the name `{name}`."
+            )));
+        }
+
+        Ok(DeviceSender {
+            controller: self,
+            device,
+            id,
+        })
+    }
+
+    /// Retrieves the [`InfoResponse`] of all devices concurrently.
+    ///
+    /// Each device is queried through its info route. When a device exposes
+    /// more than one info route, the first one in alphabetical order is used,
+    /// while devices without an info route are skipped.
+    ///
+    /// At most four requests are sent at the same time, and each request
+    /// is subject to the [`Policy`] rules.
+    ///
+    /// Results are paired with their device identifiers and sorted
+    /// by them.
+    pub async fn load_all_info(&self) -> Vec<(usize, Result<InfoResponse, Error>)> {
+        let info_routes = self
+            .devices
+            .iter()
+            .enumerate()
+            .filter_map(|(id, device)| {
+                device
+                    .requests_info()
+                    .into_iter()
+                    .filter(|request_info| request_info.response_kind == ResponseKind::Info)
+                    .map(|request_info| request_info.route)
+                    .min()
+                    .map(|route| (id, route))
+            })
+            .collect::<Vec<(usize, &str)>>();
+
+        let mut results = stream::iter(info_routes)
+            .map(|(id, route)| async move { (id, self.load_info(id, route).await) })
+            .buffer_unordered(MAX_CONCURRENT_INFO_REQUESTS)
+            .collect::<Vec<_>>()
+            .await;
+
+        results.sort_by_key(|(id, _)| *id);
+        results
+    }
+
+    /// Sends a request to the given route of all devices concurrently,
+    /// optionally with the given [`ParametersValues`].
+    ///
+    /// Devices which do not expose the route are skipped, while each request
+    /// is subject to the [`Policy`] rules of its device.
+    ///
+    /// Results are paired with their device identifiers and sorted
+    /// by them.
+    pub async fn broadcast(
+        &self,
+        route: &str,
+        parameters: Option<&ParametersValues<'_>>,
+    ) -> Vec<(usize, Result<Response, Error>)> {
+        let requests = self
+            .devices
+            .iter()
+            .enumerate()
+            .filter(|(_, device)| device.request(route).is_some())
+            .map(
+                |(id, _)| async move { (id, self.broadcast_request(id, route, parameters).await) },
+            );
+
+        future::join_all(requests).await
+    }
+
+    fn replace_devices(&mut self, devices: Devices) {
+        self.lifecycle.devices_changed(&self.devices, &devices);
+        self.devices = devices;
+    }
+
+    async fn broadcast_request(
+        &self,
+        id: usize,
+        route: &str,
+        parameters: Option<&ParametersValues<'_>>,
+    ) -> Result<Response, Error> {
+        let device_sender = self.device(id)?;
+        let request_sender = device_sender.request(route)?;
+        match parameters {
+            Some(parameters) => request_sender.send_with_parameters(parameters).await,
+            None => request_sender.send().await,
+        }
+    }
+
+    async fn load_info(&self, id: usize, route: &str) -> Result<InfoResponse, Error> {
+        match self.device(id)?.request(route)?.send().await? {
+            Response::InfoBody(body) => body.parse_body().await,
+            Response::Skipped => Err(sender_error(format!(
+                "The {route} of the device with identifier {id} is skipped by the privacy policy."
+            ))),
+            _ => Err(sender_error(format!(
+                "The {route} of the device with identifier {id} is not an info route."
+            ))),
+        }
+    }
+
     /// Shuts down the [`Controller`], stopping all asynchronous tasks and
     /// releasing all associated resources.
     ///
@@ -314,17 +856,22 @@ impl Controller {
     pub async fn shutdown(self) {
         // Stop all events tasks.
         for device in self.devices {
-            if let Some(events) = device.events {
-                // Stop the infinite loop
-                events.cancellation_token.cancel();
-            }
+            stop_event_task(device).await;
+        }
+    }
+}
 
-            if let Some(event_handle) = device.event_handle {
-                // Await the task.
-                if let Err(e) = event_handle.await {
-                    error!("Failed to await the event task: {e}");
-                }
-            }
+// Stops the event task of a device, if any.
+async fn stop_event_task(device: Device) {
+    if let Some(events) = device.events {
+        // Stop the infinite loop
+        events.cancellation_token.cancel();
+    }
+
+    if let Some(event_handle) = device.event_handle {
+        // Await the task.
+        if let Err(e) = event_handle.await {
+            error!("Failed to await the event task: {e}");
         }
     }
 }
@@ -332,12 +879,17 @@ impl Controller {
 #[cfg(test)]
 mod tests {
     use std::fmt::Debug;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
 
     use tracing::warn;
 
-    use tosca::hazards::{Hazard, Hazards};
+    use tosca::device::{DeviceKind, DeviceMetrics};
+    use tosca::economy::{Cost, CostTimespan, Costs, Economy};
+    use tosca::hazards::{Category, Hazard, Hazards};
     use tosca::parameters::ParametersValues;
-    use tosca::response::{OkResponse, SerialResponse};
+    use tosca::response::{InfoResponse, OkResponse, ResponseKind, SerialResponse};
+    use tosca::route::{Route, RouteConfigs};
 
     use serde::{Serialize, de::DeserializeOwned};
     use serde_json::json;
@@ -345,15 +897,20 @@ mod tests {
     use serial_test::serial;
 
     use crate::device::Devices;
-    use crate::error::Error;
+    use crate::error::{Error, ErrorKind};
     use crate::policy::Policy;
     use crate::response::Response;
 
     use crate::device::tests::{create_light, create_unknown};
     use crate::discovery::tests::configure_discovery;
-    use crate::tests::{Brightness, check_function_with_device};
+    use crate::tests::{
+        Brightness, check_function_with_device, counting_server, flaky_server,
+        json_sequence_server, json_server, local_device, local_light_with_toggle,
+    };
 
-    use super::{Controller, DeviceSender, RequestSender, sender_error};
+    use super::{
+        Controller, DeviceSender, EventSubscriberConfig, Lifecycle, RequestSender, sender_error,
+    };
 
     #[test]
     fn empty_controller() {
@@ -365,6 +922,9 @@ mod tests {
                 discovery: configure_discovery(),
                 devices: Devices::new(),
                 privacy_policy: Policy::init(),
+                in_flight_requests: None,
+                lifecycle: Lifecycle::new(),
+                event_subscriber_config: EventSubscriberConfig::new(),
             }
         );
 
@@ -384,10 +944,860 @@ mod tests {
                 discovery: configure_discovery(),
                 devices: Devices::from_devices(vec![create_light(), create_unknown()]),
                 privacy_policy: Policy::init(),
+                in_flight_requests: None,
+                lifecycle: Lifecycle::new(),
+                event_subscriber_config: EventSubscriberConfig::new(),
             }
         );
     }
 
+    #[test]
+    fn devices_of_kind_controller() {
+        let controller = Controller::from_devices(
+            configure_discovery(),
+            Devices::from_devices(vec![create_unknown(), create_light()]),
+        );
+
+        let lights = controller
+            .devices_of_kind(DeviceKind::Light)
+            .collect::<Vec<_>>();
+        assert_eq!(lights, vec![(1, &create_light())]);
+
+        // The identifier refers to the whole collection of devices.
+        assert_eq!(controller.device(lights[0].0).unwrap().id, 1);
+    }
+
+    #[test]
+    fn hazard_report_controller() {
+        let controller = Controller::from_devices(
+            configure_discovery(),
+            Devices::from_devices(vec![create_light(), create_unknown()]),
+        );
+
+        let report = controller.hazard_report();
+
+        // Both devices consume electric energy, even if the light declares
+        // this hazard on two routes.
+        assert_eq!(report.get(&Hazard::ElectricEnergyConsumption), Some(&2));
+        assert_eq!(report.get(&Hazard::FireHazard), Some(&1));
+        assert_eq!(report.get(&Hazard::LogEnergyConsumption), Some(&1));
+        assert_eq!(report.get(&Hazard::VideoDisplay), Some(&1));
+        assert_eq!(report.get(&Hazard::VideoRecordAndStore), Some(&1));
+        assert_eq!(report.get(&Hazard::TakeDeviceScreenshots), Some(&1));
+        assert_eq!(report.get(&Hazard::TakePictures), Some(&1));
+        assert_eq!(report.len(), 7);
+
+        // No devices, no hazards.
+        assert!(
+            Controller::new(configure_discovery())
+                .hazard_report()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn device_by_name_controller() {
+        let controller = Controller::from_devices(
+            configure_discovery(),
+            Devices::from_devices(vec![create_light(), create_unknown()]),
+        );
+
+        // Instance names are matched case-insensitively.
+        assert_eq!(controller.device_by_name("Device-Name1").unwrap().id, 0);
+        assert_eq!(controller.device_by_name("device-name2").unwrap().id, 1);
+
+        // Full names are matched as well.
+        assert_eq!(
+            controller
+                .device_by_name("device-name2._tosca._tcp.local.")
+                .unwrap()
+                .id,
+            1
+        );
+
+        assert_eq!(
+            controller.device_by_name("device-name3"),
+            Err(sender_error(
+                "Error in retrieving the device with name `device-name3`."
+            ))
+        );
+
+        // Two devices with the same name.
+        let controller = Controller::from_devices(
+            configure_discovery(),
+            Devices::from_devices(vec![create_light(), create_light()]),
+        );
+
+        assert_eq!(
+            controller.device_by_name("device-name1"),
+            Err(sender_error(
+                "More than one device matches the name `device-name1`."
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn coalesce_requests_controller() {
+        let (port, counter) = counting_server(Duration::from_millis(200));
+
+        let device = local_device(
+            "mock",
+            port,
+            "mock",
+            RouteConfigs::new().insert(Route::get("Info", "/info").serialize_data()),
+        );
+
+        let controller =
+            Controller::from_devices(configure_discovery(), Devices::from_devices(vec![device]))
+                .coalesce_requests();
+
+        let device_sender = controller.device(0).unwrap();
+        let first = device_sender.request("/info").unwrap();
+        let second = device_sender.request("/info").unwrap();
+
+        let (first, second) = tokio::join!(first.send(), second.send());
+
+        // Both callers receive the same response from a single request.
+        for response in [first, second] {
+            let Response::OkBody(body) = response.unwrap() else {
+                panic!("Expected an `OkResponse`");
+            };
+            assert_eq!(body.parse_body().await.unwrap(), OkResponse::ok());
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        // Once completed, a new request is sent again.
+        let _ = device_sender
+            .request("/info")
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn load_all_info_controller() {
+        let metrics = DeviceMetrics::with_economy(Economy::init_with_costs(Costs::init(
+            Cost::new(100, CostTimespan::Week),
+        )));
+        let body = serde_json::to_string(&InfoResponse::new(metrics.clone())).unwrap();
+
+        let info_device = |name, body| {
+            let (port, _) = json_server(Duration::ZERO, body);
+            local_device(
+                name,
+                port,
+                "/mock",
+                RouteConfigs::new().insert(
+                    Route::get("Info", "/info")
+                        .serialize_data()
+                        .change_response_kind(ResponseKind::Info),
+                ),
+            )
+        };
+
+        let devices = vec![
+            info_device("first", body.clone()),
+            local_device("without-info", 1, "/mock", RouteConfigs::new()),
+            info_device("second", body),
+        ];
+
+        let controller =
+            Controller::from_devices(configure_discovery(), Devices::from_devices(devices));
+
+        let results = controller.load_all_info().await;
+
+        // The device without an info route is skipped.
+        assert_eq!(
+            results,
+            vec![
+                (0, Ok(InfoResponse::new(metrics.clone()))),
+                (2, Ok(InfoResponse::new(metrics))),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn send_with_retry_controller() {
+        let controller = |port| {
+            let device = local_device(
+                "light",
+                port,
+                "/light",
+                RouteConfigs::new().insert(Route::put("On", "/on").serialize_data()),
+            );
+            Controller::from_devices(configure_discovery(), Devices::from_devices(vec![device]))
+        };
+
+        // The server fails twice, then succeeds.
+        let (port, counter) = flaky_server(2);
+        let controller_ok = controller(port);
+        let device_sender = controller_ok.device(0).unwrap();
+        let request_sender = device_sender.request("/on").unwrap();
+
+        assert!(matches!(
+            request_sender
+                .send_with_retry(3, Duration::from_millis(10))
+                .await,
+            Ok(Response::OkBody(_))
+        ));
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+
+        // The last error is returned when all attempts fail.
+        let (port, counter) = flaky_server(2);
+        let controller_err = controller(port);
+        let device_sender = controller_err.device(0).unwrap();
+        let request_sender = device_sender.request("/on").unwrap();
+
+        let Err(error) = request_sender
+            .send_with_retry(2, Duration::from_millis(10))
+            .await
+        else {
+            panic!("The request must fail");
+        };
+        assert!(error.is_transient());
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+        // A non-idempotent request is not sent again when the connection
+        // drops after sending it.
+        let (port, counter) = flaky_server(1);
+        let device = local_device(
+            "light",
+            port,
+            "/light",
+            RouteConfigs::new().insert(Route::post("Toggle", "/toggle").serialize_data()),
+        );
+        let controller_post =
+            Controller::from_devices(configure_discovery(), Devices::from_devices(vec![device]));
+        let device_sender = controller_post.device(0).unwrap();
+        let request_sender = device_sender.request("/toggle").unwrap();
+        assert!(!request_sender.request.idempotent);
+
+        assert!(
+            request_sender
+                .send_with_retry(3, Duration::from_millis(10))
+                .await
+                .is_err()
+        );
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        // A non-idempotent request is sent again when the connection fails.
+        let unreachable_port = std::net::TcpListener::bind((std::net::Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let device = local_device(
+            "light",
+            unreachable_port,
+            "/light",
+            RouteConfigs::new().insert(Route::post("Toggle", "/toggle").serialize_data()),
+        );
+        let controller_unreachable =
+            Controller::from_devices(configure_discovery(), Devices::from_devices(vec![device]));
+        let device_sender = controller_unreachable.device(0).unwrap();
+        let request_sender = device_sender.request("/toggle").unwrap();
+
+        let Err(error) = request_sender
+            .send_with_retry(2, Duration::from_millis(10))
+            .await
+        else {
+            panic!("The request must fail");
+        };
+        assert!(error.is_connect());
+    }
+
+    #[tokio::test]
+    async fn persisted_devices_controller() {
+        use std::net::Ipv4Addr;
+
+        use tosca::events::{BrokerData, Event, Events, EventsDescription, Topic};
+
+        let (port, counter) = counting_server(Duration::ZERO);
+
+        let mut device = local_device(
+            "light",
+            port,
+            "/light",
+            RouteConfigs::new().insert(
+                Route::put("On", "/on")
+                    .with_hazard(Hazard::ElectricEnergyConsumption)
+                    .serialize_data(),
+            ),
+        );
+
+        let mut events = Events::empty();
+        events.add_bool_event(Event::bool("light"));
+        device.events = Some(crate::events::Events::new(EventsDescription::new(
+            BrokerData::new(Ipv4Addr::LOCALHOST.into(), 1883),
+            Topic::new("light".into()),
+            events,
+        )));
+
+        let json = Devices::from_devices(vec![device]).to_json().unwrap();
+        let devices = Devices::from_json(&json).unwrap();
+
+        // The events description is restored as well.
+        let restored = devices.get(0).unwrap();
+        assert_eq!(
+            restored.events_metadata().unwrap().topic,
+            Topic::new("light".into())
+        );
+        assert!(!restored.is_event_receiver_running());
+
+        let controller = Controller::from_devices(configure_discovery(), devices);
+
+        let device_sender = controller.device(0).unwrap();
+        let request_sender = device_sender.request("/on").unwrap();
+        assert!(matches!(
+            request_sender.send().await.unwrap(),
+            Response::OkBody(_)
+        ));
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn add_device_by_address_controller() {
+        use std::net::Ipv4Addr;
+
+        use tosca::device::{DeviceDescription, DeviceKindId};
+
+        let description = DeviceDescription::new(
+            DeviceKindId::from(&DeviceKind::Light),
+            "/light",
+            RouteConfigs::new().insert(Route::put("On", "/on").serialize_data()),
+            1,
+        );
+        let (port, counter) =
+            json_server(Duration::ZERO, serde_json::to_string(&description).unwrap());
+
+        let mut controller = Controller::from_devices(
+            configure_discovery(),
+            Devices::from_devices(vec![create_light()]),
+        );
+
+        let id = controller
+            .add_device_by_address((Ipv4Addr::LOCALHOST, port).into())
+            .await
+            .unwrap();
+        assert_eq!(id, 1);
+        assert_eq!(controller.devices_of_kind(DeviceKind::Light).count(), 2);
+
+        // The registered device can be contacted.
+        let device_sender = controller.device(id).unwrap();
+        let request_sender = device_sender.request("/on").unwrap();
+        assert!(matches!(
+            request_sender.send().await.unwrap(),
+            Response::OkBody(_)
+        ));
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+        // A device which cannot be contacted is not registered.
+        let unreachable_port = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        assert!(
+            controller
+                .add_device_by_address((Ipv4Addr::LOCALHOST, unreachable_port).into())
+                .await
+                .is_err()
+        );
+        assert_eq!(controller.devices().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn refresh_device_controller() {
+        use std::net::Ipv4Addr;
+
+        use tosca::device::{DeviceDescription, DeviceKindId};
+
+        let description = |route_configs| {
+            serde_json::to_string(&DeviceDescription::new(
+                DeviceKindId::from(&DeviceKind::Light),
+                "/light",
+                route_configs,
+                1,
+            ))
+            .unwrap()
+        };
+
+        // The device gains the `/off` route after the first description.
+        let port = json_sequence_server(vec![
+            description(RouteConfigs::new().insert(Route::put("On", "/on").serialize_data())),
+            description(
+                RouteConfigs::new()
+                    .insert(Route::put("On", "/on").serialize_data())
+                    .insert(Route::put("Off", "/off").serialize_data()),
+            ),
+        ]);
+
+        let mut controller = Controller::from_devices(
+            configure_discovery(),
+            Devices::from_devices(vec![create_light()]),
+        );
+
+        let id = controller
+            .add_device_by_address((Ipv4Addr::LOCALHOST, port).into())
+            .await
+            .unwrap();
+        assert!(!controller.devices().get(id).unwrap().route_exists("/off"));
+
+        controller.refresh_device(id).await.unwrap();
+
+        // The refreshed routes appear under the same identifier.
+        let device = controller.devices().get(id).unwrap();
+        assert!(device.route_exists("/on"));
+        assert!(device.route_exists("/off"));
+        assert_eq!(device.network_info().name, format!("127.0.0.1:{port}"));
+        assert_eq!(controller.devices().len(), 2);
+
+        // A non-existent device.
+        assert!(controller.refresh_device(2).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn refresh_unreachable_device_controller() {
+        let unreachable_port = std::net::TcpListener::bind((std::net::Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let device = local_device(
+            "light",
+            unreachable_port,
+            "/light",
+            RouteConfigs::new().insert(Route::put("On", "/on").serialize_data()),
+        );
+        let mut controller =
+            Controller::from_devices(configure_discovery(), Devices::from_devices(vec![device]));
+
+        // A device which no longer responds is left untouched.
+        assert!(controller.refresh_device(0).await.is_err());
+        assert!(controller.devices().get(0).unwrap().route_exists("/on"));
+    }
+
+    #[tokio::test]
+    async fn timeout_controller() {
+        let (port, _) = counting_server(Duration::from_secs(2));
+
+        let device = local_device(
+            "light",
+            port,
+            "/light",
+            RouteConfigs::new().insert(Route::put("On", "/on").serialize_data()),
+        );
+        let controller =
+            Controller::from_devices(configure_discovery(), Devices::from_devices(vec![device]));
+
+        let device_sender = controller.device(0).unwrap();
+        let request_sender = device_sender
+            .request("/on")
+            .unwrap()
+            .timeout(Duration::from_millis(200));
+
+        let start = std::time::Instant::now();
+        let Err(error) = request_sender.send().await else {
+            panic!("The request must time out");
+        };
+
+        assert_eq!(error.kind(), ErrorKind::Timeout);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn broadcast_controller() {
+        let delay = Duration::from_millis(500);
+        let (port, counter) = counting_server(delay);
+
+        let light = |name| {
+            local_device(
+                name,
+                port,
+                "/light",
+                RouteConfigs::new()
+                    .insert(Route::put("On", "/on").serialize_data())
+                    .insert(Route::put("Off", "/off").serialize_data()),
+            )
+        };
+
+        let devices = vec![
+            light("first"),
+            local_device("without-on", 1, "/mock", RouteConfigs::new()),
+            light("second"),
+            light("blocked"),
+        ];
+
+        let controller =
+            Controller::from_devices(configure_discovery(), Devices::from_devices(devices))
+                .policy(Policy::init().block_device_route(3, "/on"));
+
+        let start = std::time::Instant::now();
+        let results = controller.broadcast("/on", None).await;
+
+        // Both requests are issued concurrently.
+        assert!(start.elapsed() < delay * 2);
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+        // The device without the route is skipped, while the blocked one
+        // follows the privacy policy.
+        assert_eq!(
+            results.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![0, 2, 3]
+        );
+        assert!(matches!(results[0].1, Ok(Response::OkBody(_))));
+        assert!(matches!(results[1].1, Ok(Response::OkBody(_))));
+        assert!(matches!(results[2].1, Ok(Response::Skipped)));
+    }
+
+    #[cfg(feature = "stream")]
+    #[tokio::test]
+    async fn open_event_stream_controller() {
+        use futures_util::StreamExt;
+
+        use tosca::events::{Event, Events, sse_frame};
+
+        let mut events = Events::empty();
+        events.add_bool_event(Event::bool("light"));
+        let first = events.clone();
+        let first_frame = sse_frame(&serde_json::to_vec(&first).unwrap());
+
+        let _ = events.update_bool_value_by_name("light", true);
+        let second = events;
+        let second_frame = sse_frame(&serde_json::to_vec(&second).unwrap());
+
+        // The first frame is split across two chunks, and a comment
+        // is sent between the two frames.
+        let port = crate::tests::sse_server(vec![
+            first_frame[..10].to_vec(),
+            first_frame[10..].to_vec(),
+            b": keep-alive\n\n".to_vec(),
+            second_frame,
+        ]);
+
+        let device = local_device("mock", port, "/mock", RouteConfigs::new());
+        let controller =
+            Controller::from_devices(configure_discovery(), Devices::from_devices(vec![device]));
+
+        let mut stream = std::pin::pin!(controller.device(0).unwrap().open_event_stream());
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), first);
+        assert_eq!(stream.next().await.unwrap().unwrap(), second);
+    }
+
+    #[tokio::test]
+    async fn subscribe_device_events_controller() {
+        use std::net::{Ipv4Addr, TcpListener};
+
+        use tosca::events::{BrokerData, Event, Events, EventsDescription, Topic};
+
+        // A closed port, so that the subscriber never reaches a broker.
+        let port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let mut events = Events::empty();
+        events.add_bool_event(Event::bool("light"));
+
+        let mut device = local_device("mock", port, "/mock", RouteConfigs::new());
+        device.events = Some(crate::events::Events::new(EventsDescription::new(
+            BrokerData::new(Ipv4Addr::LOCALHOST.into(), port),
+            Topic::new("mock".into()),
+            events,
+        )));
+
+        let mut controller = Controller::from_devices(
+            configure_discovery(),
+            Devices::from_devices(vec![device, create_light()]),
+        );
+
+        let receiver = controller.subscribe_device_events(0, 8).await;
+        assert!(receiver.is_ok());
+        assert!(
+            controller
+                .devices()
+                .get(0)
+                .unwrap()
+                .is_event_receiver_running()
+        );
+
+        // The receiver task of a device can be started only once.
+        assert_eq!(
+            controller
+                .subscribe_device_events(0, 8)
+                .await
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Events
+        );
+
+        // A device without events cannot be subscribed.
+        assert_eq!(
+            controller
+                .subscribe_device_events(1, 8)
+                .await
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Events
+        );
+
+        // A device which does not exist cannot be subscribed.
+        assert_eq!(
+            controller
+                .subscribe_device_events(2, 8)
+                .await
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Events
+        );
+
+        drop(receiver);
+        controller.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn allow_only_routes_controller() {
+        let policy = Policy::init().allow_only_routes(0, &["/info"]);
+
+        let controller = Controller::from_devices(
+            configure_discovery(),
+            Devices::from_devices(vec![create_light()]),
+        )
+        .policy(policy);
+
+        let device_sender = controller.device(0).unwrap();
+
+        // The `/on` route is skipped because it is not allowlisted.
+        let request_sender = device_sender.request("/on").unwrap();
+        assert!(request_sender.skip);
+        assert!(matches!(
+            request_sender.send().await.unwrap(),
+            Response::Skipped
+        ));
+    }
+
+    #[tokio::test]
+    async fn block_device_route_controller() {
+        let (port, _) = counting_server(Duration::ZERO);
+
+        let device = local_device(
+            "light",
+            port,
+            "/light",
+            RouteConfigs::new()
+                .insert(Route::put("On", "/on").serialize_data())
+                .insert(Route::put("Off", "/off").serialize_data()),
+        );
+
+        let controller =
+            Controller::from_devices(configure_discovery(), Devices::from_devices(vec![device]))
+                .policy(Policy::init().block_device_route(0, "/off"));
+
+        let device_sender = controller.device(0).unwrap();
+
+        // The `/off` route is skipped even without hazards.
+        let request_sender = device_sender.request("/off").unwrap();
+        assert!(request_sender.skip);
+        assert!(matches!(
+            request_sender.send().await.unwrap(),
+            Response::Skipped
+        ));
+
+        // The `/on` route is still sent.
+        let request_sender = device_sender.request("/on").unwrap();
+        assert!(!request_sender.skip);
+        assert!(matches!(
+            request_sender.send().await.unwrap(),
+            Response::OkBody(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn lifecycle_stream_controller() {
+        use futures_util::StreamExt;
+
+        use crate::lifecycle::ControllerEvent;
+
+        let (port, _) = counting_server(Duration::ZERO);
+        // A port without any server listening on it.
+        let unreachable_port = std::net::TcpListener::bind((std::net::Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let device = |name: &str, port| {
+            local_device(
+                name,
+                port,
+                "/light",
+                RouteConfigs::new().insert(Route::put("On", "/on").serialize_data()),
+            )
+        };
+
+        let mut controller = Controller::from_devices(
+            configure_discovery(),
+            Devices::from_devices(vec![device("old", port), device("kept", port)]),
+        );
+
+        let mut stream = std::pin::pin!(controller.lifecycle_stream());
+
+        // A discovery change.
+        controller.replace_devices(Devices::from_devices(vec![
+            device("kept", port),
+            device("new", unreachable_port),
+        ]));
+
+        assert_eq!(
+            stream.next().await,
+            Some(ControllerEvent::DeviceDeparted { name: "old".into() })
+        );
+        assert_eq!(
+            stream.next().await,
+            Some(ControllerEvent::DeviceDiscovered {
+                device_id: 1,
+                name: "new".into()
+            })
+        );
+
+        // Status changes, reported only when they change.
+        for _ in 0..2 {
+            let device_sender = controller.device(0).unwrap();
+            let _ = device_sender.request("/on").unwrap().send().await.unwrap();
+        }
+        let device_sender = controller.device(1).unwrap();
+        assert!(device_sender.request("/on").unwrap().send().await.is_err());
+
+        assert_eq!(
+            stream.next().await,
+            Some(ControllerEvent::DeviceReachable { device_id: 0 })
+        );
+        assert_eq!(
+            stream.next().await,
+            Some(ControllerEvent::DeviceUnreachable { device_id: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn implication_policy_controller() {
+        let controller = |policy| {
+            let video_route = Route::get("Video", "/video").with_hazard(Hazard::VideoDisplay);
+            let device = local_device(
+                "camera",
+                1,
+                "/camera",
+                RouteConfigs::new().insert(video_route.serialize_data()),
+            );
+            Controller::from_devices(configure_discovery(), Devices::from_devices(vec![device]))
+                .policy(policy)
+        };
+
+        // Without implication, the narrower hazard is not blocked.
+        let policy = Policy::new(Hazards::init(Hazard::AudioVideoDisplay));
+        let controller_without = controller(policy);
+        let device_sender = controller_without.device(0).unwrap();
+        let request_sender = device_sender.request("/video").unwrap();
+        assert!(!request_sender.skip);
+
+        // The route declares only a hazard implied by the blocked one.
+        let policy = Policy::new(Hazards::init(Hazard::AudioVideoDisplay)).with_implication();
+        let controller_with = controller(policy);
+        let device_sender = controller_with.device(0).unwrap();
+        let request_sender = device_sender.request("/video").unwrap();
+        assert!(request_sender.skip);
+        assert!(matches!(
+            request_sender.send().await.unwrap(),
+            Response::Skipped
+        ));
+    }
+
+    #[tokio::test]
+    async fn categories_policy_controller() {
+        let usage_route = Route::get("Usage", "/usage").with_hazard(Hazard::LogUsageTime);
+        let device = local_device(
+            "meter",
+            1,
+            "/meter",
+            RouteConfigs::new().insert(usage_route.serialize_data()),
+        );
+
+        let controller =
+            Controller::from_devices(configure_discovery(), Devices::from_devices(vec![device]))
+                .policy(Policy::block_on_categories(&[Category::Privacy]));
+
+        let device_sender = controller.device(0).unwrap();
+        let request_sender = device_sender.request("/usage").unwrap();
+        assert!(request_sender.skip);
+        assert!(matches!(
+            request_sender.send().await.unwrap(),
+            Response::Skipped
+        ));
+    }
+
+    #[tokio::test]
+    async fn allow_only_policy_controller() {
+        let (port, _) = counting_server(Duration::ZERO);
+
+        let energy_route = Route::put("On", "/on").with_hazard(Hazard::ElectricEnergyConsumption);
+        let usage_route = Route::get("Usage", "/usage").with_hazard(Hazard::LogUsageTime);
+        let device = local_device(
+            "meter",
+            port,
+            "/meter",
+            RouteConfigs::new()
+                .insert(energy_route.serialize_data())
+                .insert(usage_route.serialize_data()),
+        );
+
+        let controller =
+            Controller::from_devices(configure_discovery(), Devices::from_devices(vec![device]))
+                .policy(Policy::allow_only(Hazards::init(
+                    Hazard::ElectricEnergyConsumption,
+                )));
+
+        let device_sender = controller.device(0).unwrap();
+
+        // The hazard of the `/usage` route is not allowed.
+        let request_sender = device_sender.request("/usage").unwrap();
+        assert!(request_sender.skip);
+        assert!(matches!(
+            request_sender.send().await.unwrap(),
+            Response::Skipped
+        ));
+
+        // The hazard of the `/on` route is allowed, hence it is sent.
+        let request_sender = device_sender.request("/on").unwrap();
+        assert!(!request_sender.skip);
+        assert!(matches!(
+            request_sender.send().await.unwrap(),
+            Response::OkBody(_)
+        ));
+
+        // A device allow list is evaluated on top of the global one.
+        let controller = Controller::from_devices(
+            configure_discovery(),
+            Devices::from_devices(vec![local_device(
+                "meter",
+                port,
+                "/meter",
+                RouteConfigs::new().insert(
+                    Route::put("On", "/on")
+                        .with_hazard(Hazard::ElectricEnergyConsumption)
+                        .serialize_data(),
+                ),
+            )]),
+        )
+        .policy(Policy::init().allow_device_only(0, Hazards::init(Hazard::LogUsageTime)));
+
+        let device_sender = controller.device(0).unwrap();
+        assert!(device_sender.request("/on").unwrap().skip);
+    }
+
     async fn check_ok_response_plain(device_sender: &DeviceSender<'_>, route: &str) {
         check_ok_response(device_sender, route, async move |request_sender| {
             request_sender.send().await
@@ -539,6 +1949,28 @@ mod tests {
         .await;
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    #[serial]
+    async fn test_send_with_telemetry() {
+        check_function_with_device(|| async {
+            let controller = Controller::from_devices(
+                configure_discovery(),
+                Devices::from_devices(vec![local_light_with_toggle()]),
+            );
+
+            let device_sender = controller.device(0).unwrap();
+            let request_sender = device_sender.request("/on").unwrap();
+
+            let (response, telemetry) = request_sender.send_with_telemetry().await.unwrap();
+
+            assert!(matches!(response, Response::OkBody(_)));
+            assert!(telemetry.first_byte > Duration::ZERO);
+            assert!(telemetry.total >= telemetry.first_byte);
+            assert!(telemetry.response_size.is_some_and(|size| size > 0));
+        })
+        .await;
+    }
+
     #[inline]
     async fn controller_without_policy() {
         // Create a controller.
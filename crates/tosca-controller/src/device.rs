@@ -1,17 +1,19 @@
 use std::collections::{HashMap, HashSet};
 use std::net::IpAddr;
+use std::time::{Duration, SystemTime};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use tokio::sync::broadcast::{self, Receiver};
 use tokio::task::JoinHandle;
 
-use tosca::device::{DeviceEnvironment, DeviceKindId};
+use tosca::device::{DeviceClock, DeviceEnvironment, DeviceKind, DeviceKindId};
 use tosca::events::{Events as ToscaEvents, EventsDescription};
-use tosca::route::RouteConfigs;
+use tosca::hazards::Hazards;
+use tosca::route::{RestKind, RouteConfigs};
 
 use crate::error::{Error, ErrorKind, Result};
-use crate::events::{Events, EventsRunner};
+use crate::events::{EventSubscriberConfig, Events, EventsRunner};
 use crate::request::{Request, RequestInfo, create_requests};
 
 pub(crate) fn build_device_address(scheme: &str, address: &IpAddr, port: u16) -> String {
@@ -22,7 +24,7 @@ pub(crate) fn build_device_address(scheme: &str, address: &IpAddr, port: u16) ->
 ///
 /// It contains all the necessary data to contact a `tosca` device within
 /// a network.
-#[derive(Debug, PartialEq, Clone, Serialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct NetworkInformation {
     /// Full device name.
     pub name: String,
@@ -31,12 +33,12 @@ pub struct NetworkInformation {
     /// Device Wi-Fi MAC address.
     ///
     /// If [`None`], the Wi-Fi MAC address is not present.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub wifi_mac: Option<[u8; 6]>,
     /// Device Ethernet MAC address.
     ///
     /// If [`None`], the Ethernet MAC address is not present.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ethernet_mac: Option<[u8; 6]>,
     /// The port on which the device is listening.
     pub port: u16,
@@ -87,7 +89,7 @@ impl NetworkInformation {
 /// Device description.
 ///
 /// All properties defining a device.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Description {
     /// Device kind.
     pub kind: DeviceKindId,
@@ -97,6 +99,7 @@ pub struct Description {
     pub main_route: String,
     /// Device description.
     #[cfg(feature = "metadata")]
+    #[serde(default)]
     pub description: Option<String>,
 }
 
@@ -127,8 +130,92 @@ impl Description {
     }
 }
 
+/// Differences between the capabilities of two [`Device`]s.
+///
+/// Routes are sorted alphabetically.
+#[derive(Debug, Default, PartialEq)]
+pub struct CapabilitiesDiff {
+    /// Routes exposed only by the compared device.
+    pub missing_in_other: Vec<String>,
+    /// Routes exposed only by the other device.
+    pub missing_in_self: Vec<String>,
+    /// Routes exposed by both devices, but with a different `REST` kind,
+    /// response kind, or parameters.
+    pub mismatched_routes: Vec<String>,
+}
+
+impl CapabilitiesDiff {
+    /// Checks whether the two devices expose the same capabilities.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.missing_in_other.is_empty()
+            && self.missing_in_self.is_empty()
+            && self.mismatched_routes.is_empty()
+    }
+}
+
+/// The time base of a device, used to align device timestamps to the
+/// wall-clock time.
+///
+/// Device timestamps are expressed in milliseconds elapsed since the
+/// device boot, so the boot time is all that is needed to convert them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeviceTimeBase {
+    boot_time: SystemTime,
+    synced: bool,
+}
+
+impl DeviceTimeBase {
+    /// Creates a [`DeviceTimeBase`] from a [`DeviceClock`] received by
+    /// the controller at the given time.
+    ///
+    /// When the device clock is synchronized, its boot time is used as is.
+    /// Otherwise, the boot time is estimated from the controller clock,
+    /// hence it is delayed by the time the clock took to be received.
+    #[must_use]
+    pub fn new(clock: &DeviceClock, received_at: SystemTime) -> Self {
+        match clock.boot_time_ms() {
+            Some(boot_time_ms) => Self {
+                boot_time: SystemTime::UNIX_EPOCH + Duration::from_millis(boot_time_ms),
+                synced: true,
+            },
+            None => Self {
+                boot_time: received_at
+                    .checked_sub(Duration::from_millis(clock.uptime_ms))
+                    .unwrap_or(SystemTime::UNIX_EPOCH),
+                synced: false,
+            },
+        }
+    }
+
+    /// Returns the device boot time.
+    #[must_use]
+    pub const fn boot_time(&self) -> SystemTime {
+        self.boot_time
+    }
+
+    /// Checks whether the time base comes from a synchronized device clock
+    /// rather than being estimated.
+    #[must_use]
+    pub const fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Converts a device timestamp, expressed in milliseconds elapsed
+    /// since the device boot, into an estimated wall-clock time.
+    #[must_use]
+    pub fn wall_clock(&self, timestamp_ms: u64) -> SystemTime {
+        self.boot_time + Duration::from_millis(timestamp_ms)
+    }
+}
+
 /// A `tosca` device.
-#[derive(Debug, Serialize)]
+///
+/// A device can be serialized and deserialized, hence it can be persisted
+/// and restored later. Its runtime state, such as a running event receiver,
+/// is not persisted.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Device {
     // Information needed to contact a device in a network.
     network_info: NetworkInformation,
@@ -139,7 +226,7 @@ pub struct Device {
     // All device events.
     //
     // If [`None`], the device does not support events.
-    #[serde(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) events: Option<Events>,
     // The join handle for the event task.
     #[serde(skip)]
@@ -234,6 +321,60 @@ impl Device {
         self.requests.get(route)
     }
 
+    /// Returns an iterator over the device routes, together with
+    /// their [`RestKind`] and [`Hazards`].
+    ///
+    /// Routes are returned in no particular order.
+    #[inline]
+    pub fn routes(&self) -> impl Iterator<Item = (&str, RestKind, &Hazards)> {
+        self.requests
+            .iter()
+            .map(|(route, request)| (route.as_str(), request.kind, &request.hazards))
+    }
+
+    /// Checks whether the given route exists on the device.
+    #[must_use]
+    #[inline]
+    pub fn route_exists(&self, route: &str) -> bool {
+        self.requests.contains_key(route)
+    }
+
+    /// Compares the routes and parameters of a [`Device`] with those of
+    /// another one, returning their [`CapabilitiesDiff`].
+    ///
+    /// Useful to verify whether a device can replace another one.
+    #[must_use]
+    pub fn diff_capabilities(&self, other: &Device) -> CapabilitiesDiff {
+        let mut diff = CapabilitiesDiff::default();
+
+        for (route, request) in &self.requests {
+            match other.requests.get(route) {
+                Some(other_request) => {
+                    if request.kind != other_request.kind
+                        || request.response_kind != other_request.response_kind
+                        || request.parameters_data != other_request.parameters_data
+                    {
+                        diff.mismatched_routes.push(route.clone());
+                    }
+                }
+                None => diff.missing_in_other.push(route.clone()),
+            }
+        }
+
+        diff.missing_in_self = other
+            .requests
+            .keys()
+            .filter(|route| !self.requests.contains_key(*route))
+            .cloned()
+            .collect();
+
+        diff.missing_in_other.sort();
+        diff.missing_in_self.sort();
+        diff.mismatched_routes.sort();
+
+        diff
+    }
+
     /// Checks if a [`Device`] supports events.
     #[must_use]
     pub const fn has_events(&self) -> bool {
@@ -275,6 +416,16 @@ impl Device {
         &mut self,
         id: usize,
         buffer_size: usize,
+    ) -> Result<Receiver<ToscaEvents>> {
+        self.start_event_receiver_with_config(id, buffer_size, &EventSubscriberConfig::default())
+            .await
+    }
+
+    pub(crate) async fn start_event_receiver_with_config(
+        &mut self,
+        id: usize,
+        buffer_size: usize,
+        config: &EventSubscriberConfig,
     ) -> Result<Receiver<ToscaEvents>> {
         if self.event_handle.is_some() {
             return Err(Error::new(
@@ -292,7 +443,7 @@ impl Device {
 
         let (tx, _) = broadcast::channel(buffer_size);
 
-        let handle = EventsRunner::run_device_subscriber(events, id, tx.clone()).await?;
+        let handle = EventsRunner::run_device_subscriber(events, id, tx.clone(), config).await?;
         self.event_handle = Some(handle);
 
         Ok(tx.subscribe())
@@ -315,7 +466,7 @@ impl Device {
 }
 
 /// A collection of [`Device`]s.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Devices(pub(crate) Vec<Device>);
 
 impl Default for Devices {
@@ -401,20 +552,69 @@ impl Devices {
     pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Device> {
         self.0.iter_mut()
     }
+
+    /// Serializes [`Devices`] into a `JSON` document.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a [`Device`] cannot be serialized.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| {
+            Error::new(
+                ErrorKind::Devices,
+                format!("Error in serializing the devices: {e}"),
+            )
+        })
+    }
+
+    /// Deserializes [`Devices`] from a `JSON` document.
+    ///
+    /// Event receivers are not restored, hence they must be started again.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the document does not contain valid
+    /// [`Devices`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| {
+            Error::new(
+                ErrorKind::Devices,
+                format!("Error in deserializing the devices: {e}"),
+            )
+        })
+    }
+
+    /// Returns the [`Device`]s of the given [`DeviceKind`].
+    #[must_use]
+    pub fn filter_by_kind(&self, kind: DeviceKind) -> Vec<&Device> {
+        self.0
+            .iter()
+            .filter(|device| device.description.kind.matches(&kind))
+            .collect()
+    }
 }
 
 #[cfg(test)]
 pub(crate) mod tests {
     use std::collections::{HashMap, HashSet};
 
-    use tosca::device::{DeviceEnvironment, DeviceKindId};
+    use tosca::device::{DeviceEnvironment, DeviceKind, DeviceKindId};
     use tosca::hazards::{Hazard, Hazards};
     use tosca::parameters::Parameters;
-    use tosca::route::{Route, RouteConfigs};
+    use tosca::route::{RestKind, Route, RouteConfigs};
+
+    use std::time::{Duration, SystemTime};
+
+    use tosca::device::DeviceClock;
 
-    use super::{Description, Device, Devices, NetworkInformation, build_device_address};
+    use crate::error::ErrorKind;
 
-    fn create_network_info(address: &str, port: u16) -> NetworkInformation {
+    use super::{
+        CapabilitiesDiff, Description, Device, DeviceTimeBase, Devices, NetworkInformation,
+        build_device_address,
+    };
+
+    fn create_network_info(name: &str, address: &str, port: u16) -> NetworkInformation {
         let ip_address = address.parse().unwrap();
 
         let complete_address = build_device_address("http", &ip_address, port);
@@ -426,15 +626,9 @@ pub(crate) mod tests {
         let mut properties = HashMap::new();
         let _ = properties.insert("scheme".into(), "http".into());
 
-        NetworkInformation::new(
-            "device-name1._tosca._tcp.local.".into(),
-            addresses,
-            port,
-            properties,
-            complete_address,
-        )
-        .wifi_mac([0x02, 0x11, 0x22, 0x33, 0x44, 0x55])
-        .ethernet_mac([0x06, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE])
+        NetworkInformation::new(name.into(), addresses, port, properties, complete_address)
+            .wifi_mac([0x02, 0x11, 0x22, 0x33, 0x44, 0x55])
+            .ethernet_mac([0x06, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE])
     }
 
     fn create_description(
@@ -449,7 +643,8 @@ pub(crate) mod tests {
     }
 
     pub(crate) fn create_light() -> Device {
-        let network_info = create_network_info("192.168.1.174", 5000);
+        let network_info =
+            create_network_info("device-name1._tosca._tcp.local.", "192.168.1.174", 5000);
         let description = create_description(
             DeviceKindId::new("Light"),
             "light/",
@@ -483,7 +678,8 @@ pub(crate) mod tests {
     }
 
     pub(crate) fn create_unknown() -> Device {
-        let network_info = create_network_info("192.168.1.176", 5500);
+        let network_info =
+            create_network_info("device-name2._tosca._tcp.local.", "192.168.1.176", 5500);
         let description = create_description(
             DeviceKindId::new("Unknown"),
             "ip-camera/",
@@ -545,4 +741,140 @@ pub(crate) mod tests {
         // Get a reference to a device. The order is important.
         assert_eq!(devices.get(1), Some(&create_unknown()));
     }
+
+    #[test]
+    fn devices_json() {
+        let devices = Devices::from_devices(vec![create_light(), create_unknown()]);
+
+        let json = devices.to_json().unwrap();
+        let restored = Devices::from_json(&json).unwrap();
+
+        // Routes, hazards, and parameters are restored.
+        assert_eq!(restored, devices);
+        assert_eq!(
+            restored.get(0).unwrap().request("/toggle"),
+            create_light().request("/toggle")
+        );
+
+        // An invalid document.
+        assert_eq!(
+            Devices::from_json("{}").unwrap_err().kind(),
+            ErrorKind::Devices
+        );
+    }
+
+    #[test]
+    fn device_routes() {
+        let light = create_light();
+
+        let mut routes = light.routes().collect::<Vec<_>>();
+        routes.sort_by_key(|(route, _, _)| *route);
+
+        assert_eq!(
+            routes,
+            vec![
+                (
+                    "/off",
+                    RestKind::Put,
+                    &Hazards::new().insert(Hazard::LogEnergyConsumption)
+                ),
+                (
+                    "/on",
+                    RestKind::Put,
+                    &Hazards::new().insert(Hazard::ElectricEnergyConsumption)
+                ),
+                (
+                    "/toggle",
+                    RestKind::Get,
+                    &Hazards::new()
+                        .insert(Hazard::FireHazard)
+                        .insert(Hazard::ElectricEnergyConsumption)
+                ),
+            ]
+        );
+
+        assert!(light.route_exists("/on"));
+        assert!(light.route_exists("/toggle"));
+        assert!(!light.route_exists("/stream"));
+        assert!(!light.route_exists("on"));
+    }
+
+    #[test]
+    fn filter_devices_by_kind() {
+        let devices = Devices::from_devices(vec![create_unknown(), create_light()]);
+
+        assert_eq!(
+            devices.filter_by_kind(DeviceKind::Light),
+            vec![&create_light()]
+        );
+        assert_eq!(
+            devices.filter_by_kind(DeviceKind::Unknown),
+            vec![&create_unknown()]
+        );
+    }
+
+    #[test]
+    fn diff_capabilities() {
+        let light = create_light();
+
+        // Same capabilities.
+        assert!(light.diff_capabilities(&create_light()).is_empty());
+
+        // A light missing the `/toggle` route.
+        let mut light_without_toggle = create_light();
+        let _ = light_without_toggle.requests.remove("/toggle");
+
+        assert_eq!(
+            light.diff_capabilities(&light_without_toggle),
+            CapabilitiesDiff {
+                missing_in_other: vec!["/toggle".into()],
+                missing_in_self: Vec::new(),
+                mismatched_routes: Vec::new(),
+            }
+        );
+
+        assert_eq!(
+            light_without_toggle.diff_capabilities(&light),
+            CapabilitiesDiff {
+                missing_in_other: Vec::new(),
+                missing_in_self: vec!["/toggle".into()],
+                mismatched_routes: Vec::new(),
+            }
+        );
+
+        // A light whose `/on` route has a different REST kind.
+        let mut light_with_post = create_light();
+        if let Some(request) = light_with_post.requests.get_mut("/on") {
+            request.kind = tosca::route::RestKind::Post;
+        }
+
+        assert_eq!(
+            light.diff_capabilities(&light_with_post),
+            CapabilitiesDiff {
+                missing_in_other: Vec::new(),
+                missing_in_self: Vec::new(),
+                mismatched_routes: vec!["/on".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn device_time_base() {
+        let epoch = |ms| SystemTime::UNIX_EPOCH + Duration::from_millis(ms);
+
+        // A synchronized clock provides its own boot time, regardless of
+        // when it is received.
+        let clock = DeviceClock::uptime(5_000).unix_time(1_700_000_005_000);
+        let time_base = DeviceTimeBase::new(&clock, epoch(1_800_000_000_000));
+        assert!(time_base.is_synced());
+        assert_eq!(time_base.boot_time(), epoch(1_700_000_000_000));
+        assert_eq!(time_base.wall_clock(7_500), epoch(1_700_000_007_500));
+
+        // An unsynchronized clock is aligned to the controller clock.
+        let clock = DeviceClock::uptime(5_000);
+        let time_base = DeviceTimeBase::new(&clock, epoch(1_800_000_005_000));
+        assert!(!time_base.is_synced());
+        assert_eq!(time_base.boot_time(), epoch(1_800_000_000_000));
+        assert_eq!(time_base.wall_clock(2_000), epoch(1_800_000_002_000));
+    }
 }
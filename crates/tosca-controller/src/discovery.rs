@@ -8,7 +8,7 @@ use flume::RecvTimeoutError;
 
 use mdns_sd::{IfKind, Receiver, ResolvedService, ServiceDaemon, ServiceEvent};
 
-use tokio::time::sleep;
+use tokio::time::{Instant, sleep_until};
 
 use tracing::{info, warn};
 
@@ -16,6 +16,7 @@ use crate::device::{Description, Device, Devices, NetworkInformation, build_devi
 use crate::error::Error;
 use crate::events::Events;
 use crate::request::create_requests;
+use crate::response::decode_json;
 
 // Service top-level domain.
 //
@@ -61,6 +62,7 @@ pub struct Discovery {
     disable_ipv6: bool,
     disable_ip: Option<IpAddr>,
     disable_network_interface: Option<&'static str>,
+    network_interface: Option<String>,
 }
 
 impl Discovery {
@@ -76,12 +78,14 @@ impl Discovery {
             disable_ipv6: false,
             disable_ip: None,
             disable_network_interface: None,
+            network_interface: None,
         }
     }
 
     /// Sets the service timeout.
     ///
-    /// The entire discovery process will last for the given timeout value.
+    /// The entire discovery process will last for the given timeout value,
+    /// returning the devices found until then.
     #[must_use]
     pub const fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
@@ -137,6 +141,16 @@ impl Discovery {
         self
     }
 
+    /// Restricts the discovery service to the given network interface.
+    ///
+    /// All the other network interfaces are ignored.
+    #[must_use]
+    #[inline]
+    pub fn interface(mut self, name: &str) -> Self {
+        self.network_interface = Some(name.into());
+        self
+    }
+
     pub(crate) async fn discover(&self) -> Result<Devices, Error> {
         // Discover devices.
         let discovery_info = self.discover_devices().await?;
@@ -148,6 +162,12 @@ impl Discovery {
         // Create a mdns daemon
         let mdns = ServiceDaemon::new()?;
 
+        // Browse only on the chosen network interface.
+        if let Some(network_interface) = &self.network_interface {
+            mdns.disable_interface(IfKind::All)?;
+            mdns.enable_interface(network_interface.as_str())?;
+        }
+
         // Disable IPv6 interface.
         if self.disable_ipv6 {
             mdns.disable_interface(IfKind::IPv6)?;
@@ -179,7 +199,8 @@ impl Discovery {
 
         // Run for n-seconds in search of devices and saves their information
         // in memory.
-        while let Ok(event) = self.with_timeout(&receiver).await {
+        let deadline = Instant::now() + self.timeout;
+        while let Ok(event) = Self::with_deadline(&receiver, deadline).await {
             if let ServiceEvent::ServiceResolved(info) = event {
                 // Check whether there are device addresses.
                 //
@@ -206,8 +227,11 @@ impl Discovery {
     }
 
     #[inline]
-    async fn with_timeout<T>(&self, receiver: &Receiver<T>) -> Result<T, RecvTimeoutError> {
-        let timeout_future = sleep(self.timeout);
+    async fn with_deadline<T>(
+        receiver: &Receiver<T>,
+        deadline: Instant,
+    ) -> Result<T, RecvTimeoutError> {
+        let timeout_future = sleep_until(deadline);
 
         tokio::select! {
             () = timeout_future => {
@@ -252,7 +276,7 @@ impl Discovery {
                     .await
                 {
                     Ok(response) => {
-                        let device_desc: DeviceDescription = response.json().await?;
+                        let device_desc: DeviceDescription = decode_json(&response.bytes().await?)?;
 
                         if device_desc.data.wifi_mac.is_none()
                             && device_desc.data.ethernet_mac.is_none()
@@ -263,24 +287,7 @@ impl Discovery {
                             continue;
                         }
 
-                        let requests = create_requests(
-                            device_desc.route_configs,
-                            &complete_address,
-                            &device_desc.main_route,
-                            device_desc.data.environment,
-                        );
-
-                        let description = Description::new(
-                            device_desc.data.kind,
-                            device_desc.data.environment,
-                            device_desc.main_route.into_owned(),
-                        );
-                        #[cfg(feature = "metadata")]
-                        let description = description.description(
-                            device_desc.data.description.map(std::convert::Into::into),
-                        );
-
-                        let mut network_info = NetworkInformation::new(
+                        let network_info = NetworkInformation::new(
                             service.fullname,
                             service
                                 .addresses
@@ -292,17 +299,7 @@ impl Discovery {
                             complete_address,
                         );
 
-                        if let Some(mac) = device_desc.data.wifi_mac {
-                            network_info = network_info.wifi_mac(mac);
-                        }
-
-                        if let Some(mac) = device_desc.data.ethernet_mac {
-                            network_info = network_info.ethernet_mac(mac);
-                        }
-
-                        let events = device_desc.events_description.map(Events::new);
-
-                        devices.add(Device::init(network_info, description, requests, events));
+                        devices.add(device_from_description(device_desc, network_info));
 
                         // Only a single address is necessary.
                         break;
@@ -354,9 +351,46 @@ impl Discovery {
     }
 }
 
+// Builds a device from its description and network information.
+//
+// The device requests are addressed to the last reachable address of
+// the network information.
+pub(crate) fn device_from_description(
+    device_desc: DeviceDescription,
+    mut network_info: NetworkInformation,
+) -> Device {
+    let requests = create_requests(
+        device_desc.route_configs,
+        &network_info.last_reachable_address,
+        &device_desc.main_route,
+        device_desc.data.environment,
+    );
+
+    let description = Description::new(
+        device_desc.data.kind,
+        device_desc.data.environment,
+        device_desc.main_route.into_owned(),
+    );
+    #[cfg(feature = "metadata")]
+    let description =
+        description.description(device_desc.data.description.map(std::convert::Into::into));
+
+    if let Some(mac) = device_desc.data.wifi_mac {
+        network_info = network_info.wifi_mac(mac);
+    }
+
+    if let Some(mac) = device_desc.data.ethernet_mac {
+        network_info = network_info.ethernet_mac(mac);
+    }
+
+    let events = device_desc.events_description.map(Events::new);
+
+    Device::init(network_info, description, requests, events)
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
 
     use tracing::warn;
 
@@ -403,6 +437,22 @@ pub(crate) mod tests {
         }
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_discovery_timeout() {
+        run_discovery_function("discovery_timeout", || async {
+            let timeout = Duration::from_millis(200);
+            let discovery = Discovery::new(DOMAIN).timeout(timeout).interface("lo");
+
+            let start = Instant::now();
+            assert!(discovery.discover().await.is_ok());
+
+            // The discovery stops shortly after the timeout.
+            assert!(start.elapsed() < timeout + Duration::from_secs(1));
+        })
+        .await;
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     #[serial]
     async fn test_single_device_discovery() {
@@ -9,6 +9,8 @@ pub enum ErrorKind {
     Discovery,
     /// Errors encountered when sending requests to a device.
     Request,
+    /// Errors caused by a device which has not answered a request in time.
+    Timeout,
     /// Errors caused by an invalid parameter.
     InvalidParameter,
     /// Errors encountered while parsing a `json` response.
@@ -20,6 +22,10 @@ pub enum ErrorKind {
     Sender,
     /// Errors related to event management.
     Events,
+    /// Errors encountered while loading a privacy policy.
+    Policy,
+    /// Errors encountered while persisting or restoring devices.
+    Devices,
 }
 
 impl ErrorKind {
@@ -27,12 +33,15 @@ impl ErrorKind {
         match self {
             Self::Discovery => "Discovery",
             Self::Request => "Request",
+            Self::Timeout => "Timeout",
             Self::InvalidParameter => "Invalid Parameter",
             Self::JsonResponse => "Json Response",
             #[cfg(feature = "stream")]
             Self::StreamResponse => "Stream Response",
             Self::Sender => "Response Sender",
             Self::Events => "Events",
+            Self::Policy => "Policy",
+            Self::Devices => "Devices",
         }
     }
 }
@@ -44,10 +53,15 @@ impl std::fmt::Display for ErrorKind {
 }
 
 /// Controller error.
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct Error {
     kind: ErrorKind,
     description: Cow<'static, str>,
+    // Whether the error is caused by a temporary network failure.
+    transient: bool,
+    // Whether the error is caused by a failed connection, hence before
+    // sending the request.
+    connect: bool,
 }
 
 impl std::fmt::Display for Error {
@@ -68,7 +82,24 @@ impl Error {
     pub fn new(kind: ErrorKind, description: impl Into<Cow<'static, str>>) -> Self {
         let description = description.into();
         error!("{}", description.as_ref());
-        Self { kind, description }
+        Self {
+            kind,
+            description,
+            transient: false,
+            connect: false,
+        }
+    }
+
+    pub(crate) const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub(crate) const fn is_transient(&self) -> bool {
+        self.transient
+    }
+
+    pub(crate) const fn is_connect(&self) -> bool {
+        self.connect
     }
 
     fn format(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -78,7 +109,17 @@ impl Error {
 
 impl From<reqwest::Error> for Error {
     fn from(e: reqwest::Error) -> Self {
-        Self::new(ErrorKind::Request, e.to_string())
+        let kind = if e.is_timeout() {
+            ErrorKind::Timeout
+        } else {
+            ErrorKind::Request
+        };
+        let mut error = Self::new(kind, e.to_string());
+        // Connection failures, timeouts, and connections dropped while
+        // sending a request might not happen again.
+        error.transient = e.is_connect() || e.is_timeout() || e.is_request();
+        error.connect = e.is_connect();
+        error
     }
 }
 
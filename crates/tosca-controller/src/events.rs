@@ -1,12 +1,15 @@
+use std::hash::{BuildHasher, Hasher, RandomState};
 use std::time::Duration;
 
 use tosca::events::{BrokerData, Events as ToscaEvents, EventsDescription};
 
 use rumqttc::v5::{
-    AsyncClient, ConnectionError, Event, EventLoop, MqttOptions, mqttbytes::QoS,
+    AsyncClient, ConnectionError, Event, EventLoop, MqttOptions, mqttbytes::QoS as MqttQoS,
     mqttbytes::v5::Packet,
 };
 
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 use tokio::sync::{broadcast, mpsc};
 use tokio::task::JoinHandle;
 
@@ -15,6 +18,7 @@ use tokio_util::sync::CancellationToken;
 use tracing::{error, warn};
 
 use crate::error::Result;
+use crate::lifecycle::ControllerEvent;
 
 // The capacity of the bounded asynchronous channel.
 const ASYNC_CHANNEL_CAPACITY: usize = 10;
@@ -22,6 +26,166 @@ const ASYNC_CHANNEL_CAPACITY: usize = 10;
 // Keep alive time to send `pingreq` to broker when the connection is idle.
 const KEEP_ALIVE_TIME: Duration = Duration::from_secs(5);
 
+// Minimum keep alive time accepted by the `MQTT` client.
+const MIN_KEEP_ALIVE_TIME: Duration = Duration::from_secs(5);
+
+// Initial delay before reconnecting to a broker after a connection error.
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_millis(500);
+
+// Maximum delay before reconnecting to a broker after a connection error.
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Quality of service used to subscribe to the topic of a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QoS {
+    /// An event is delivered at most once, hence it might be lost.
+    AtMostOnce,
+    /// An event is delivered at least once, hence it might be duplicated.
+    AtLeastOnce,
+    /// An event is delivered exactly once.
+    ExactlyOnce,
+}
+
+impl QoS {
+    const fn to_mqtt(self) -> MqttQoS {
+        match self {
+            Self::AtMostOnce => MqttQoS::AtMostOnce,
+            Self::AtLeastOnce => MqttQoS::AtLeastOnce,
+            Self::ExactlyOnce => MqttQoS::ExactlyOnce,
+        }
+    }
+}
+
+/// TLS configuration used to connect to a broker.
+#[cfg(feature = "tls")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// PEM-encoded certificate authority used to validate the broker
+    /// certificate.
+    pub ca: Vec<u8>,
+    /// PEM-encoded client certificate and private key, used when the
+    /// broker authenticates its clients.
+    pub client_auth: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+#[cfg(feature = "tls")]
+impl TlsConfig {
+    /// Creates a [`TlsConfig`] from a PEM-encoded certificate authority.
+    #[must_use]
+    pub const fn new(ca: Vec<u8>) -> Self {
+        Self {
+            ca,
+            client_auth: None,
+        }
+    }
+
+    /// Sets the PEM-encoded client certificate and private key.
+    #[must_use]
+    #[inline]
+    pub fn client_auth(mut self, certificate: Vec<u8>, key: Vec<u8>) -> Self {
+        self.client_auth = Some((certificate, key));
+        self
+    }
+
+    fn transport(&self) -> rumqttc::Transport {
+        rumqttc::Transport::tls_with_config(rumqttc::TlsConfiguration::Simple {
+            ca: self.ca.clone(),
+            alpn: None,
+            client_auth: self.client_auth.clone(),
+        })
+    }
+}
+
+/// Configuration of the tasks subscribing to the events of a device.
+///
+/// The default configuration subscribes with [`QoS::AtMostOnce`], a
+/// keep-alive time of 5 seconds, and a client channel capacity of 10
+/// requests, over a plain TCP connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSubscriberConfig {
+    /// Quality of service used to subscribe to the topic of a device.
+    pub qos: QoS,
+    /// Time after which the broker is pinged when the connection is idle.
+    ///
+    /// Values less than 5 seconds are raised to 5 seconds.
+    pub keep_alive: Duration,
+    /// Number of requests the `MQTT` client channel can hold.
+    pub channel_capacity: usize,
+    /// TLS configuration, used to connect to brokers which require
+    /// a secure connection.
+    ///
+    /// When the broker is referenced by hostname, the hostname is used
+    /// to validate the broker certificate.
+    #[cfg(feature = "tls")]
+    pub tls: Option<TlsConfig>,
+}
+
+impl Default for EventSubscriberConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventSubscriberConfig {
+    /// Creates the default [`EventSubscriberConfig`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            qos: QoS::AtMostOnce,
+            keep_alive: KEEP_ALIVE_TIME,
+            channel_capacity: ASYNC_CHANNEL_CAPACITY,
+            #[cfg(feature = "tls")]
+            tls: None,
+        }
+    }
+
+    /// Sets the [`QoS`].
+    #[must_use]
+    pub const fn qos(mut self, qos: QoS) -> Self {
+        self.qos = qos;
+        self
+    }
+
+    /// Sets the keep-alive time.
+    ///
+    /// Values less than 5 seconds are raised to 5 seconds.
+    #[must_use]
+    pub const fn keep_alive(mut self, keep_alive: Duration) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// Sets the `MQTT` client channel capacity.
+    #[must_use]
+    pub const fn channel_capacity(mut self, channel_capacity: usize) -> Self {
+        self.channel_capacity = channel_capacity;
+        self
+    }
+
+    /// Sets the [`TlsConfig`].
+    #[cfg(feature = "tls")]
+    #[must_use]
+    #[inline]
+    pub fn tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    fn mqtt_options(&self, id: usize, broker_data: &BrokerData) -> MqttOptions {
+        let BrokerData { address, port } = broker_data;
+
+        let mut mqttoptions = MqttOptions::new(id.to_string(), address.to_string(), *port);
+        let _ = mqttoptions.set_keep_alive(self.keep_alive.max(MIN_KEEP_ALIVE_TIME));
+
+        #[cfg(feature = "tls")]
+        if let Some(tls) = &self.tls {
+            let _ = mqttoptions.set_transport(tls.transport());
+        }
+
+        mqttoptions
+    }
+}
+
 /// Event payload transmitted by the global asynchronous receiver task.
 ///
 /// The payload consists of a device identifier and its associated event data.
@@ -65,16 +229,22 @@ impl Events {
     }
 }
 
-#[inline]
-fn parse_event(event: &std::result::Result<Event, ConnectionError>) -> Option<ToscaEvents> {
-    let event = match event {
-        Ok(event) => event,
-        Err(e) => {
-            error!("Error in receiving the event, discard it: {e}");
-            return None;
-        }
-    };
+// Only the events description is persisted, while the cancellation token
+// is recreated on load.
+impl Serialize for Events {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.description.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Events {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        EventsDescription::deserialize(deserializer).map(Self::new)
+    }
+}
 
+#[inline]
+fn parse_event(event: &Event) -> Option<ToscaEvents> {
     let packet = match event {
         Event::Incoming(packet) => packet,
         Event::Outgoing(outgoing) => {
@@ -97,67 +267,202 @@ fn parse_event(event: &std::result::Result<Event, ConnectionError>) -> Option<To
     }
 }
 
-async fn run_global_event_subscriber(
-    client: AsyncClient,
-    mut eventloop: EventLoop,
+// Exponential backoff, with jitter, between two reconnections to a broker.
+struct Backoff {
+    initial: Duration,
+    max: Duration,
+    delay: Duration,
+}
+
+impl Backoff {
+    const fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max,
+            delay: initial,
+        }
+    }
+
+    const fn reset(&mut self) {
+        self.delay = self.initial;
+    }
+
+    // Returns a random delay between the half and the whole of the current
+    // delay, so that the subscribers of many devices do not reconnect all
+    // at once, then doubles the current delay up to the maximum.
+    fn next_delay(&mut self) -> Duration {
+        let delay = self.delay;
+        self.delay = self.delay.saturating_mul(2).min(self.max);
+
+        let half = delay / 2;
+        let half_ms = u64::try_from(half.as_millis()).unwrap_or(u64::MAX);
+        let jitter = RandomState::new().build_hasher().finish() % half_ms.saturating_add(1);
+        half + Duration::from_millis(jitter)
+    }
+}
+
+// A connection to a broker which yields `MQTT` events.
+trait EventSource {
+    fn poll(&mut self) -> impl Future<Output = std::result::Result<Event, ConnectionError>> + Send;
+}
+
+struct MqttConnection {
+    // The client must be kept alive for the event loop to work.
+    _client: AsyncClient,
+    eventloop: EventLoop,
+}
+
+impl EventSource for MqttConnection {
+    fn poll(&mut self) -> impl Future<Output = std::result::Result<Event, ConnectionError>> + Send {
+        self.eventloop.poll()
+    }
+}
+
+// All data needed to connect to the broker of a device and to subscribe
+// to its topic, both at first and after a connection error.
+#[derive(Clone)]
+struct Connector {
     id: usize,
-    cancellation_token: CancellationToken,
-    sender: mpsc::Sender<EventPayload>,
-) {
-    loop {
-        tokio::select! {
-            // Use the cancellation token to stop the loop
-            () = cancellation_token.cancelled() => { break; }
-            // Poll the `MQTT` event coming from the network
-            event = eventloop.poll() => {
-                let Some(tosca_events) = parse_event(&event) else {
-                    continue;
-                };
+    options: MqttOptions,
+    topic: String,
+    qos: MqttQoS,
+    channel_capacity: usize,
+}
+
+impl Connector {
+    fn new(id: usize, events: &Events, config: &EventSubscriberConfig) -> Self {
+        Self {
+            id,
+            options: config.mqtt_options(id, &events.description.broker_data),
+            topic: events.description.topic.as_str().into(),
+            qos: config.qos.to_mqtt(),
+            channel_capacity: config.channel_capacity,
+        }
+    }
+
+    async fn connect(self) -> Result<MqttConnection> {
+        let Self {
+            id,
+            options,
+            topic,
+            qos,
+            channel_capacity,
+        } = self;
+
+        let (client, eventloop) = AsyncClient::new(options, channel_capacity);
+        client.subscribe(&topic, qos).await.map_err(|e| {
+            error!("Impossible to subscribe to topic {topic} for device {id}: {e}");
+            e
+        })?;
+
+        Ok(MqttConnection {
+            _client: client,
+            eventloop,
+        })
+    }
+}
+
+// Where the events received by a subscriber are sent.
+enum EventsSink {
+    // The global receiver of all devices, along with the lifecycle stream.
+    Global {
+        sender: mpsc::Sender<EventPayload>,
+        lifecycle: broadcast::Sender<ControllerEvent>,
+    },
+    // The receiver of a single device.
+    Device(broadcast::Sender<ToscaEvents>),
+}
+
+impl EventsSink {
+    // Returns `false` when the events can no longer be sent.
+    async fn send(&self, id: usize, tosca_events: ToscaEvents) -> bool {
+        match self {
+            Self::Global { sender, lifecycle } => {
+                // Events are cloned only when the lifecycle stream is consumed.
+                if lifecycle.receiver_count() > 0 {
+                    let _ = lifecycle.send(ControllerEvent::EventReceived {
+                        device_id: id,
+                        events: Box::new(tosca_events.clone()),
+                    });
+                }
 
                 if let Err(e) = sender.send(EventPayload::new(id, tosca_events)).await {
-                    error!(
-                        "Stop sending events to the global receiver: {e}"
-                    );
-                    break;
+                    error!("Stop sending events to the global receiver: {e}");
+                    return false;
+                }
+            }
+            Self::Device(sender) => {
+                if let Err(e) = sender.send(tosca_events) {
+                    error!("Stop sending events to the device receiver with id `{id}`: {e}");
+                    return false;
                 }
             }
         }
+        true
     }
-    drop(sender);
-    drop(eventloop);
-    drop(client);
 }
 
-async fn run_event_subscriber(
-    client: AsyncClient,
-    mut eventloop: EventLoop,
+// Receives the events of a device until the task is cancelled or the events
+// can no longer be sent.
+//
+// On a connection error, the broker is connected again after a backoff
+// delay, which is reset as soon as the connection works again.
+async fn run_event_subscriber<S, C, F>(
+    mut source: S,
+    mut connect: C,
+    mut backoff: Backoff,
     id: usize,
     cancellation_token: CancellationToken,
-    sender: broadcast::Sender<ToscaEvents>,
-) {
+    sink: EventsSink,
+) where
+    S: EventSource,
+    C: FnMut() -> F,
+    F: Future<Output = Result<S>>,
+{
     loop {
-        tokio::select! {
+        let event = tokio::select! {
             // Use the cancellation token to stop the loop
             () = cancellation_token.cancelled() => { break; }
             // Poll the `MQTT` event coming from the network
-            event = eventloop.poll() => {
-                let Some(tosca_events) = parse_event(&event) else {
-                    continue;
-                };
+            event = source.poll() => event,
+        };
 
-                if let Err(e) = sender.send(tosca_events) {
-                    error!(
-                        "Stop sending events to the device receiver with id `{id}`: {e}"
-                    );
-                    break;
+        let event = match event {
+            Ok(event) => {
+                backoff.reset();
+                event
+            }
+            Err(e) => {
+                let delay = backoff.next_delay();
+                warn!(
+                    "Connection error for the device with id `{id}`, \
+                     reconnecting in {}ms: {e}",
+                    delay.as_millis()
+                );
+
+                tokio::select! {
+                    () = cancellation_token.cancelled() => { break; }
+                    () = tokio::time::sleep(delay) => {}
+                }
+
+                match connect().await {
+                    Ok(new_source) => source = new_source,
+                    Err(e) => {
+                        error!("Impossible to reconnect the device with id `{id}`: {e}");
+                    }
                 }
+                continue;
             }
+        };
+
+        let Some(tosca_events) = parse_event(&event) else {
+            continue;
+        };
+
+        if !sink.send(id, tosca_events).await {
+            break;
         }
-        tokio::time::sleep(Duration::from_millis(100)).await;
     }
-    drop(sender);
-    drop(eventloop);
-    drop(client);
 }
 
 pub(crate) struct EventsRunner;
@@ -167,51 +472,222 @@ impl EventsRunner {
         events: &Events,
         id: usize,
         sender: mpsc::Sender<EventPayload>,
+        lifecycle: broadcast::Sender<ControllerEvent>,
+        config: &EventSubscriberConfig,
     ) -> Result<JoinHandle<()>> {
-        let (client, eventloop) = Self::init(id, events).await?;
-
-        Ok(tokio::spawn(run_global_event_subscriber(
-            client,
-            eventloop,
-            id,
-            events.cancellation_token.clone(),
-            sender,
-        )))
+        Self::run(events, id, config, EventsSink::Global { sender, lifecycle }).await
     }
 
     pub(crate) async fn run_device_subscriber(
         events: &Events,
         id: usize,
         sender: broadcast::Sender<ToscaEvents>,
+        config: &EventSubscriberConfig,
     ) -> Result<JoinHandle<()>> {
-        let (client, eventloop) = Self::init(id, events).await?;
+        Self::run(events, id, config, EventsSink::Device(sender)).await
+    }
+
+    async fn run(
+        events: &Events,
+        id: usize,
+        config: &EventSubscriberConfig,
+        sink: EventsSink,
+    ) -> Result<JoinHandle<()>> {
+        let connector = Connector::new(id, events, config);
+        let connection = connector.clone().connect().await?;
 
         Ok(tokio::spawn(run_event_subscriber(
-            client,
-            eventloop,
+            connection,
+            move || connector.clone().connect(),
+            Backoff::new(RECONNECT_INITIAL_DELAY, RECONNECT_MAX_DELAY),
             id,
             events.cancellation_token.clone(),
-            sender,
+            sink,
         )))
     }
+}
 
-    #[inline]
-    async fn init(id: usize, events: &Events) -> Result<(AsyncClient, EventLoop)> {
-        let BrokerData { address, port } = events.description.broker_data;
-        let topic = events.description.topic.as_str();
-
-        let mut mqttoptions = MqttOptions::new(id.to_string(), address.to_string(), port);
-        let _ = mqttoptions.set_keep_alive(KEEP_ALIVE_TIME);
-
-        let (client, eventloop) = AsyncClient::new(mqttoptions, ASYNC_CHANNEL_CAPACITY);
-        client
-            .subscribe(topic, QoS::AtMostOnce)
-            .await
-            .map_err(|e| {
-                error!("Impossible to subscribe to topic {topic} for device {id}: {e}");
-                e
-            })?;
-
-        Ok((client, eventloop))
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::net::Ipv4Addr;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use tosca::events::{BrokerData, Event as ToscaEvent, Events as ToscaEvents};
+
+    use rumqttc::v5::mqttbytes::QoS as MqttQoS;
+    use rumqttc::v5::mqttbytes::v5::{Packet, Publish};
+    use rumqttc::v5::{ConnectionError, Event};
+
+    use tokio::sync::broadcast;
+
+    use tokio_util::sync::CancellationToken;
+
+    use super::{
+        Backoff, EventSource, EventSubscriberConfig, EventsSink, QoS, run_event_subscriber,
+    };
+
+    // An event source yielding the given results, then pending forever.
+    struct FakeSource(VecDeque<Result<Event, ConnectionError>>);
+
+    impl EventSource for FakeSource {
+        fn poll(&mut self) -> impl Future<Output = Result<Event, ConnectionError>> + Send {
+            let next = self.0.pop_front();
+            async move {
+                match next {
+                    Some(result) => result,
+                    None => std::future::pending().await,
+                }
+            }
+        }
+    }
+
+    fn publish(events: &ToscaEvents) -> Event {
+        Event::Incoming(Packet::Publish(Publish::new(
+            "topic",
+            MqttQoS::AtMostOnce,
+            serde_json::to_vec(events).unwrap(),
+            None,
+        )))
+    }
+
+    fn connection_error() -> Result<Event, ConnectionError> {
+        Err(ConnectionError::Io(
+            std::io::ErrorKind::ConnectionReset.into(),
+        ))
+    }
+
+    #[cfg(feature = "tls")]
+    #[test]
+    fn event_subscriber_tls_config() {
+        use rumqttc::{TlsConfiguration, Transport};
+
+        use super::TlsConfig;
+
+        let broker_data = BrokerData::host("broker.local".into(), 8883);
+
+        // Without a TLS configuration, the connection is plain TCP.
+        assert!(matches!(
+            EventSubscriberConfig::new()
+                .mqtt_options(0, &broker_data)
+                .transport(),
+            Transport::Tcp
+        ));
+
+        let config = EventSubscriberConfig::new()
+            .tls(TlsConfig::new(b"ca".to_vec()).client_auth(b"cert".to_vec(), b"key".to_vec()));
+        let mqttoptions = config.mqtt_options(0, &broker_data);
+
+        assert_eq!(mqttoptions.broker_address(), ("broker.local".into(), 8883));
+        let Transport::Tls(TlsConfiguration::Simple {
+            ca,
+            alpn,
+            client_auth,
+        }) = mqttoptions.transport()
+        else {
+            panic!("The connection is not configured for TLS");
+        };
+        assert_eq!(ca, b"ca");
+        assert_eq!(alpn, None);
+        assert_eq!(client_auth, Some((b"cert".to_vec(), b"key".to_vec())));
+    }
+
+    #[test]
+    fn backoff() {
+        let mut backoff = Backoff::new(Duration::from_millis(100), Duration::from_millis(400));
+
+        let in_range = |delay: Duration, min: u64, max: u64| {
+            delay >= Duration::from_millis(min) && delay <= Duration::from_millis(max)
+        };
+
+        assert!(in_range(backoff.next_delay(), 50, 100));
+        assert!(in_range(backoff.next_delay(), 100, 200));
+        assert!(in_range(backoff.next_delay(), 200, 400));
+        // The delay is capped.
+        assert!(in_range(backoff.next_delay(), 200, 400));
+
+        backoff.reset();
+        assert!(in_range(backoff.next_delay(), 50, 100));
+    }
+
+    #[tokio::test]
+    async fn reconnect_after_connection_errors() {
+        let mut first = ToscaEvents::empty();
+        first.add_bool_event(ToscaEvent::bool("light"));
+        let mut second = first.clone();
+        let _ = second.update_bool_value_by_name("light", true);
+
+        // The first connection yields an event and then breaks, the second
+        // one breaks at once, while the third one yields another event.
+        let source = FakeSource(VecDeque::from([Ok(publish(&first)), connection_error()]));
+        let mut reconnections = VecDeque::from([
+            FakeSource(VecDeque::from([connection_error()])),
+            FakeSource(VecDeque::from([Ok(publish(&second))])),
+        ]);
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let connect = {
+            let connections = Arc::clone(&connections);
+            move || {
+                let _ = connections.fetch_add(1, Ordering::SeqCst);
+                let source = reconnections.pop_front().unwrap();
+                async move { Ok(source) }
+            }
+        };
+
+        let (sender, mut receiver) = broadcast::channel(4);
+        let cancellation_token = CancellationToken::new();
+
+        let handle = tokio::spawn(run_event_subscriber(
+            source,
+            connect,
+            Backoff::new(Duration::from_millis(10), Duration::from_millis(40)),
+            0,
+            cancellation_token.clone(),
+            EventsSink::Device(sender),
+        ));
+
+        assert_eq!(receiver.recv().await.unwrap(), first);
+        // Events resume after reconnecting.
+        assert_eq!(receiver.recv().await.unwrap(), second);
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+
+        cancellation_token.cancel();
+        handle.await.unwrap();
+    }
+
+    #[test]
+    fn event_subscriber_config() {
+        let broker_data = BrokerData::new(Ipv4Addr::LOCALHOST.into(), 1883);
+
+        // The default configuration keeps the previous behavior.
+        let config = EventSubscriberConfig::default();
+        assert_eq!(config.qos.to_mqtt(), MqttQoS::AtMostOnce);
+        assert_eq!(config.channel_capacity, 10);
+        assert_eq!(
+            config.mqtt_options(0, &broker_data).keep_alive(),
+            Duration::from_secs(5)
+        );
+
+        let config = EventSubscriberConfig::new()
+            .qos(QoS::AtLeastOnce)
+            .keep_alive(Duration::from_secs(30))
+            .channel_capacity(32);
+        assert_eq!(config.qos.to_mqtt(), MqttQoS::AtLeastOnce);
+        assert_eq!(config.channel_capacity, 32);
+
+        let mqttoptions = config.mqtt_options(3, &broker_data);
+        assert_eq!(mqttoptions.keep_alive(), Duration::from_secs(30));
+        assert_eq!(mqttoptions.client_id(), "3");
+        assert_eq!(mqttoptions.broker_address(), ("127.0.0.1".into(), 1883));
+
+        // A too short keep-alive time is raised to the minimum.
+        let config = EventSubscriberConfig::new().keep_alive(Duration::from_secs(1));
+        assert_eq!(
+            config.mqtt_options(0, &broker_data).keep_alive(),
+            Duration::from_secs(5)
+        );
     }
 }
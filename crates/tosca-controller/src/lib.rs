@@ -14,7 +14,9 @@
 //!   their tasks
 //! - Defining privacy policies to allow or block requests to a device
 //! - Intercepting device events by subscribing to the brokers where
-//!   they are published
+//!   they are published, or by reading the device Server-Sent Events stream
+//! - Following the lifecycle of all devices, such as their discovery and
+//!   reachability, as a single stream of events
 //!
 //! To optimize system resource usage, `tosca-controller` leverages `tokio` as
 //! an asynchronous executor, allowing concurrent execution of independent
@@ -32,6 +34,8 @@ pub mod discovery;
 pub mod error;
 /// All events data.
 pub mod events;
+/// Lifecycle events of the devices managed by a controller.
+pub mod lifecycle;
 /// A privacy policy manager that blocks or allows the requests to devices
 /// based on a set of privacy rules.
 pub mod policy;
@@ -40,5 +44,8 @@ pub mod request;
 /// All supported methods and data for handling `tosca` device responses.
 pub mod response;
 
+#[cfg(feature = "stream")]
+mod sse;
+
 #[cfg(test)]
 mod tests;
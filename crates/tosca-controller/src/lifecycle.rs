@@ -0,0 +1,160 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, PoisonError};
+
+use tosca::events::Events as ToscaEvents;
+
+use futures_util::{Stream, stream};
+
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use tracing::warn;
+
+use crate::device::Devices;
+
+// The capacity of the lifecycle events channel.
+const LIFECYCLE_CHANNEL_CAPACITY: usize = 64;
+
+/// A lifecycle event of the devices managed by a
+/// [`Controller`](crate::controller::Controller).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControllerEvent {
+    /// A device has been discovered on the network.
+    DeviceDiscovered {
+        /// Device identifier.
+        device_id: usize,
+        /// Full device name.
+        name: String,
+    },
+    /// A device is no longer present on the network.
+    ///
+    /// Device identifiers are reassigned by each discovery, hence
+    /// a departed device is identified by its name.
+    DeviceDeparted {
+        /// Full device name.
+        name: String,
+    },
+    /// A device has answered a request after being unreachable, or for
+    /// the first time.
+    DeviceReachable {
+        /// Device identifier.
+        device_id: usize,
+    },
+    /// A device has failed to answer a request after being reachable, or
+    /// for the first time.
+    DeviceUnreachable {
+        /// Device identifier.
+        device_id: usize,
+    },
+    /// Events have been received from a device.
+    EventReceived {
+        /// Device identifier.
+        device_id: usize,
+        /// Device events.
+        events: Box<ToscaEvents>,
+    },
+}
+
+// Multiplexes the lifecycle events produced by the controller.
+#[derive(Debug)]
+pub(crate) struct Lifecycle {
+    sender: broadcast::Sender<ControllerEvent>,
+    // The last known reachability of each device.
+    reachability: Mutex<HashMap<usize, bool>>,
+}
+
+// Lifecycle events are transient, so they are not taken into account when
+// comparing controllers.
+impl PartialEq for Lifecycle {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Lifecycle {
+    pub(crate) fn new() -> Self {
+        let (sender, _) = broadcast::channel(LIFECYCLE_CHANNEL_CAPACITY);
+        Self {
+            sender,
+            reachability: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> impl Stream<Item = ControllerEvent> + Send + use<> {
+        stream::unfold(self.sender.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("The lifecycle stream skipped {skipped} events");
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    pub(crate) fn sender(&self) -> broadcast::Sender<ControllerEvent> {
+        self.sender.clone()
+    }
+
+    pub(crate) fn devices_changed(&self, old_devices: &Devices, new_devices: &Devices) {
+        let old_names = old_devices
+            .iter()
+            .map(|device| device.network_info().name.as_str())
+            .collect::<HashSet<&str>>();
+        let new_names = new_devices
+            .iter()
+            .map(|device| device.network_info().name.as_str())
+            .collect::<HashSet<&str>>();
+
+        for device in old_devices {
+            let name = device.network_info().name.as_str();
+            if !new_names.contains(name) {
+                self.send(ControllerEvent::DeviceDeparted { name: name.into() });
+            }
+        }
+
+        for (device_id, device) in new_devices.iter().enumerate() {
+            let name = device.network_info().name.as_str();
+            if !old_names.contains(name) {
+                self.send(ControllerEvent::DeviceDiscovered {
+                    device_id,
+                    name: name.into(),
+                });
+            }
+        }
+
+        // Identifiers are reassigned, so the reachability starts over.
+        self.reachability
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    pub(crate) fn device_added(&self, device_id: usize, name: String) {
+        self.send(ControllerEvent::DeviceDiscovered { device_id, name });
+    }
+
+    pub(crate) fn reachability_changed(&self, device_id: usize, reachable: bool) {
+        let previous = self
+            .reachability
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(device_id, reachable);
+
+        if previous == Some(reachable) {
+            return;
+        }
+
+        self.send(if reachable {
+            ControllerEvent::DeviceReachable { device_id }
+        } else {
+            ControllerEvent::DeviceUnreachable { device_id }
+        });
+    }
+
+    fn send(&self, event: ControllerEvent) {
+        // An error only means that nobody is listening.
+        let _ = self.sender.send(event);
+    }
+}
@@ -1,6 +1,11 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 
-use tosca::hazards::Hazards;
+use serde::{Deserialize, Serialize};
+
+use tosca::hazards::{Category, Hazards};
+
+use crate::error::{Error, ErrorKind};
 
 // TODO: Eventually rewrite policy IDs as &'static str.
 
@@ -8,10 +13,29 @@ use tosca::hazards::Hazards;
 ///
 /// It allows or blocks the requests to devices, or to a specific device,
 /// according to a set of privacy rules.
-#[derive(Debug, PartialEq)]
+///
+/// Route rules are evaluated before hazard rules: a route which is not
+/// in the allowlist of a device, or which is blocked for the device, is always
+/// blocked, while an allowlisted route can still be blocked by its hazards.
+///
+/// A [`Policy`] is default-allow: a request is sent unless its hazards are
+/// blocked. Allow lists turn it into default-deny, so that a request is
+/// skipped as soon as it contains a hazard which is not allowed.
+///
+/// A [`Policy`] can be serialized and deserialized, hence it can be loaded
+/// from a file through [`Policy::from_json_str`]. Missing fields are
+/// considered empty.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Policy {
     block_on_hazards: Hazards,
     block_device_on_hazards: HashMap<usize, Hazards>,
+    // When present, only the contained hazards are allowed.
+    allow_on_hazards: Option<Hazards>,
+    allow_device_on_hazards: HashMap<usize, Hazards>,
+    allow_device_routes: HashMap<usize, HashSet<String>>,
+    block_device_routes: HashMap<usize, HashSet<String>>,
+    implication: bool,
 }
 
 impl Policy {
@@ -23,9 +47,35 @@ impl Policy {
         Self {
             block_on_hazards,
             block_device_on_hazards: HashMap::new(),
+            allow_on_hazards: None,
+            allow_device_on_hazards: HashMap::new(),
+            allow_device_routes: HashMap::new(),
+            block_device_routes: HashMap::new(),
+            implication: false,
         }
     }
 
+    /// Creates a [`Policy`] to block **all** requests that have any
+    /// [`Hazard`](tosca::hazards::Hazard) of the given [`Category`]s in
+    /// their routes.
+    #[must_use]
+    #[inline]
+    pub fn block_on_categories(categories: &[Category]) -> Self {
+        Self::new(categories_hazards(Hazards::new(), categories))
+    }
+
+    /// Creates a [`Policy`] to allow **only** the requests whose route
+    /// [`Hazards`] are all contained in the given ones.
+    ///
+    /// Requests containing any other hazard are blocked.
+    #[must_use]
+    #[inline]
+    pub fn allow_only(allowed: Hazards) -> Self {
+        let mut policy = Self::init();
+        policy.allow_on_hazards = Some(allowed);
+        policy
+    }
+
     /// Creates a [`Policy`] to block **all** [`crate::device::Device`] id
     /// requests that have the given [`Hazards`] in their routes.
     #[must_use]
@@ -44,43 +94,162 @@ impl Policy {
         self
     }
 
+    /// Adds a new [`Policy`] to block **all** [`crate::device::Device`] id
+    /// requests that have any [`Hazard`](tosca::hazards::Hazard) of the
+    /// given [`Category`]s in their routes.
+    ///
+    /// The hazards are added to the ones already blocked for the device.
+    #[must_use]
+    #[inline]
+    pub fn block_device_on_categories(mut self, id: usize, categories: &[Category]) -> Self {
+        let hazards = self.block_device_on_hazards.remove(&id).unwrap_or_default();
+        let _ = self
+            .block_device_on_hazards
+            .insert(id, categories_hazards(hazards, categories));
+        self
+    }
+
+    /// Adds a new [`Policy`] to allow **only** the [`crate::device::Device`]
+    /// id requests whose route [`Hazards`] are all contained in the given
+    /// ones.
+    ///
+    /// Requests to the device containing any other hazard are blocked.
+    #[must_use]
+    #[inline]
+    pub fn allow_device_only(mut self, id: usize, allowed: Hazards) -> Self {
+        let _ = self.allow_device_on_hazards.insert(id, allowed);
+        self
+    }
+
+    /// Adds a new [`Policy`] to allow **only** the given routes for
+    /// a [`crate::device::Device`] id, regardless of their [`Hazards`].
+    ///
+    /// All the other routes of the device are blocked.
+    #[must_use]
+    #[inline]
+    pub fn allow_only_routes(mut self, id: usize, routes: &[&str]) -> Self {
+        let routes = routes.iter().map(|route| (*route).to_string()).collect();
+        let _ = self.allow_device_routes.insert(id, routes);
+        self
+    }
+
+    /// Adds a new [`Policy`] to block the given route for
+    /// a [`crate::device::Device`] id, regardless of its [`Hazards`].
+    #[must_use]
+    #[inline]
+    pub fn block_device_route(mut self, id: usize, route: &str) -> Self {
+        let _ = self
+            .block_device_routes
+            .entry(id)
+            .or_default()
+            .insert(route.to_string());
+        self
+    }
+
+    /// Creates a [`Policy`] from a `JSON` document.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the document is not a valid [`Policy`].
+    pub fn from_json_str(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(|e| {
+            Error::new(
+                ErrorKind::Policy,
+                format!("Error in parsing the privacy policy: {e}"),
+            )
+        })
+    }
+
+    /// Enables hazard implication while evaluating the [`Policy`].
+    ///
+    /// Both the blocked hazards and the hazards of a request are expanded
+    /// with the hazards they imply, as defined by
+    /// [`Hazard::implied`](tosca::hazards::Hazard::implied).
+    /// Hence, blocking a broad hazard also blocks the routes which declare
+    /// only a narrower one.
+    #[must_use]
+    #[inline]
+    pub fn with_implication(mut self) -> Self {
+        self.implication = true;
+        self
+    }
+
     pub(crate) fn init() -> Self {
-        Self {
-            block_on_hazards: Hazards::new(),
-            block_device_on_hazards: HashMap::new(),
-        }
+        Self::default()
+    }
+
+    pub(crate) fn is_route_allowed(&self, id: usize, route: &str) -> bool {
+        self.allow_device_routes
+            .get(&id)
+            .is_none_or(|routes| routes.contains(route))
+    }
+
+    pub(crate) fn is_route_blocked(&self, id: usize, route: &str) -> bool {
+        self.block_device_routes
+            .get(&id)
+            .is_some_and(|routes| routes.contains(route))
     }
 
     pub(crate) fn global_blocked_hazards(&self, hazards: &Hazards) -> Hazards {
-        let mut blocked_hazards = Hazards::new();
-        for hazard in hazards {
-            if self.block_on_hazards.contains(hazard) {
-                blocked_hazards.add(*hazard);
-            }
-        }
-        blocked_hazards
+        self.blocked_hazards(&self.block_on_hazards, hazards)
     }
 
     pub(crate) fn local_blocked_hazards(&self, id: usize, hazards: &Hazards) -> Hazards {
         if let Some(local_hazards) = self.block_device_on_hazards.get(&id) {
-            let mut blocked_hazards = Hazards::new();
-            for hazard in hazards {
-                if local_hazards.contains(hazard) {
-                    blocked_hazards.add(*hazard);
-                }
-            }
-            blocked_hazards
+            self.blocked_hazards(local_hazards, hazards)
         } else {
             Hazards::new()
         }
     }
+
+    pub(crate) fn global_denied_hazards(&self, hazards: &Hazards) -> Hazards {
+        self.allow_on_hazards
+            .as_ref()
+            .map_or_else(Hazards::new, |allowed| {
+                self.denied_hazards(allowed, hazards)
+            })
+    }
+
+    pub(crate) fn local_denied_hazards(&self, id: usize, hazards: &Hazards) -> Hazards {
+        self.allow_device_on_hazards
+            .get(&id)
+            .map_or_else(Hazards::new, |allowed| {
+                self.denied_hazards(allowed, hazards)
+            })
+    }
+
+    fn denied_hazards(&self, allowed_hazards: &Hazards, hazards: &Hazards) -> Hazards {
+        self.expand(hazards)
+            .difference(&self.expand(allowed_hazards))
+    }
+
+    fn blocked_hazards(&self, block_on_hazards: &Hazards, hazards: &Hazards) -> Hazards {
+        self.expand(hazards)
+            .intersection(&self.expand(block_on_hazards))
+    }
+
+    fn expand<'a>(&self, hazards: &'a Hazards) -> Cow<'a, Hazards> {
+        if self.implication {
+            Cow::Owned(hazards.with_implied())
+        } else {
+            Cow::Borrowed(hazards)
+        }
+    }
+}
+
+fn categories_hazards(hazards: Hazards, categories: &[Category]) -> Hazards {
+    categories.iter().fold(hazards, |hazards, category| {
+        hazards.insert_category(*category)
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
 
-    use tosca::hazards::{Hazard, Hazards};
+    use tosca::hazards::{Category, Hazard, Hazards};
+
+    use crate::error::ErrorKind;
 
     use super::Policy;
 
@@ -102,6 +271,11 @@ mod tests {
             &Policy {
                 block_on_hazards,
                 block_device_on_hazards: devices_hazards,
+                allow_on_hazards: None,
+                allow_device_on_hazards: HashMap::new(),
+                allow_device_routes: HashMap::new(),
+                block_device_routes: HashMap::new(),
+                implication: false,
             }
         );
     }
@@ -114,7 +288,12 @@ mod tests {
             policy,
             Policy {
                 block_on_hazards: hazards,
-                block_device_on_hazards: HashMap::new()
+                block_device_on_hazards: HashMap::new(),
+                allow_on_hazards: None,
+                allow_device_on_hazards: HashMap::new(),
+                allow_device_routes: HashMap::new(),
+                block_device_routes: HashMap::new(),
+                implication: false,
             }
         );
     }
@@ -141,4 +320,180 @@ mod tests {
 
         check_device_policies(&policy, global_hazards, &local_hazards);
     }
+
+    #[test]
+    fn allow_only_routes() {
+        let policy = Policy::init().allow_only_routes(1, &["/info"]);
+
+        let mut allowed_routes = HashMap::new();
+        let _ = allowed_routes.insert(1, HashSet::from(["/info".to_string()]));
+
+        assert_eq!(
+            policy,
+            Policy {
+                block_on_hazards: Hazards::new(),
+                block_device_on_hazards: HashMap::new(),
+                allow_on_hazards: None,
+                allow_device_on_hazards: HashMap::new(),
+                allow_device_routes: allowed_routes,
+                block_device_routes: HashMap::new(),
+                implication: false,
+            }
+        );
+
+        // Only the allowlisted route is allowed for the device.
+        assert!(policy.is_route_allowed(1, "/info"));
+        assert!(!policy.is_route_allowed(1, "/on"));
+
+        // Devices without an allowlist allow all routes.
+        assert!(policy.is_route_allowed(2, "/on"));
+    }
+
+    #[test]
+    fn block_device_route() {
+        let policy = Policy::init()
+            .block_device_route(1, "/off")
+            .block_device_route(1, "/toggle");
+
+        // Only the blocked routes of the device are blocked.
+        assert!(policy.is_route_blocked(1, "/off"));
+        assert!(policy.is_route_blocked(1, "/toggle"));
+        assert!(!policy.is_route_blocked(1, "/on"));
+        assert!(!policy.is_route_blocked(2, "/off"));
+    }
+
+    #[test]
+    fn implication_policy() {
+        let policy = Policy::new(Hazards::init(Hazard::AudioVideoRecordAndStore))
+            .block_device_on_hazards(1, Hazards::init(Hazard::PaySubscriptionFee));
+
+        let hazards = Hazards::new()
+            .insert(Hazard::VideoRecordAndStore)
+            .insert(Hazard::SpendMoney);
+
+        // Without implication, only the declared hazards are compared.
+        assert!(policy.global_blocked_hazards(&hazards).is_empty());
+        assert!(policy.local_blocked_hazards(1, &hazards).is_empty());
+
+        let policy = policy.with_implication();
+
+        assert_eq!(
+            policy.global_blocked_hazards(&hazards),
+            Hazards::init(Hazard::VideoRecordAndStore)
+        );
+        assert_eq!(
+            policy.local_blocked_hazards(1, &hazards),
+            Hazards::init(Hazard::SpendMoney)
+        );
+        assert!(policy.local_blocked_hazards(2, &hazards).is_empty());
+    }
+
+    #[test]
+    fn categories_policy() {
+        let policy = Policy::block_on_categories(&[Category::Privacy]);
+
+        assert_eq!(
+            policy,
+            Policy::new(Hazards::new().insert_category(Category::Privacy))
+        );
+
+        let hazards = Hazards::new()
+            .insert(Hazard::LogUsageTime)
+            .insert(Hazard::ElectricEnergyConsumption);
+        assert_eq!(
+            policy.global_blocked_hazards(&hazards),
+            Hazards::init(Hazard::LogUsageTime)
+        );
+
+        // Categories are added to the hazards already blocked for a device.
+        let policy = Policy::init()
+            .block_device_on_hazards(1, Hazards::init(Hazard::Explosion))
+            .block_device_on_categories(1, &[Category::Financial]);
+
+        let hazards = Hazards::new()
+            .insert(Hazard::Explosion)
+            .insert(Hazard::SpendMoney)
+            .insert(Hazard::LogUsageTime);
+        assert_eq!(
+            policy.local_blocked_hazards(1, &hazards),
+            Hazards::new()
+                .insert(Hazard::Explosion)
+                .insert(Hazard::SpendMoney)
+        );
+        assert!(policy.local_blocked_hazards(2, &hazards).is_empty());
+    }
+
+    #[test]
+    fn allow_only_policy() {
+        let policy = Policy::allow_only(Hazards::init(Hazard::ElectricEnergyConsumption))
+            .allow_device_only(1, Hazards::init(Hazard::LogUsageTime));
+
+        let allowed = Hazards::init(Hazard::ElectricEnergyConsumption);
+        let hazards = Hazards::new()
+            .insert(Hazard::ElectricEnergyConsumption)
+            .insert(Hazard::LogUsageTime);
+
+        // Only the hazards outside of the allow lists are denied.
+        assert!(policy.global_denied_hazards(&allowed).is_empty());
+        assert_eq!(
+            policy.global_denied_hazards(&hazards),
+            Hazards::init(Hazard::LogUsageTime)
+        );
+        assert_eq!(
+            policy.local_denied_hazards(1, &hazards),
+            Hazards::init(Hazard::ElectricEnergyConsumption)
+        );
+
+        // Devices without an allow list deny nothing locally.
+        assert!(policy.local_denied_hazards(2, &hazards).is_empty());
+
+        // A default-allow policy denies nothing.
+        assert!(Policy::init().global_denied_hazards(&hazards).is_empty());
+    }
+
+    #[test]
+    fn json_policy() {
+        let policy = Policy::from_json_str(
+            r#"{
+                "block_on_hazards": ["LogUsageTime"],
+                "block_device_on_hazards": { "1": ["Explosion", "FireHazard"] }
+            }"#,
+        )
+        .unwrap();
+
+        let hazards = Hazards::new()
+            .insert(Hazard::LogUsageTime)
+            .insert(Hazard::FireHazard)
+            .insert(Hazard::ElectricEnergyConsumption);
+
+        assert_eq!(
+            policy.global_blocked_hazards(&hazards),
+            Hazards::init(Hazard::LogUsageTime)
+        );
+        assert_eq!(
+            policy.local_blocked_hazards(1, &hazards),
+            Hazards::init(Hazard::FireHazard)
+        );
+        assert!(policy.local_blocked_hazards(2, &hazards).is_empty());
+
+        // A policy with both global and per-device rules is round-tripped.
+        let policy = create_policy()
+            .1
+            .block_device_on_hazards(1, Hazards::init(Hazard::Explosion))
+            .allow_device_only(2, Hazards::init(Hazard::LogUsageTime))
+            .allow_only_routes(3, &["/info"])
+            .block_device_route(4, "/off")
+            .with_implication();
+
+        let json = serde_json::to_string(&policy).unwrap();
+        assert_eq!(Policy::from_json_str(&json).unwrap(), policy);
+
+        // An invalid document.
+        assert_eq!(
+            Policy::from_json_str(r#"{"block_on_hazards": ["Unknown"]}"#)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Policy
+        );
+    }
 }
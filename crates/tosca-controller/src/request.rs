@@ -1,8 +1,17 @@
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
 
-use serde::Serialize;
+use bytes::Bytes;
+
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+
+use serde::{Deserialize, Serialize};
+
+use tokio::sync::OnceCell;
 
 use tracing::error;
 
@@ -112,6 +121,8 @@ pub struct RequestInfo<'device> {
     pub parameters_data: &'device ParametersData,
     /// Response kind.
     pub response_kind: ResponseKind,
+    /// Whether the request is idempotent.
+    pub idempotent: bool,
 }
 
 impl<'device> RequestInfo<'device> {
@@ -124,6 +135,111 @@ impl<'device> RequestInfo<'device> {
             hazards: &request.hazards,
             parameters_data: &request.parameters_data,
             response_kind: request.response_kind,
+            idempotent: request.idempotent,
+        }
+    }
+}
+
+/// Request telemetry.
+///
+/// Timing and size data collected while sending a request to a device.
+///
+/// Device addresses are already resolved, so no `DNS` lookup takes place,
+/// while the connection time is included in the time to the first byte.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct RequestTelemetry {
+    /// Time elapsed until the response headers have been received.
+    pub first_byte: Duration,
+    /// Time elapsed until the response is ready to be parsed.
+    pub total: Duration,
+    /// Response body size, in bytes, as declared by the device.
+    ///
+    /// If [`None`], the size is unknown or the request has been skipped.
+    pub response_size: Option<u64>,
+}
+
+// A device response whose body has been entirely read, so that it can be
+// handed out to all callers of a coalesced request.
+#[derive(Debug, Clone)]
+struct SharedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl SharedResponse {
+    async fn read(response: reqwest::Response) -> Result<Self, Error> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+
+        Ok(Self {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    fn to_response(&self) -> reqwest::Response {
+        let mut response = http::Response::new(self.body.clone());
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers.clone();
+        reqwest::Response::from(response)
+    }
+}
+
+type InFlightSlot = Arc<OnceCell<Result<SharedResponse, Error>>>;
+
+// Requests currently being sent, identified by their route.
+//
+// Concurrent identical requests share the same slot, hence only the first
+// caller sends the request, while the others wait for its response.
+#[derive(Debug, Default)]
+pub(crate) struct InFlightRequests(Mutex<HashMap<String, InFlightSlot>>);
+
+// In-flight requests are transient, so they are not taken into account when
+// comparing controllers.
+impl PartialEq for InFlightRequests {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl InFlightRequests {
+    pub(crate) async fn plain_send(
+        &self,
+        request: &Request,
+        timeout: Option<Duration>,
+    ) -> Result<reqwest::Response, Error> {
+        let slot = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(request.route.clone())
+            .or_default()
+            .clone();
+
+        let shared_response = slot
+            .get_or_init(|| async {
+                SharedResponse::read(request.plain_send(timeout).await?).await
+            })
+            .await;
+
+        // Once completed, remove the slot so that subsequent requests are
+        // sent again. Another caller might have already replaced it.
+        {
+            let mut in_flight = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+            if in_flight
+                .get(&request.route)
+                .is_some_and(|current| Arc::ptr_eq(current, &slot))
+            {
+                let _ = in_flight.remove(&request.route);
+            }
+        }
+
+        match shared_response {
+            Ok(shared_response) => Ok(shared_response.to_response()),
+            Err(e) => Err(e.clone()),
         }
     }
 }
@@ -132,7 +248,7 @@ impl<'device> RequestInfo<'device> {
 ///
 /// A request can either be plain, with no associated parameters, or include
 /// parameters that serve as inputs for device tasks.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Request {
     pub(crate) kind: RestKind,
     pub(crate) hazards: Hazards,
@@ -141,6 +257,7 @@ pub struct Request {
     pub(crate) description: Option<String>,
     pub(crate) parameters_data: ParametersData,
     pub(crate) response_kind: ResponseKind,
+    pub(crate) idempotent: bool,
     pub(crate) device_environment: DeviceEnvironment,
 }
 
@@ -157,6 +274,12 @@ impl Request {
         self.kind
     }
 
+    /// Returns whether the request is idempotent, as declared by the device.
+    #[must_use]
+    pub fn is_idempotent(&self) -> bool {
+        self.idempotent
+    }
+
     /// Returns an immutable reference to the [`ParametersData`] associated with
     /// a request.
     ///
@@ -184,6 +307,7 @@ impl Request {
         let hazards = route_config.data.hazards;
         let parameters_data = route_config.data.parameters;
         let response_kind = route_config.response_kind;
+        let idempotent = route_config.idempotent;
 
         Self {
             kind,
@@ -193,6 +317,7 @@ impl Request {
             description: route_config.data.description.map(|s| s.to_string()),
             parameters_data,
             response_kind,
+            idempotent,
             device_environment,
         }
     }
@@ -230,22 +355,30 @@ impl Request {
         })
     }
 
-    pub(crate) async fn plain_send(&self) -> Result<reqwest::Response, Error> {
+    pub(crate) async fn plain_send(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<reqwest::Response, Error> {
         let request_data =
             self.request_data(|| self.axum_get_plain(), || self.create_params_plain());
 
-        self.parameters_send(request_data).await
+        self.parameters_send(request_data, timeout).await
     }
 
     pub(crate) async fn create_response(
         &self,
         parameters: &ParametersValues<'_>,
+        timeout: Option<Duration>,
     ) -> Result<reqwest::Response, Error> {
         let request_data = self.create_request(parameters)?;
-        self.parameters_send(request_data).await
+        self.parameters_send(request_data, timeout).await
     }
 
-    async fn parameters_send(&self, request_data: RequestData) -> Result<reqwest::Response, Error> {
+    async fn parameters_send(
+        &self,
+        request_data: RequestData,
+        timeout: Option<Duration>,
+    ) -> Result<reqwest::Response, Error> {
         let RequestData {
             request,
             parameters,
@@ -266,6 +399,12 @@ impl Request {
             request_builder
         };
 
+        let request_builder = if let Some(timeout) = timeout {
+            request_builder.timeout(timeout)
+        } else {
+            request_builder
+        };
+
         // Close the connection after issuing a request.
         let response = request_builder.header("Connection", "close").send().await?;
 
@@ -402,7 +541,7 @@ mod tests {
     use tosca::parameters::{ParameterKind, Parameters, ParametersData, ParametersValues};
     use tosca::route::{RestKind, Route, RouteConfig};
 
-    use super::{Request, RequestData, ResponseKind, parameter_error};
+    use super::{Request, RequestData, RequestInfo, ResponseKind, parameter_error};
 
     const ADDRESS_ROUTE: &str = "http://tosca.local/";
     const ADDRESS_ROUTE_WITHOUT_SLASH: &str = "http://tosca.local/";
@@ -429,6 +568,7 @@ mod tests {
                 description,
                 parameters_data: ParametersData::new(),
                 response_kind: ResponseKind::Ok,
+                idempotent: kind.is_idempotent(),
                 device_environment: DeviceEnvironment::Os,
             }
         );
@@ -481,6 +621,7 @@ mod tests {
                 description,
                 parameters_data,
                 response_kind: ResponseKind::Ok,
+                idempotent: kind.is_idempotent(),
                 device_environment: DeviceEnvironment::Os,
             }
         );
@@ -532,6 +673,7 @@ mod tests {
                 description: None,
                 parameters_data: ParametersData::new(),
                 response_kind: ResponseKind::Ok,
+                idempotent: true,
                 device_environment: DeviceEnvironment::Os,
             }
         );
@@ -558,6 +700,38 @@ mod tests {
         plain_request(route, RestKind::Post, Hazards::new());
     }
 
+    #[test]
+    fn request_idempotent() {
+        let request = Request::new(
+            ADDRESS_ROUTE,
+            "light/",
+            DeviceEnvironment::Os,
+            Route::get("Route", "/route").serialize_data(),
+        );
+        assert!(request.is_idempotent());
+        assert!(RequestInfo::new("/route", &request).idempotent);
+
+        // A `GET` route performing a mutation.
+        let request = Request::new(
+            ADDRESS_ROUTE,
+            "light/",
+            DeviceEnvironment::Os,
+            Route::get("Route", "/route")
+                .idempotent(false)
+                .serialize_data(),
+        );
+        assert!(!request.is_idempotent());
+        assert!(!RequestInfo::new("/route", &request).idempotent);
+
+        let request = Request::new(
+            ADDRESS_ROUTE,
+            "light/",
+            DeviceEnvironment::Os,
+            Route::post("Route", "/route").serialize_data(),
+        );
+        assert!(!request.is_idempotent());
+    }
+
     #[test]
     fn create_plain_put_request() {
         let route = Route::put("Route", "/route").description("A PUT route.");
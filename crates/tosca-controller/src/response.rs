@@ -1,3 +1,6 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::SystemTime;
+
 use tosca::response::{InfoResponse, OkResponse, SerialResponse};
 
 use reqwest::Response as ReqwestResponse;
@@ -7,19 +10,28 @@ use serde::{Serialize, de::DeserializeOwned};
 use crate::error::{Error, ErrorKind, Result};
 
 // TODO:
-// OkCollector --> Save Ok responses in order to maintain a history.
-// SerialCollector --> Save serial responses in order to maintain a history.
-// InfoCollector --> Save Info responses in order to maintain a history.
 // StreamCollector --> Save information about a Stream Response before and after
 
+// Decodes a JSON body received from a device.
+//
+// Device bodies are untrusted, so a malformed body must always produce
+// an error, never a panic.
+pub(crate) fn decode_json<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    serde_json::from_slice(bytes)
+        .map_err(|e| Error::new(ErrorKind::JsonResponse, format!("Json error caused by {e}")))
+}
+
 async fn json_response<T>(response: ReqwestResponse) -> Result<T>
 where
     T: Serialize + DeserializeOwned,
 {
-    response
-        .json::<T>()
-        .await
-        .map_err(|e| Error::new(ErrorKind::JsonResponse, format!("Json error caused by {e}")))
+    let bytes = response.bytes().await.map_err(|e| {
+        Error::new(
+            ErrorKind::JsonResponse,
+            format!("Error reading the response body: {e}"),
+        )
+    })?;
+    decode_json(&bytes)
 }
 
 /// An [`OkResponse`] body parser.
@@ -103,11 +115,122 @@ impl StreamResponse {
         })
     }
 
+    /// Writes the whole bytes stream into the given writer.
+    ///
+    /// Returns the number of written bytes.
+    ///
+    /// # Errors
+    ///
+    /// Byte stream parsing may fail due to network errors or data corruption,
+    /// while writing may fail due to writer errors.
+    pub async fn write_to<W>(self, writer: &mut W) -> Result<u64>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        use futures_util::StreamExt;
+        use tokio::io::AsyncWriteExt;
+
+        let mut stream = std::pin::pin!(self.open_stream());
+        let mut written = 0;
+        while let Some(bytes) = stream.next().await {
+            let bytes = bytes?;
+            writer
+                .write_all(&bytes)
+                .await
+                .map_err(|e| write_error(&e))?;
+            written += bytes.len() as u64;
+        }
+        writer.flush().await.map_err(|e| write_error(&e))?;
+
+        Ok(written)
+    }
+
     pub(crate) const fn new(response: ReqwestResponse) -> Self {
         Self(response)
     }
 }
 
+#[cfg(feature = "stream")]
+fn write_error(e: &std::io::Error) -> Error {
+    Error::new(
+        ErrorKind::StreamResponse,
+        format!("Error writing the stream: {e}"),
+    )
+}
+
+/// A parsed response stored by a [`ResponseCollector`].
+#[derive(Debug, PartialEq)]
+pub struct CollectedResponse<T> {
+    /// The time at which the response has been collected.
+    pub received_at: SystemTime,
+    /// The parsed response.
+    pub response: T,
+}
+
+/// A history of parsed responses, grouped by device identifier.
+///
+/// For each device, only the last `capacity` responses are kept, hence
+/// the oldest response is evicted when a new one exceeds the capacity.
+#[derive(Debug, PartialEq)]
+pub struct ResponseCollector<T> {
+    capacity: usize,
+    responses: HashMap<usize, VecDeque<CollectedResponse<T>>>,
+}
+
+impl<T> ResponseCollector<T> {
+    /// Creates a [`ResponseCollector`] keeping at most `capacity` responses
+    /// for each device.
+    #[must_use]
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            responses: HashMap::new(),
+        }
+    }
+
+    /// Stores a parsed response of the device with the given identifier,
+    /// timestamped with the current time.
+    #[inline]
+    pub fn push(&mut self, id: usize, response: T) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let history = self.responses.entry(id).or_default();
+        if history.len() == self.capacity {
+            let _ = history.pop_front();
+        }
+        history.push_back(CollectedResponse {
+            received_at: SystemTime::now(),
+            response,
+        });
+    }
+
+    /// Returns the responses of the device with the given identifier,
+    /// from the oldest to the most recent one.
+    pub fn history(&self, id: usize) -> impl Iterator<Item = &CollectedResponse<T>> {
+        self.responses.get(&id).into_iter().flatten()
+    }
+
+    /// Returns the most recent response of the device with the given
+    /// identifier.
+    #[must_use]
+    #[inline]
+    pub fn latest(&self, id: usize) -> Option<&CollectedResponse<T>> {
+        self.responses.get(&id).and_then(VecDeque::back)
+    }
+}
+
+/// A history of parsed [`OkResponse`]s.
+pub type OkCollector = ResponseCollector<OkResponse>;
+
+/// A history of parsed [`SerialResponse`]s.
+pub type SerialCollector<T> = ResponseCollector<SerialResponse<T>>;
+
+/// A history of parsed [`InfoResponse`]s.
+pub type InfoCollector = ResponseCollector<InfoResponse>;
+
 /// All response types supported by a `tosca` device.
 ///
 /// Each response includes a dedicated body parser to extract the embedded data.
@@ -125,3 +248,145 @@ pub enum Response {
     #[cfg(feature = "stream")]
     StreamBody(StreamResponse),
 }
+
+#[cfg(test)]
+mod tests {
+    use tosca::device::{DeviceClock, DeviceDescription, DeviceMetrics};
+    use tosca::economy::Economy;
+    use tosca::events::Events;
+    use tosca::response::{InfoResponse, OkResponse, SerialResponse};
+
+    use proptest::prelude::*;
+
+    use serde_json::{Value, json};
+
+    use super::{OkCollector, SerialCollector, decode_json};
+
+    // Seed corpus of valid bodies, mutated by the property tests below to
+    // reach the deeper decoding paths.
+    fn seed_corpus() -> Vec<Vec<u8>> {
+        vec![
+            serde_json::to_vec(&OkResponse::ok()).unwrap(),
+            serde_json::to_vec(&SerialResponse::new(json!({"temperature": 20.5}))).unwrap(),
+            serde_json::to_vec(&InfoResponse::new(
+                DeviceMetrics::with_economy(Economy::empty()).add_clock(DeviceClock {
+                    uptime_ms: 5_000,
+                    unix_time_ms: Some(1_700_000_000_000),
+                }),
+            ))
+            .unwrap(),
+            serde_json::to_vec(&Events::empty()).unwrap(),
+            br#"{"error": "Invalid data", "description": "Missing parameter"}"#.to_vec(),
+        ]
+    }
+
+    // Decodes the bytes with all the decoders used on device bodies.
+    //
+    // Only the absence of panics matters, so the results are discarded.
+    fn decode_all(bytes: &[u8]) {
+        let _ = decode_json::<OkResponse>(bytes);
+        let _ = decode_json::<SerialResponse<Value>>(bytes);
+        let _ = decode_json::<InfoResponse>(bytes);
+        let _ = decode_json::<Events>(bytes);
+        let _ = decode_json::<DeviceDescription>(bytes);
+    }
+
+    #[test]
+    fn decode_seed_corpus() {
+        for seed in seed_corpus() {
+            decode_all(&seed);
+        }
+        assert!(decode_json::<OkResponse>(&seed_corpus()[0]).is_ok());
+        assert!(decode_json::<OkResponse>(b"{").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn decode_random_bytes(bytes in proptest::collection::vec(any::<u8>(), 0..512)) {
+            decode_all(&bytes);
+        }
+
+        #[test]
+        fn decode_json_like_bytes(body in r#"[{}\[\]":,.\-0-9a-z_ ]{0,256}"#) {
+            decode_all(body.as_bytes());
+        }
+
+        #[test]
+        fn decode_mutated_seeds(
+            seed in 0..5usize,
+            mutations in proptest::collection::vec((any::<usize>(), any::<u8>()), 0..8),
+            truncate in any::<usize>(),
+        ) {
+            let mut bytes = seed_corpus().swap_remove(seed);
+            for (position, byte) in mutations {
+                let position = position % bytes.len();
+                bytes[position] = byte;
+            }
+            bytes.truncate(truncate % (bytes.len() + 1));
+            decode_all(&bytes);
+        }
+    }
+
+    #[test]
+    fn collector_capacity() {
+        let mut collector = SerialCollector::new(2);
+
+        for value in 0..3u8 {
+            collector.push(0, SerialResponse::new(value));
+        }
+
+        // The oldest response is evicted.
+        assert_eq!(
+            collector
+                .history(0)
+                .map(|collected| &collected.response)
+                .collect::<Vec<_>>(),
+            vec![&SerialResponse::new(1), &SerialResponse::new(2)]
+        );
+        assert_eq!(
+            collector.latest(0).map(|collected| &collected.response),
+            Some(&SerialResponse::new(2))
+        );
+
+        // Responses are ordered by their collection time.
+        let history = collector.history(0).collect::<Vec<_>>();
+        assert!(history[0].received_at <= history[1].received_at);
+
+        // A collector without capacity keeps nothing.
+        let mut collector = OkCollector::new(0);
+        collector.push(0, OkResponse::ok());
+        assert!(collector.latest(0).is_none());
+    }
+
+    #[test]
+    fn collector_devices() {
+        let mut collector = OkCollector::new(4);
+
+        collector.push(0, OkResponse::ok());
+        collector.push(0, OkResponse::ok());
+        collector.push(1, OkResponse::ok());
+
+        // Each device has its own history.
+        assert_eq!(collector.history(0).count(), 2);
+        assert_eq!(collector.history(1).count(), 1);
+        assert_eq!(collector.history(2).count(), 0);
+        assert!(collector.latest(2).is_none());
+    }
+
+    #[cfg(feature = "stream")]
+    #[tokio::test]
+    async fn stream_write_to() {
+        use super::StreamResponse;
+
+        let payload = b"a known stream payload".to_vec();
+        let response = StreamResponse::new(reqwest::Response::from(http::Response::new(
+            payload.clone(),
+        )));
+
+        let mut written = Vec::new();
+        let count = response.write_to(&mut written).await.unwrap();
+
+        assert_eq!(written, payload);
+        assert_eq!(count, payload.len() as u64);
+    }
+}
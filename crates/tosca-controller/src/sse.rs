@@ -0,0 +1,273 @@
+use std::pin::Pin;
+use std::time::Duration;
+
+use tosca::events::Events as ToscaEvents;
+
+use bytes::Bytes;
+
+use futures_util::{Stream, StreamExt, stream};
+
+use tracing::warn;
+
+use crate::error::{Error, ErrorKind, Result};
+
+// Time to wait before reconnecting to a device events stream.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+// Maximum size of a frame, in bytes.
+//
+// A device which never terminates a frame cannot make the buffer grow
+// beyond this size.
+const MAXIMUM_FRAME_SIZE: usize = 64 * 1024;
+
+type BodyStream = Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>;
+
+// Accumulates the bytes of a Server-Sent Events stream and splits them
+// into frames, even when a frame is spread over several chunks.
+#[derive(Default)]
+struct FrameParser {
+    buffer: Vec<u8>,
+    // Number of buffered bytes already searched for a frame terminator.
+    searched: usize,
+}
+
+impl FrameParser {
+    fn push(&mut self, bytes: &[u8]) {
+        // Carriage returns are discarded, so that lines are only terminated
+        // by a line feed.
+        self.buffer
+            .extend(bytes.iter().copied().filter(|byte| *byte != b'\r'));
+    }
+
+    fn clear(&mut self) {
+        self.buffer.clear();
+        self.searched = 0;
+    }
+
+    // Returns the data of the next complete frame, if any.
+    //
+    // Frames without data, such as comments, are skipped.
+    fn next_data(&mut self) -> Result<Option<Vec<u8>>> {
+        loop {
+            // A terminator might start with the last searched byte.
+            let start = self.searched.saturating_sub(1);
+            let Some(position) = self.buffer[start..]
+                .windows(2)
+                .position(|window| window == b"\n\n")
+            else {
+                self.searched = self.buffer.len();
+                return Self::check_frame_size(self.buffer.len()).map(|()| None);
+            };
+
+            let end = start + position;
+            Self::check_frame_size(end)?;
+
+            let frame = self.buffer.drain(..end + 2).collect::<Vec<u8>>();
+            self.searched = 0;
+            if let Some(data) = Self::frame_data(&frame[..end]) {
+                return Ok(Some(data));
+            }
+        }
+    }
+
+    fn check_frame_size(size: usize) -> Result<()> {
+        if size > MAXIMUM_FRAME_SIZE {
+            return Err(Error::new(
+                ErrorKind::StreamResponse,
+                format!("Frame exceeding the maximum size of {MAXIMUM_FRAME_SIZE} bytes"),
+            ));
+        }
+        Ok(())
+    }
+
+    // Joins the data lines of a frame, ignoring all other fields.
+    fn frame_data(frame: &[u8]) -> Option<Vec<u8>> {
+        let mut data: Option<Vec<u8>> = None;
+        for line in frame.split(|byte| *byte == b'\n') {
+            let Some(value) = line.strip_prefix(b"data:") else {
+                continue;
+            };
+            let value = value.strip_prefix(b" ").unwrap_or(value);
+
+            match &mut data {
+                Some(data) => {
+                    data.push(b'\n');
+                    data.extend_from_slice(value);
+                }
+                None => data = Some(value.to_vec()),
+            }
+        }
+        data
+    }
+}
+
+struct EventStream {
+    client: reqwest::Client,
+    url: String,
+    body: Option<BodyStream>,
+    parser: FrameParser,
+    reconnect: bool,
+}
+
+impl EventStream {
+    fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            body: None,
+            parser: FrameParser::default(),
+            reconnect: false,
+        }
+    }
+
+    async fn next_events(&mut self) -> Result<ToscaEvents> {
+        loop {
+            let data = match self.parser.next_data() {
+                Ok(data) => data,
+                Err(e) => {
+                    self.disconnect();
+                    return Err(e);
+                }
+            };
+
+            if let Some(data) = data {
+                return serde_json::from_slice(&data).map_err(|e| {
+                    Error::new(
+                        ErrorKind::StreamResponse,
+                        format!("Error converting frame bytes into events: {e}"),
+                    )
+                });
+            }
+
+            if self.body.is_none() {
+                self.body = Some(self.connect().await?);
+            }
+
+            let Some(body) = self.body.as_mut() else {
+                continue;
+            };
+
+            match body.next().await {
+                Some(Ok(bytes)) => self.parser.push(&bytes),
+                Some(Err(e)) => {
+                    self.disconnect();
+                    return Err(Error::new(
+                        ErrorKind::StreamResponse,
+                        format!("Stream error caused by {e}"),
+                    ));
+                }
+                None => {
+                    warn!("The events stream at {} has been closed", self.url);
+                    self.disconnect();
+                }
+            }
+        }
+    }
+
+    async fn connect(&mut self) -> Result<BodyStream> {
+        // Wait before reconnecting, to not flood an unreachable device.
+        if self.reconnect {
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+        self.reconnect = true;
+
+        let response = self
+            .client
+            .get(&self.url)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(Box::pin(response.bytes_stream()))
+    }
+
+    fn disconnect(&mut self) {
+        // A partial frame cannot be completed by a new connection.
+        self.body = None;
+        self.parser.clear();
+    }
+}
+
+// Opens the Server-Sent Events stream at the given address, reconnecting
+// whenever the connection is lost.
+pub(crate) fn event_stream(url: String) -> impl Stream<Item = Result<ToscaEvents>> + Send {
+    stream::unfold(EventStream::new(url), |mut event_stream| async move {
+        let events = event_stream.next_events().await;
+        Some((events, event_stream))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{FrameParser, MAXIMUM_FRAME_SIZE};
+
+    fn parse_chunks(chunks: &[&[u8]]) -> Vec<Vec<u8>> {
+        let mut parser = FrameParser::default();
+        let mut frames = Vec::new();
+        for chunk in chunks {
+            parser.push(chunk);
+            while let Some(data) = parser.next_data().unwrap() {
+                frames.push(data);
+            }
+        }
+        frames
+    }
+
+    #[test]
+    fn oversized_frame() {
+        let mut parser = FrameParser::default();
+
+        // A frame which is never terminated.
+        let chunk = [b'a'; 1024];
+        let mut result = Ok(None);
+        for _ in 0..=MAXIMUM_FRAME_SIZE / chunk.len() {
+            parser.push(&chunk);
+            result = parser.next_data();
+            if result.is_err() {
+                break;
+            }
+        }
+        assert!(result.is_err());
+
+        // The parser is reused after a reconnection.
+        parser.clear();
+        parser.push(b"data: {}\n\n");
+        assert_eq!(parser.next_data().unwrap(), Some(b"{}".to_vec()));
+    }
+
+    #[test]
+    fn terminator_across_chunks() {
+        let frames = parse_chunks(&[b"data: 1\n", b"\n", b"data: 2\n\ndata:", b" 3\n\n"]);
+        assert_eq!(frames, [b"1".to_vec(), b"2".to_vec(), b"3".to_vec()]);
+    }
+
+    proptest! {
+        #[test]
+        fn parse_random_chunks(
+            chunks in proptest::collection::vec(proptest::collection::vec(any::<u8>(), 0..64), 0..16),
+        ) {
+            let chunks = chunks.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let _ = parse_chunks(&chunks);
+        }
+
+        #[test]
+        fn parse_split_frames(
+            data in proptest::collection::vec("[a-z0-9{}\":, ]{0,32}", 1..4),
+            split in any::<usize>(),
+        ) {
+            let mut stream = Vec::new();
+            for data in &data {
+                stream.extend_from_slice(b": comment\r\n\r\n");
+                stream.extend_from_slice(format!("data: {data}\r\n\r\n").as_bytes());
+            }
+
+            // The frames do not depend on how the stream is chunked.
+            let (first, second) = stream.split_at(split % (stream.len() + 1));
+            let frames = parse_chunks(&[first, second]);
+            let expected = data.into_iter().map(String::into_bytes).collect::<Vec<_>>();
+            prop_assert_eq!(frames, expected);
+        }
+    }
+}
@@ -1,11 +1,15 @@
-use std::net::Ipv4Addr;
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, TcpListener};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
 use tosca::device::{DeviceEnvironment, DeviceKindId};
 use tosca::hazards::{Hazard, Hazards};
 use tosca::parameters::{ParameterKind, Parameters, ParametersData};
 use tosca::response::ResponseKind;
-use tosca::route::{LightOffRoute, LightOnRoute, RestKind, Route};
+use tosca::route::{LightOffRoute, LightOnRoute, RestKind, Route, RouteConfigs};
 
 use tosca_os::devices::light::Light;
 use tosca_os::extract::Path;
@@ -19,7 +23,7 @@ use serde::{Deserialize, Serialize};
 
 use tracing::info;
 
-use crate::device::Device;
+use crate::device::{Description, Device, NetworkInformation, build_device_address};
 use crate::request::Request;
 
 const PORT_ONE: u16 = 3000;
@@ -119,6 +123,180 @@ pub(crate) async fn light_without_toggle(close_rx: tokio::sync::oneshot::Receive
     light(PORT_TWO, "light-without-toggle", false, close_rx).await;
 }
 
+// Creates a device which can be contacted on the local host without
+// running a discovery process.
+pub(crate) fn local_device(
+    name: &str,
+    port: u16,
+    main_route: &str,
+    route_configs: RouteConfigs,
+) -> Device {
+    let address = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+    let mut addresses = HashSet::new();
+    let _ = addresses.insert(address);
+
+    let network_info = NetworkInformation::new(
+        name.into(),
+        addresses,
+        port,
+        HashMap::new(),
+        build_device_address("http", &address, port),
+    );
+
+    let description = Description::new(
+        DeviceKindId::new("Light"),
+        DeviceEnvironment::Os,
+        main_route.into(),
+    );
+
+    Device::new(network_info, description, route_configs)
+}
+
+pub(crate) fn local_light_with_toggle() -> Device {
+    let light_on_route = Route::put("On", "/on")
+        .description("Turn light on.")
+        .with_hazard(Hazard::ElectricEnergyConsumption);
+
+    local_device(
+        "light-with-toggle",
+        PORT_ONE,
+        FIRST_DEVICE_ROUTE,
+        RouteConfigs::new().insert(light_on_route.serialize_data()),
+    )
+}
+
+// Runs a server which answers every request with an `OkResponse` after the
+// given delay, counting the received connections.
+//
+// Returns the server port and the connections counter.
+pub(crate) fn counting_server(delay: Duration) -> (u16, Arc<AtomicUsize>) {
+    json_server(delay, r#"{"action_terminated_correctly":true}"#.into())
+}
+
+// Runs a server which answers every request with the given JSON body after
+// the given delay, counting the received connections.
+//
+// Returns the server port and the connections counter.
+pub(crate) fn json_server(delay: Duration, body: String) -> (u16, Arc<AtomicUsize>) {
+    flaky_json_server(delay, body, 0)
+}
+
+// Runs a server which answers each connection with the next of the given
+// JSON bodies, repeating the last one once all the others have been sent.
+//
+// Returns the server port.
+pub(crate) fn json_sequence_server(bodies: Vec<String>) -> u16 {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let _ = std::thread::spawn(move || {
+        for (connection, stream) in listener.incoming().enumerate() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+
+            let body = &bodies[connection.min(bodies.len() - 1)];
+
+            let mut buffer = [0; 1024];
+            let _ = stream.read(&mut buffer);
+
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+
+    port
+}
+
+// Runs a server which closes the given number of initial connections without
+// answering, and then behaves like the server returned by `counting_server`.
+//
+// Returns the server port and the connections counter.
+pub(crate) fn flaky_server(failures: usize) -> (u16, Arc<AtomicUsize>) {
+    flaky_json_server(
+        Duration::ZERO,
+        r#"{"action_terminated_correctly":true}"#.into(),
+        failures,
+    )
+}
+
+fn flaky_json_server(delay: Duration, body: String, failures: usize) -> (u16, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let server_counter = Arc::clone(&counter);
+
+    let _ = std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let connections = server_counter.fetch_add(1, Ordering::SeqCst);
+
+            if connections < failures {
+                let mut buffer = [0; 1024];
+                let _ = stream.read(&mut buffer);
+                // Close the connection without answering.
+                drop(stream);
+                continue;
+            }
+
+            let body = body.clone();
+            let _ = std::thread::spawn(move || {
+                let mut buffer = [0; 1024];
+                let _ = stream.read(&mut buffer);
+
+                std::thread::sleep(delay);
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            });
+        }
+    });
+
+    (port, counter)
+}
+
+// Runs a server which answers the first connection with a Server-Sent
+// Events stream, writing the given chunks one at a time.
+//
+// Returns the server port.
+#[cfg(feature = "stream")]
+pub(crate) fn sse_server(chunks: Vec<Vec<u8>>) -> u16 {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let _ = std::thread::spawn(move || {
+        let Ok((mut stream, _)) = listener.accept() else {
+            return;
+        };
+
+        let mut buffer = [0; 1024];
+        let _ = stream.read(&mut buffer);
+
+        let _ = write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n"
+        );
+
+        for chunk in chunks {
+            let _ = stream.write_all(&chunk);
+            let _ = stream.flush();
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    });
+
+    port
+}
+
 fn build_route(device: &Device, route: &str) -> String {
     format!(
         "{}{}{}",
@@ -149,6 +327,7 @@ fn check_request(
             description: Some(description.to_string()),
             parameters_data,
             response_kind,
+            idempotent: kind.is_idempotent(),
             device_environment: DeviceEnvironment::Os,
         })
     );
@@ -15,6 +15,7 @@
 //! [datasheet](https://www.alldatasheet.com/datasheet-pdf/pdf/1179499/ETC2/AM312.html).
 
 use core::result::Result;
+use core::time::Duration;
 
 use embedded_hal::digital::InputPin;
 
@@ -83,6 +84,41 @@ where
         }
     }
 
+    /// Waits until motion is detected, ignoring spurious triggers.
+    ///
+    /// Motion is reported only after the line stays high for the whole
+    /// `quiet` period. When the line goes low during that period,
+    /// the trigger is discarded and the wait starts over.
+    ///
+    /// The line is sampled every 50 milliseconds, or once at the end of
+    /// shorter periods.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying pin fails while waiting for
+    /// or reading the input state.
+    pub async fn wait_for_motion_debounced(&mut self, quiet: Duration) -> Result<(), P::Error> {
+        let quiet_ms = u32::try_from(quiet.as_millis()).unwrap_or(u32::MAX);
+
+        'trigger: loop {
+            self.pin.wait_for_high().await?;
+
+            let mut remaining_ms = quiet_ms;
+            while remaining_ms > 0 {
+                let step_ms = remaining_ms.min(DEBOUNCE_MS);
+                self.delay.delay_ms(step_ms).await;
+                remaining_ms -= step_ms;
+
+                // A bounce, start over.
+                if self.pin.is_low()? {
+                    continue 'trigger;
+                }
+            }
+
+            return Ok(());
+        }
+    }
+
     /// Returns `true` if motion is currently detected.
     ///
     /// # Errors
@@ -137,6 +173,103 @@ mod tests {
         am312.pin.done();
     }
 
+    #[tokio::test]
+    async fn test_wait_for_motion_debounced_clean() {
+        let expectations = [
+            PinTransaction::wait_for_state(State::High),
+            PinTransaction::get(State::High),
+            PinTransaction::get(State::High),
+            PinTransaction::get(State::High),
+        ];
+
+        let pin = PinMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut am312 = Am312::new(pin, delay);
+
+        let res = am312
+            .wait_for_motion_debounced(Duration::from_millis(120))
+            .await;
+        assert!(res.is_ok());
+
+        am312.pin.done();
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_motion_debounced_bounce() {
+        let expectations = [
+            // Spurious trigger.
+            PinTransaction::wait_for_state(State::High),
+            PinTransaction::get(State::High),
+            PinTransaction::get(State::Low),
+            // Real motion.
+            PinTransaction::wait_for_state(State::High),
+            PinTransaction::get(State::High),
+            PinTransaction::get(State::High),
+        ];
+
+        let pin = PinMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut am312 = Am312::new(pin, delay);
+
+        let res = am312
+            .wait_for_motion_debounced(Duration::from_millis(100))
+            .await;
+        assert!(res.is_ok());
+
+        am312.pin.done();
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_motion_debounced_no_motion() {
+        // The line never goes high, so the wait never completes.
+        struct LowPin;
+
+        impl embedded_hal::digital::ErrorType for LowPin {
+            type Error = core::convert::Infallible;
+        }
+
+        impl InputPin for LowPin {
+            fn is_high(&mut self) -> Result<bool, Self::Error> {
+                Ok(false)
+            }
+
+            fn is_low(&mut self) -> Result<bool, Self::Error> {
+                Ok(true)
+            }
+        }
+
+        impl Wait for LowPin {
+            async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+                core::future::pending().await
+            }
+
+            async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+
+            async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+                core::future::pending().await
+            }
+
+            async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+                core::future::pending().await
+            }
+
+            async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+                core::future::pending().await
+            }
+        }
+
+        let mut am312 = Am312::new(LowPin, NoopDelay::new());
+
+        let res = tokio::time::timeout(
+            Duration::from_millis(50),
+            am312.wait_for_motion_debounced(Duration::from_millis(100)),
+        )
+        .await;
+        assert!(res.is_err());
+    }
+
     #[test]
     fn test_is_motion_detected_true() {
         let expectations = [PinTransaction::get(State::High)];
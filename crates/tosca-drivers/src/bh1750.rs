@@ -15,6 +15,8 @@ use core::result::Result::{self, Ok};
 use embedded_hal_async::delay::DelayNs;
 use embedded_hal_async::i2c::I2c;
 
+use crate::low_power::LowPower;
+
 // Instruction set architecture opcodes.
 const POWER_DOWN: u8 = 0x00;
 const POWER_ON: u8 = 0x01;
@@ -31,6 +33,14 @@ pub const MTREG_MIN: u8 = 31;
 pub const MTREG_MAX: u8 = 254;
 const DEFAULT_MTREG: u8 = 69; // Default per datasheet.
 
+// Raw value bounds outside of which `MTreg` is adjusted automatically.
+// Above the high bound the sensor is close to saturation, below the low bound
+// the reading has a poor resolution.
+const AUTO_RANGE_HIGH: u16 = 0xF000;
+const AUTO_RANGE_LOW: u16 = 0x0400;
+// Raw value targeted by the automatic adjustment, half of the 16-bit range.
+const AUTO_RANGE_TARGET: u32 = 0x8000;
+
 /// Errors that may occur when interacting with the `BH1750` sensor.
 #[derive(Debug, Copy, Clone)]
 pub enum Bh1750Error<E> {
@@ -41,6 +51,11 @@ pub enum Bh1750Error<E> {
     /// This error occurs when attempting to read a continuous measurement
     /// before the measurement has started.
     ContinuousMeasurementNotStarted,
+    /// Invalid number of samples.
+    ///
+    /// This error occurs when an average is requested over zero samples
+    /// or over more than [`u16::MAX`] samples.
+    InvalidSampleCount,
 }
 
 impl<E> From<E> for Bh1750Error<E> {
@@ -202,6 +217,36 @@ where
         Ok(())
     }
 
+    /// Adjusts the measurement time register (`MTreg`) to the current light
+    /// level and returns the chosen value.
+    ///
+    /// A one-time measurement is performed at the given resolution. When the
+    /// raw value is close to the 16-bit ceiling, `MTreg` is lowered toward
+    /// [`MTREG_MIN`], while when it is very low, `MTreg` is raised toward
+    /// [`MTREG_MAX`]. The new value is scaled to bring the raw value to
+    /// mid-range and it is applied through [`Self::set_mtreg`], so the same
+    /// clamping applies. Otherwise, `MTreg` is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if I²C communication with the sensor fails.
+    pub async fn auto_adjust_mtreg(&mut self, res: Resolution) -> Result<u8, Bh1750Error<E>> {
+        self.start_one_time_measurement(res).await?;
+        self.delay.delay_ms(self.measurement_time_ms(res)).await;
+        let raw = self.read_raw().await?;
+
+        if (AUTO_RANGE_LOW..=AUTO_RANGE_HIGH).contains(&raw) {
+            return Ok(self.mtreg);
+        }
+
+        // The raw value scales linearly with MTreg.
+        let mtreg = u32::from(self.mtreg) * AUTO_RANGE_TARGET / u32::from(raw.max(1));
+        self.set_mtreg(u8::try_from(mtreg).unwrap_or(MTREG_MAX))
+            .await?;
+
+        Ok(self.mtreg)
+    }
+
     /// Performs a one-time measurement and returns the light level in lux.
     ///
     /// After the measurement, the sensor automatically powers down.
@@ -253,6 +298,38 @@ where
         Ok(self.raw_to_lux(raw, res))
     }
 
+    /// Reads `samples` values from a continuous measurement and returns
+    /// their arithmetic mean, in lux.
+    ///
+    /// # Errors
+    ///
+    /// - [`Bh1750Error::InvalidSampleCount`] if `samples` is zero or greater
+    ///   than [`u16::MAX`].
+    /// - [`Bh1750Error::ContinuousMeasurementNotStarted`] if the caller
+    ///   attempts to read before starting the continuous measurement mode.
+    /// - An I²C error if communication with the sensor fails.
+    pub async fn read_continuous_average(&mut self, samples: usize) -> Result<f32, Bh1750Error<E>> {
+        let samples = u16::try_from(samples)
+            .ok()
+            .filter(|samples| *samples > 0)
+            .ok_or(Bh1750Error::InvalidSampleCount)?;
+
+        let res = self
+            .continuous_resolution
+            .ok_or(Bh1750Error::ContinuousMeasurementNotStarted)?;
+
+        let mut sum = 0.0;
+        for _ in 0..samples {
+            // Wait for a new measurement to be available.
+            self.delay.delay_ms(self.measurement_time_ms(res)).await;
+
+            let raw = self.read_raw().await?;
+            sum += self.raw_to_lux(raw, res);
+        }
+
+        Ok(sum / f32::from(samples))
+    }
+
     async fn start_one_time_measurement(&mut self, res: Resolution) -> Result<(), Bh1750Error<E>> {
         self.send_instruction(res.one_time_measurement_opcode())
             .await
@@ -296,6 +373,31 @@ where
     }
 }
 
+/// Sleeping powers the sensor down. Waking powers it on and restarts the
+/// continuous measurement, if any.
+impl<I2C, E, D> LowPower for Bh1750<I2C, D>
+where
+    I2C: I2c<u8, Error = E>,
+    D: DelayNs,
+{
+    type Error = Bh1750Error<E>;
+
+    async fn sleep(&mut self) -> Result<(), Self::Error> {
+        self.power_down().await
+    }
+
+    async fn wake(&mut self) -> Result<(), Self::Error> {
+        self.power_on().await?;
+
+        if let Some(res) = self.continuous_resolution {
+            self.send_instruction(res.continuous_measurement_opcode())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -372,6 +474,66 @@ mod tests {
         bh1750.i2c.done();
     }
 
+    #[tokio::test]
+    async fn test_auto_adjust_mtreg_decrease() {
+        // Saturated reading: MTreg is lowered to 69 * 0x8000 / 0xFFFF = 34 (0x22).
+        let expectations = [
+            I2cTransaction::write(0x23, vec![0x20]), // Start one-time.
+            I2cTransaction::read(0x23, vec![0xFF, 0xFF]),
+            I2cTransaction::write(0x23, vec![0x40 | (0x22 >> 5)]),
+            I2cTransaction::write(0x23, vec![0x60 | (0x22 & 0x1F)]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut bh1750 = Bh1750::new(i2c, delay, Address::Low);
+
+        let mtreg = bh1750.auto_adjust_mtreg(Resolution::High).await.unwrap();
+        assert_eq!(mtreg, 34);
+        assert_eq!(bh1750.mtreg, 34);
+
+        bh1750.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_auto_adjust_mtreg_increase() {
+        // Dim reading: MTreg is raised and clamped to 254 (0xFE, max).
+        let expectations = [
+            I2cTransaction::write(0x23, vec![0x20]), // Start one-time.
+            I2cTransaction::read(0x23, vec![0x00, 0x10]),
+            I2cTransaction::write(0x23, vec![0x40 | (0xFE >> 5)]),
+            I2cTransaction::write(0x23, vec![0x60 | (0xFE & 0x1F)]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut bh1750 = Bh1750::new(i2c, delay, Address::Low);
+
+        let mtreg = bh1750.auto_adjust_mtreg(Resolution::High).await.unwrap();
+        assert_eq!(mtreg, MTREG_MAX);
+        assert_eq!(bh1750.mtreg, MTREG_MAX);
+
+        bh1750.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_auto_adjust_mtreg_unchanged() {
+        // Mid-range reading: MTreg is not written.
+        let expectations = [
+            I2cTransaction::write(0x23, vec![0x20]), // Start one-time.
+            I2cTransaction::read(0x23, vec![0x80, 0x00]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut bh1750 = Bh1750::new(i2c, delay, Address::Low);
+
+        let mtreg = bh1750.auto_adjust_mtreg(Resolution::High).await.unwrap();
+        assert_eq!(mtreg, DEFAULT_MTREG);
+
+        bh1750.i2c.done();
+    }
+
     #[tokio::test]
     async fn test_one_time_measurement() {
         // One-time measurement opcode (High resolution): 0x20.
@@ -414,6 +576,100 @@ mod tests {
         bh1750.i2c.done();
     }
 
+    #[tokio::test]
+    async fn test_continuous_average() {
+        let expectations = [
+            I2cTransaction::write(0x23, vec![0x10]), // Start continuous.
+            I2cTransaction::read(0x23, vec![0x00, 0x64]),
+            I2cTransaction::read(0x23, vec![0x00, 0xC8]),
+            I2cTransaction::read(0x23, vec![0x01, 0x2C]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+
+        let mut bh1750 = Bh1750::new(i2c, delay, Address::Low);
+        bh1750
+            .start_continuous_measurement(Resolution::High)
+            .await
+            .unwrap();
+
+        // Raw values 100, 200 and 300 average to 200.
+        let lux = bh1750.read_continuous_average(3).await.unwrap();
+        assert!((lux - raw_to_lux(200, Resolution::High, DEFAULT_MTREG)).abs() < 1e-3);
+
+        bh1750.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_continuous_average_error_if_not_started() {
+        let i2c = I2cMock::new(&[]);
+        let delay = NoopDelay::new();
+        let mut bh1750 = Bh1750::new(i2c, delay, Address::Low);
+
+        let err = bh1750.read_continuous_average(3).await.unwrap_err();
+        assert!(matches!(err, Bh1750Error::ContinuousMeasurementNotStarted));
+
+        bh1750.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_continuous_average_invalid_samples() {
+        let i2c = I2cMock::new(&[]);
+        let delay = NoopDelay::new();
+        let mut bh1750 = Bh1750::new(i2c, delay, Address::Low);
+
+        let err = bh1750.read_continuous_average(0).await.unwrap_err();
+        assert!(matches!(err, Bh1750Error::InvalidSampleCount));
+
+        let samples = usize::from(u16::MAX) + 1;
+        let err = bh1750.read_continuous_average(samples).await.unwrap_err();
+        assert!(matches!(err, Bh1750Error::InvalidSampleCount));
+
+        bh1750.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_low_power() {
+        let expectations = [
+            I2cTransaction::write(0x23, vec![0x00]), // POWER_DOWN.
+            I2cTransaction::write(0x23, vec![0x01]), // POWER_ON.
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut bh1750 = Bh1750::new(i2c, delay, Address::Low);
+
+        bh1750.sleep().await.unwrap();
+        bh1750.wake().await.unwrap();
+
+        bh1750.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_low_power_continuous_measurement() {
+        let expectations = [
+            I2cTransaction::write(0x23, vec![0x13]), // Start continuous.
+            I2cTransaction::write(0x23, vec![0x00]), // POWER_DOWN.
+            I2cTransaction::write(0x23, vec![0x01]), // POWER_ON.
+            I2cTransaction::write(0x23, vec![0x13]), // Restart continuous.
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut bh1750 = Bh1750::new(i2c, delay, Address::Low);
+
+        bh1750
+            .start_continuous_measurement(Resolution::Low)
+            .await
+            .unwrap();
+
+        bh1750.sleep().await.unwrap();
+        bh1750.wake().await.unwrap();
+
+        bh1750.i2c.done();
+    }
+
     #[tokio::test]
     async fn test_continuous_measurement_error_if_not_started() {
         let i2c = I2cMock::new(&[]);
@@ -0,0 +1,439 @@
+//! # BMP280 Driver
+//!
+//! This module provides an asynchronous, architecture-agnostic driver for the
+//! `BMP280` barometric pressure and temperature sensor, communicating over the
+//! I²C protocol.
+//!
+//! The sensor stores factory calibration coefficients in its non-volatile
+//! memory. They are read once by [`Bmp280::init`](crate::bmp280::Bmp280::init) and then used to compensate
+//! every raw reading through the integer formulas of the datasheet.
+//!
+//! Each reading triggers a measurement in `forced` mode, with an oversampling
+//! of one for both temperature and pressure, after which the sensor goes
+//! back to sleep.
+//!
+//! For detailed specifications, refer to the
+//! [datasheet](https://www.alldatasheet.com/datasheet-pdf/pdf/1132069/BOSCH/BMP280.html).
+
+use core::result::Result::{self, Err, Ok};
+
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+
+use crate::low_power::LowPower;
+
+// Register addresses.
+const REG_CALIBRATION: u8 = 0x88;
+const REG_CHIP_ID: u8 = 0xD0;
+const REG_RESET: u8 = 0xE0;
+const REG_CTRL_MEAS: u8 = 0xF4;
+const REG_DATA: u8 = 0xF7;
+
+// Value identifying the sensor, stored in the chip identifier register.
+const CHIP_ID: u8 = 0x58;
+// Value which resets the sensor when written into the reset register.
+const RESET_VALUE: u8 = 0xB6;
+
+// Temperature and pressure oversampling of one, in forced mode.
+const FORCED_MEASUREMENT: u8 = 0b0010_0101;
+// Temperature and pressure oversampling of one, in sleep mode.
+const SLEEP_MODE: u8 = 0b0010_0100;
+
+// Maximum measurement time with an oversampling of one, rounded up.
+const MEASUREMENT_TIME_MS: u32 = 7;
+// Time needed by the sensor to start up after a reset.
+const STARTUP_TIME_MS: u32 = 2;
+
+/// Errors that may occur when interacting with the `BMP280` sensor.
+#[derive(Debug, Copy, Clone)]
+pub enum Bmp280Error<E> {
+    /// I²C bus error.
+    I2c(E),
+    /// The device does not identify itself as a `BMP280` sensor.
+    ///
+    /// Contains the chip identifier read from the device.
+    InvalidChipId(u8),
+    /// Sensor not initialized.
+    ///
+    /// This error occurs when attempting to read a measurement before
+    /// reading the calibration coefficients.
+    NotInitialized,
+}
+
+impl<E> From<E> for Bmp280Error<E> {
+    fn from(e: E) -> Self {
+        Bmp280Error::I2c(e)
+    }
+}
+
+/// I²C address of the `BMP280` sensor.
+///
+/// The sensor supports two possible addresses, determined by the
+/// state of the SDO pin.
+#[derive(Debug, Clone, Copy)]
+pub enum Address {
+    /// Low: `0x76` when the SDO pin is connected to GND.
+    Low = 0x76,
+    /// High: `0x77` when the SDO pin is connected to VCC.
+    High = 0x77,
+}
+
+// Factory calibration coefficients.
+#[derive(Debug, Clone, Copy)]
+struct Calibration {
+    t1: u16,
+    t2: i16,
+    t3: i16,
+    p1: u16,
+    p2: i16,
+    p3: i16,
+    p4: i16,
+    p5: i16,
+    p6: i16,
+    p7: i16,
+    p8: i16,
+    p9: i16,
+}
+
+impl Calibration {
+    fn from_bytes(buf: &[u8; 24]) -> Self {
+        // Coefficients are stored as little-endian 16-bit words.
+        let unsigned = |i: usize| u16::from_le_bytes([buf[i], buf[i + 1]]);
+        let signed = |i: usize| i16::from_le_bytes([buf[i], buf[i + 1]]);
+
+        Self {
+            t1: unsigned(0),
+            t2: signed(2),
+            t3: signed(4),
+            p1: unsigned(6),
+            p2: signed(8),
+            p3: signed(10),
+            p4: signed(12),
+            p5: signed(14),
+            p6: signed(16),
+            p7: signed(18),
+            p8: signed(20),
+            p9: signed(22),
+        }
+    }
+
+    // Returns the fine temperature value, shared by the temperature and
+    // pressure compensation formulas.
+    fn t_fine(&self, adc_t: i32) -> i32 {
+        let t1 = i32::from(self.t1);
+
+        let var1 = (((adc_t >> 3) - (t1 << 1)) * i32::from(self.t2)) >> 11;
+        let var2 = (((((adc_t >> 4) - t1) * ((adc_t >> 4) - t1)) >> 12) * i32::from(self.t3)) >> 14;
+
+        var1 + var2
+    }
+
+    // Returns the temperature in hundredths of degrees Celsius.
+    fn compensate_temperature(t_fine: i32) -> i32 {
+        (t_fine * 5 + 128) >> 8
+    }
+
+    // Returns the pressure in Pascal as a Q24.8 fixed-point value.
+    fn compensate_pressure(&self, adc_p: i32, t_fine: i32) -> i64 {
+        let mut var1 = i64::from(t_fine) - 128_000;
+        let mut var2 = var1 * var1 * i64::from(self.p6);
+        var2 += (var1 * i64::from(self.p5)) << 17;
+        var2 += i64::from(self.p4) << 35;
+        var1 = ((var1 * var1 * i64::from(self.p3)) >> 8) + ((var1 * i64::from(self.p2)) << 12);
+        var1 = (((1_i64 << 47) + var1) * i64::from(self.p1)) >> 33;
+
+        // Avoid a division by zero, as the datasheet does.
+        if var1 == 0 {
+            return 0;
+        }
+
+        let mut p = 1_048_576 - i64::from(adc_p);
+        p = (((p << 31) - var2) * 3125) / var1;
+        var1 = (i64::from(self.p9) * (p >> 13) * (p >> 13)) >> 25;
+        var2 = (i64::from(self.p8) * p) >> 19;
+
+        ((p + var1 + var2) >> 8) + (i64::from(self.p7) << 4)
+    }
+}
+
+/// The `BMP280` driver.
+pub struct Bmp280<I2C, D>
+where
+    D: DelayNs,
+{
+    i2c: I2C,
+    delay: D,
+    address: Address,
+    calibration: Option<Calibration>,
+}
+
+impl<I2C, E, D> Bmp280<I2C, D>
+where
+    I2C: I2c<u8, Error = E>,
+    D: DelayNs,
+{
+    /// Creates a [`Bmp280`] driver with the given I²C bus, delay provider,
+    /// and address.
+    ///
+    /// [`Self::init`] must be called before reading any measurement.
+    #[must_use]
+    pub fn new(i2c: I2C, delay: D, address: Address) -> Self {
+        Self {
+            i2c,
+            delay,
+            address,
+            calibration: None,
+        }
+    }
+
+    /// Checks the sensor identity and reads its calibration coefficients.
+    ///
+    /// # Errors
+    ///
+    /// - [`Bmp280Error::InvalidChipId`] if the device is not a `BMP280`.
+    /// - An I²C error if communication with the sensor fails.
+    pub async fn init(&mut self) -> Result<(), Bmp280Error<E>> {
+        let mut chip_id = [0u8; 1];
+        self.read_registers(REG_CHIP_ID, &mut chip_id).await?;
+        if chip_id[0] != CHIP_ID {
+            return Err(Bmp280Error::InvalidChipId(chip_id[0]));
+        }
+
+        let mut buf = [0u8; 24];
+        self.read_registers(REG_CALIBRATION, &mut buf).await?;
+        self.calibration = Some(Calibration::from_bytes(&buf));
+
+        Ok(())
+    }
+
+    /// Resets the sensor, as after a power-on.
+    ///
+    /// The calibration coefficients are preserved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the I²C communication with the sensor fails.
+    pub async fn reset(&mut self) -> Result<(), Bmp280Error<E>> {
+        self.write_register(REG_RESET, RESET_VALUE).await?;
+        self.delay.delay_ms(STARTUP_TIME_MS).await;
+
+        Ok(())
+    }
+
+    /// Performs a measurement and returns the temperature in degrees
+    /// Celsius (°C), with a resolution of 0.01 °C.
+    ///
+    /// # Errors
+    ///
+    /// - [`Bmp280Error::NotInitialized`] if the caller attempts to read
+    ///   before calling [`Self::init`].
+    /// - An I²C error if communication with the sensor fails.
+    pub async fn read_temperature(&mut self) -> Result<f32, Bmp280Error<E>> {
+        let calibration = self.calibration.ok_or(Bmp280Error::NotInitialized)?;
+        let (adc_t, _) = self.measure().await?;
+
+        let temperature = Calibration::compensate_temperature(calibration.t_fine(adc_t));
+
+        // The datasheet fixed-point temperature, in hundredths of a degree
+        // within the -40..=85 °C sensor range, is exactly represented by an
+        // `f32`.
+        #[allow(clippy::cast_precision_loss)]
+        let temperature = temperature as f32 / 100.0;
+
+        Ok(temperature)
+    }
+
+    /// Performs a measurement and returns the pressure in Pascal (Pa).
+    ///
+    /// # Errors
+    ///
+    /// - [`Bmp280Error::NotInitialized`] if the caller attempts to read
+    ///   before calling [`Self::init`].
+    /// - An I²C error if communication with the sensor fails.
+    pub async fn read_pressure(&mut self) -> Result<f32, Bmp280Error<E>> {
+        let calibration = self.calibration.ok_or(Bmp280Error::NotInitialized)?;
+        let (adc_t, adc_p) = self.measure().await?;
+
+        // The pressure compensation depends on the temperature.
+        let pressure = calibration.compensate_pressure(adc_p, calibration.t_fine(adc_t));
+
+        // The datasheet fixed-point pressure, in Q24.8 format within the
+        // 300..=1100 hPa sensor range, fits in an `f32` losing at most
+        // a small fraction of a Pascal, well below the sensor resolution.
+        #[allow(clippy::cast_precision_loss)]
+        let pressure = pressure as f32 / 256.0;
+
+        Ok(pressure)
+    }
+
+    async fn measure(&mut self) -> Result<(i32, i32), Bmp280Error<E>> {
+        self.write_register(REG_CTRL_MEAS, FORCED_MEASUREMENT)
+            .await?;
+        self.delay.delay_ms(MEASUREMENT_TIME_MS).await;
+
+        // Pressure and temperature, each as 20-bit values split into
+        // a most significant, a least significant, and an extra byte.
+        let mut buf = [0u8; 6];
+        self.read_registers(REG_DATA, &mut buf).await?;
+
+        let raw = |i: usize| {
+            (i32::from(buf[i]) << 12) | (i32::from(buf[i + 1]) << 4) | (i32::from(buf[i + 2]) >> 4)
+        };
+
+        Ok((raw(3), raw(0)))
+    }
+
+    #[inline]
+    async fn read_registers(&mut self, register: u8, buf: &mut [u8]) -> Result<(), E> {
+        self.i2c
+            .write_read(self.address as u8, &[register], buf)
+            .await
+    }
+
+    #[inline]
+    async fn write_register(&mut self, register: u8, value: u8) -> Result<(), Bmp280Error<E>> {
+        self.i2c
+            .write(self.address as u8, &[register, value])
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Sleeping aborts any ongoing measurement. Waking needs no transaction,
+/// since each reading triggers its own measurement.
+impl<I2C, E, D> LowPower for Bmp280<I2C, D>
+where
+    I2C: I2c<u8, Error = E>,
+    D: DelayNs,
+{
+    type Error = Bmp280Error<E>;
+
+    async fn sleep(&mut self) -> Result<(), Self::Error> {
+        self.write_register(REG_CTRL_MEAS, SLEEP_MODE).await
+    }
+
+    async fn wake(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+    use std::vec;
+    use std::vec::Vec;
+
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    // Calibration coefficients of the datasheet example.
+    fn calibration_bytes() -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend(27504_u16.to_le_bytes());
+        for coefficient in [26435_i16, -1000] {
+            buf.extend(coefficient.to_le_bytes());
+        }
+        buf.extend(36477_u16.to_le_bytes());
+        for coefficient in [-10685_i16, 3024, 2855, 140, -7, 15500, -14600, 6000] {
+            buf.extend(coefficient.to_le_bytes());
+        }
+        buf
+    }
+
+    // Raw pressure 415148 and raw temperature 519888 of the datasheet example.
+    const RAW_DATA: [u8; 6] = [0x65, 0x5A, 0xC0, 0x7E, 0xED, 0x00];
+
+    fn init_expectations() -> Vec<I2cTransaction> {
+        vec![
+            I2cTransaction::write_read(0x76, vec![0xD0], vec![0x58]),
+            I2cTransaction::write_read(0x76, vec![0x88], calibration_bytes()),
+        ]
+    }
+
+    fn measurement_expectations() -> Vec<I2cTransaction> {
+        vec![
+            I2cTransaction::write(0x76, vec![0xF4, 0x25]), // Forced mode.
+            I2cTransaction::write_read(0x76, vec![0xF7], RAW_DATA.to_vec()),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_read_temperature() {
+        let mut expectations = init_expectations();
+        expectations.extend(measurement_expectations());
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut bmp280 = Bmp280::new(i2c, delay, Address::Low);
+
+        bmp280.init().await.unwrap();
+
+        // 25.08 °C from the datasheet.
+        let temperature = bmp280.read_temperature().await.unwrap();
+        assert!((temperature - 25.08).abs() < 0.001);
+
+        bmp280.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_read_pressure() {
+        let mut expectations = init_expectations();
+        expectations.extend(measurement_expectations());
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut bmp280 = Bmp280::new(i2c, delay, Address::Low);
+
+        bmp280.init().await.unwrap();
+
+        // 100653.27 Pa from the datasheet, computed with the floating-point
+        // formula, which differs from the integer one by a few hundredths.
+        let pressure = bmp280.read_pressure().await.unwrap();
+        assert!((pressure - 100_653.27).abs() < 0.05);
+
+        bmp280.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_low_power() {
+        let expectations = [I2cTransaction::write(0x76, vec![0xF4, 0x24])]; // Sleep mode.
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut bmp280 = Bmp280::new(i2c, delay, Address::Low);
+
+        bmp280.sleep().await.unwrap();
+        bmp280.wake().await.unwrap();
+
+        bmp280.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_chip_id() {
+        let expectations = [I2cTransaction::write_read(0x76, vec![0xD0], vec![0x60])];
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut bmp280 = Bmp280::new(i2c, delay, Address::Low);
+
+        let err = bmp280.init().await.unwrap_err();
+        assert!(matches!(err, Bmp280Error::InvalidChipId(0x60)));
+
+        bmp280.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_read_error_if_not_initialized() {
+        let i2c = I2cMock::new(&[]);
+        let delay = NoopDelay::new();
+        let mut bmp280 = Bmp280::new(i2c, delay, Address::Low);
+
+        let err = bmp280.read_pressure().await.unwrap_err();
+        assert!(matches!(err, Bmp280Error::NotInitialized));
+
+        bmp280.i2c.done();
+    }
+}
@@ -31,6 +31,16 @@ const START_SIGNAL_HIGH_US: u32 = 40; // Then releases the line (high) for ~20
 const BIT_SAMPLE_DELAY_US: u32 = 35; // Time after which to sample the data bit.
 const POLL_DELAY_US: u32 = 1; // Delay between pin state polls when waiting for edges.
 const MAX_ATTEMPTS: usize = 100; // Maximum polling iterations before timeout.
+const RETRY_DELAY_MS: u32 = 2000; // Minimum interval between two consecutive reads.
+
+/// Unit of measurement for a temperature value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureUnit {
+    /// Degrees Celsius (°C).
+    Celsius,
+    /// Degrees Fahrenheit (°F).
+    Fahrenheit,
+}
 
 /// A single humidity and temperature measurement.
 #[derive(Debug, Clone, Copy)]
@@ -41,6 +51,54 @@ pub struct Measurement {
     pub temperature: f32,
 }
 
+impl Measurement {
+    /// Returns the temperature in the given [`TemperatureUnit`].
+    #[must_use]
+    pub fn temperature_in(&self, unit: TemperatureUnit) -> f32 {
+        match unit {
+            TemperatureUnit::Celsius => self.temperature,
+            TemperatureUnit::Fahrenheit => self.temperature * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    /// Returns the temperature in degrees Fahrenheit (°F).
+    #[must_use]
+    pub fn temperature_fahrenheit(&self) -> f32 {
+        self.temperature_in(TemperatureUnit::Fahrenheit)
+    }
+
+    /// Returns the heat index in degrees Celsius (°C), that is the
+    /// temperature perceived by the human body given the relative humidity.
+    ///
+    /// The heat index is computed with the formulas of the US National
+    /// Weather Service: a simple formula for mild conditions, and the
+    /// Rothfusz regression when the simple result reaches 80 °F, without
+    /// the adjustments for extreme humidity values.
+    #[must_use]
+    pub fn heat_index(&self) -> f32 {
+        let t = self.temperature_fahrenheit();
+        let rh = self.humidity;
+
+        let simple = 0.5 * (t + 61.0 + (t - 68.0) * 1.2 + rh * 0.094);
+
+        // The simple formula is averaged with the temperature to decide
+        // whether the regression is needed.
+        let heat_index = if f32::midpoint(simple, t) < 80.0 {
+            simple
+        } else {
+            -42.379 + 2.049_015 * t + 10.143_331 * rh
+                - 0.224_755 * t * rh
+                - 0.006_837_83 * t * t
+                - 0.054_817_17 * rh * rh
+                + 0.001_228_74 * t * t * rh
+                + 0.000_852_82 * t * rh * rh
+                - 0.000_001_99 * t * t * rh * rh
+        };
+
+        (heat_index - 32.0) * 5.0 / 9.0
+    }
+}
+
 /// Errors that may occur when interacting with the `DHT22` sensor.
 #[derive(Debug)]
 pub enum Dht22Error<E> {
@@ -91,6 +149,23 @@ where
     /// - The sensor does not respond within the expected timing window
     /// - The received data fails checksum validation
     pub fn read(&mut self) -> Result<Measurement, Dht22Error<P::Error>> {
+        self.read_debug().map(|(measurement, _)| measurement)
+    }
+
+    /// Reads a single humidity and temperature measurement, returning it
+    /// together with the five raw bytes sent by the sensor.
+    ///
+    /// The raw bytes are, in order: humidity high, humidity low,
+    /// temperature high, temperature low, and checksum.
+    /// They help diagnosing timing issues when bringing up a new board.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Reading from the pin fails
+    /// - The sensor does not respond within the expected timing window
+    /// - The received data fails checksum validation
+    pub fn read_debug(&mut self) -> Result<(Measurement, [u8; 5]), Dht22Error<P::Error>> {
         // Initiate communication by sending the start signal to the sensor.
         self.send_start_signal()?;
 
@@ -103,10 +178,42 @@ where
         // Validate that the transmitted checksum matches the calculated one.
         Self::validate_checksum(hh, hl, th, tl, checksum)?;
 
-        Ok(Measurement {
+        let measurement = Measurement {
             humidity: Self::decode_humidity(hh, hl),
             temperature: Self::decode_temperature(th, tl),
-        })
+        };
+
+        Ok((measurement, [hh, hl, th, tl, checksum]))
+    }
+
+    /// Reads a single humidity and temperature measurement, retrying up to
+    /// `retries` more times when the read fails because of a checksum
+    /// mismatch or a timeout.
+    ///
+    /// Before each retry, the driver waits for about 2 seconds, the minimum
+    /// interval the sensor requires between two consecutive reads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Reading from the pin fails, without any further retry
+    /// - All attempts fail because of a checksum mismatch or a timeout,
+    ///   returning the error of the last attempt
+    pub fn read_with_retries(
+        &mut self,
+        retries: usize,
+    ) -> Result<Measurement, Dht22Error<P::Error>> {
+        let mut retries_left = retries;
+
+        loop {
+            match self.read() {
+                Err(Dht22Error::ChecksumMismatch | Dht22Error::Timeout) if retries_left > 0 => {
+                    retries_left -= 1;
+                    SyncDelay::delay_ms(&mut self.delay, RETRY_DELAY_MS);
+                }
+                result => return result,
+            }
+        }
     }
 
     fn send_start_signal(&mut self) -> Result<(), Dht22Error<P::Error>> {
@@ -223,9 +330,38 @@ mod tests {
     extern crate std;
     use std::vec;
 
+    use std::io::ErrorKind;
+    use std::vec::Vec;
+
+    use embedded_hal_mock::eh1::MockError;
     use embedded_hal_mock::eh1::delay::NoopDelay;
     use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
 
+    // Pin transactions of a complete read returning the given bytes.
+    fn read_expectations(bytes: [u8; 5]) -> Vec<PinTransaction> {
+        let mut expectations = vec![
+            PinTransaction::set(State::Low), // Start signal.
+            PinTransaction::set(State::High),
+            PinTransaction::get(State::Low), // Sensor response.
+            PinTransaction::get(State::High),
+        ];
+
+        for byte in bytes {
+            for i in 0..8 {
+                let bit = if byte & (1 << (7 - i)) != 0 {
+                    State::High
+                } else {
+                    State::Low
+                };
+                expectations.push(PinTransaction::get(State::Low)); // Falling edge.
+                expectations.push(PinTransaction::get(State::High)); // Rising edge.
+                expectations.push(PinTransaction::get(bit)); // Sampling.
+            }
+        }
+
+        expectations
+    }
+
     #[test]
     fn test_send_start_signal() {
         let expectations = [
@@ -326,6 +462,112 @@ mod tests {
         assert!((temperature_neg + 25.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_read_debug() {
+        // 60.0% and -25.0°C.
+        let bytes = [0x02, 0x58, 0x80, 0xFA, 0xD4];
+        let expectations = read_expectations(bytes);
+
+        let pin = PinMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut dht22 = Dht22::new(pin, delay);
+
+        let (measurement, raw) = dht22.read_debug().unwrap();
+        assert!((measurement.humidity - 60.0).abs() < f32::EPSILON);
+        assert!((measurement.temperature + 25.0).abs() < f32::EPSILON);
+        assert_eq!(raw, bytes);
+
+        dht22.pin.done();
+    }
+
+    #[test]
+    fn test_read_with_retries_checksum_once() {
+        // 60.0% and 25.0°C, first with a wrong checksum and then a right one.
+        let mut expectations = read_expectations([0x02, 0x58, 0x00, 0xFA, 0x00]);
+        expectations.extend(read_expectations([0x02, 0x58, 0x00, 0xFA, 0x54]));
+
+        let pin = PinMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut dht22 = Dht22::new(pin, delay);
+
+        let measurement = dht22.read_with_retries(1).unwrap();
+        assert!((measurement.humidity - 60.0).abs() < f32::EPSILON);
+        assert!((measurement.temperature - 25.0).abs() < f32::EPSILON);
+
+        dht22.pin.done();
+    }
+
+    #[test]
+    fn test_read_with_retries_exhausted() {
+        let mut expectations = read_expectations([0x02, 0x58, 0x00, 0xFA, 0x00]);
+        expectations.extend(read_expectations([0x02, 0x58, 0x00, 0xFA, 0x00]));
+
+        let pin = PinMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut dht22 = Dht22::new(pin, delay);
+
+        let result = dht22.read_with_retries(1);
+        assert!(matches!(result, Err(Dht22Error::ChecksumMismatch)));
+
+        dht22.pin.done();
+    }
+
+    #[test]
+    fn test_read_with_retries_pin_error() {
+        // Pin errors are not retried.
+        let expectations =
+            [PinTransaction::set(State::Low).with_error(MockError::Io(ErrorKind::NotConnected))];
+
+        let pin = PinMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut dht22 = Dht22::new(pin, delay);
+
+        let result = dht22.read_with_retries(3);
+        assert!(matches!(result, Err(Dht22Error::Pin(_))));
+
+        dht22.pin.done();
+    }
+
+    #[test]
+    fn test_temperature_fahrenheit() {
+        let measurement = Measurement {
+            humidity: 50.0,
+            temperature: 25.0,
+        };
+
+        assert!((measurement.temperature_fahrenheit() - 77.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_temperature_unit() {
+        let measurement = Measurement {
+            humidity: 50.0,
+            temperature: -40.0,
+        };
+
+        assert!((measurement.temperature_in(TemperatureUnit::Celsius) + 40.0).abs() < f32::EPSILON);
+        assert!(
+            (measurement.temperature_in(TemperatureUnit::Fahrenheit) + 40.0).abs() < f32::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_heat_index() {
+        // Rothfusz regression: 89.6 °F and 70% → 104.74 °F → 40.41 °C.
+        let hot = Measurement {
+            humidity: 70.0,
+            temperature: 32.0,
+        };
+        assert!((hot.heat_index() - 40.41).abs() < 0.05);
+
+        // Simple formula: 68 °F and 50% → 66.85 °F → 19.36 °C.
+        let mild = Measurement {
+            humidity: 50.0,
+            temperature: 20.0,
+        };
+        assert!((mild.heat_index() - 19.36).abs() < 0.05);
+    }
+
     #[test]
     fn test_validate_checksum() {
         let result_ok = Dht22::<PinMock, NoopDelay>::validate_checksum(1, 2, 3, 4, 10);
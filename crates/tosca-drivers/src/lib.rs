@@ -18,6 +18,10 @@ pub mod am312;
 #[cfg(feature = "bh1750")]
 pub mod bh1750;
 
+/// The `BMP280` driver.
+#[cfg(feature = "bmp280")]
+pub mod bmp280;
+
 /// The `DHT22` driver.
 #[cfg(feature = "dht22")]
 pub mod dht22;
@@ -25,3 +29,14 @@ pub mod dht22;
 /// The `DS18B20` driver.
 #[cfg(feature = "ds18b20")]
 pub mod ds18b20;
+
+/// A uniform interface to put sensors into a low power state.
+pub mod low_power;
+
+/// The `SHT31` driver.
+#[cfg(feature = "sht31")]
+pub mod sht31;
+
+/// A bit-banged I²C bus.
+#[cfg(feature = "soft-i2c")]
+pub mod soft_i2c;
@@ -0,0 +1,20 @@
+//! # Low Power
+//!
+//! This module provides the [`LowPower`](crate::low_power::LowPower) trait, a uniform way to put
+//! sensors into a low power state between readings, for instance on
+//! battery-powered devices.
+
+use core::future::Future;
+use core::result::Result;
+
+/// A sensor which can be put into a low power state.
+pub trait LowPower {
+    /// Error returned when the sensor cannot change its power state.
+    type Error;
+
+    /// Puts the sensor into its low power state.
+    fn sleep(&mut self) -> impl Future<Output = Result<(), Self::Error>>;
+
+    /// Brings the sensor back from its low power state, ready to measure.
+    fn wake(&mut self) -> impl Future<Output = Result<(), Self::Error>>;
+}
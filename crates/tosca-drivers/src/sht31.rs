@@ -0,0 +1,504 @@
+//! # SHT31 Driver
+//!
+//! This module provides an asynchronous, architecture-agnostic driver for the
+//! `SHT31` temperature and humidity sensor, communicating over the I²C
+//! protocol.
+//!
+//! The driver supports both single-shot and periodic measurements.
+//! Every measurement consists of a temperature and a humidity word, each
+//! followed by a CRC-8 checksum which is validated before decoding.
+//!
+//! Commands are sent with clock stretching disabled, so the sensor can also
+//! be driven through buses which do not support it, such as the
+//! [`SoftI2c`](crate::soft_i2c::SoftI2c) one.
+//!
+//! For detailed specifications, refer to the
+//! [datasheet](https://www.alldatasheet.com/datasheet-pdf/pdf/1247532/SENSIRION/SHT31-DIS.html).
+
+use core::result::Result::{self, Err, Ok};
+
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+
+use crate::low_power::LowPower;
+
+// Command opcodes.
+const FETCH_DATA: u16 = 0xE000;
+const BREAK: u16 = 0x3093;
+const SOFT_RESET: u16 = 0x30A2;
+
+// Time needed by the sensor to be ready after a soft reset or a break command.
+const COMMAND_RECOVERY_MS: u32 = 1;
+
+// CRC-8 parameters: polynomial x^8 + x^5 + x^4 + 1, initialized to 0xFF.
+const CRC_POLYNOMIAL: u8 = 0x31;
+const CRC_INIT: u8 = 0xFF;
+
+/// Errors that may occur when interacting with the `SHT31` sensor.
+#[derive(Debug, Copy, Clone)]
+pub enum Sht31Error<E> {
+    /// I²C bus error.
+    I2c(E),
+    /// Data checksum mismatch.
+    CrcMismatch,
+    /// Periodic measurement not started.
+    ///
+    /// This error occurs when attempting to read a periodic measurement
+    /// before the measurement has started.
+    PeriodicMeasurementNotStarted,
+}
+
+impl<E> From<E> for Sht31Error<E> {
+    fn from(e: E) -> Self {
+        Sht31Error::I2c(e)
+    }
+}
+
+/// I²C address of the `SHT31` sensor.
+///
+/// The sensor supports two possible addresses, determined by the
+/// state of the ADDR pin.
+#[derive(Debug, Clone, Copy)]
+pub enum Address {
+    /// Low: `0x44` when the ADDR pin is connected to GND.
+    Low = 0x44,
+    /// High: `0x45` when the ADDR pin is connected to VCC.
+    High = 0x45,
+}
+
+/// Repeatability of the measurements.
+///
+/// A higher repeatability reduces the noise of the measurements, at the
+/// cost of a longer measurement time.
+#[derive(Debug, Clone, Copy)]
+pub enum Repeatability {
+    /// High repeatability.
+    ///
+    /// The measurement time is 15.5 ms.
+    High,
+    /// Medium repeatability.
+    ///
+    /// The measurement time is 6.5 ms.
+    Medium,
+    /// Low repeatability.
+    ///
+    /// The measurement time is 4.5 ms.
+    Low,
+}
+
+impl Repeatability {
+    #[inline]
+    const fn single_shot_opcode(self) -> u16 {
+        match self {
+            Self::High => 0x2400,
+            Self::Medium => 0x240B,
+            Self::Low => 0x2416,
+        }
+    }
+
+    #[inline]
+    const fn measurement_time_ms(self) -> u32 {
+        // Maximum measurement times from the datasheet, rounded up.
+        match self {
+            Self::High => 16,
+            Self::Medium => 7,
+            Self::Low => 5,
+        }
+    }
+}
+
+/// Number of measurements per second performed in periodic mode.
+#[derive(Debug, Clone, Copy)]
+pub enum MeasurementRate {
+    /// One measurement every two seconds.
+    HalfPerSecond,
+    /// One measurement per second.
+    OnePerSecond,
+    /// Two measurements per second.
+    TwoPerSecond,
+    /// Four measurements per second.
+    FourPerSecond,
+    /// Ten measurements per second.
+    TenPerSecond,
+}
+
+impl MeasurementRate {
+    #[inline]
+    const fn periodic_opcode(self, repeatability: Repeatability) -> u16 {
+        match (self, repeatability) {
+            (Self::HalfPerSecond, Repeatability::High) => 0x2032,
+            (Self::HalfPerSecond, Repeatability::Medium) => 0x2024,
+            (Self::HalfPerSecond, Repeatability::Low) => 0x202F,
+            (Self::OnePerSecond, Repeatability::High) => 0x2130,
+            (Self::OnePerSecond, Repeatability::Medium) => 0x2126,
+            (Self::OnePerSecond, Repeatability::Low) => 0x212D,
+            (Self::TwoPerSecond, Repeatability::High) => 0x2236,
+            (Self::TwoPerSecond, Repeatability::Medium) => 0x2220,
+            (Self::TwoPerSecond, Repeatability::Low) => 0x222B,
+            (Self::FourPerSecond, Repeatability::High) => 0x2334,
+            (Self::FourPerSecond, Repeatability::Medium) => 0x2322,
+            (Self::FourPerSecond, Repeatability::Low) => 0x2329,
+            (Self::TenPerSecond, Repeatability::High) => 0x2737,
+            (Self::TenPerSecond, Repeatability::Medium) => 0x2721,
+            (Self::TenPerSecond, Repeatability::Low) => 0x272A,
+        }
+    }
+}
+
+/// A single humidity and temperature measurement.
+#[derive(Debug, Clone, Copy)]
+pub struct Measurement {
+    /// Relative humidity as a percentage (% RH).
+    pub humidity: f32,
+    /// Temperature in degrees Celsius (°C).
+    pub temperature: f32,
+}
+
+/// The `SHT31` driver.
+pub struct Sht31<I2C, D>
+where
+    D: DelayNs,
+{
+    i2c: I2C,
+    delay: D,
+    address: Address,
+    // Command of the running periodic measurement.
+    periodic_command: Option<u16>,
+    // Command of the periodic measurement stopped by a sleep.
+    suspended_command: Option<u16>,
+}
+
+impl<I2C, E, D> Sht31<I2C, D>
+where
+    I2C: I2c<u8, Error = E>,
+    D: DelayNs,
+{
+    /// Creates a [`Sht31`] driver with the given I²C bus, delay provider,
+    /// and address.
+    #[must_use]
+    pub fn new(i2c: I2C, delay: D, address: Address) -> Self {
+        Self {
+            i2c,
+            delay,
+            address,
+            periodic_command: None,
+            suspended_command: None,
+        }
+    }
+
+    /// Resets the sensor to its default state.
+    ///
+    /// Must be called only when the sensor is not performing periodic
+    /// measurements.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the I²C communication with the sensor fails.
+    pub async fn soft_reset(&mut self) -> Result<(), Sht31Error<E>> {
+        self.send_command(SOFT_RESET).await?;
+        self.delay.delay_ms(COMMAND_RECOVERY_MS).await;
+
+        Ok(())
+    }
+
+    /// Performs a single-shot measurement with the given repeatability.
+    ///
+    /// # Errors
+    ///
+    /// - [`Sht31Error::CrcMismatch`] if the received data fails checksum
+    ///   validation.
+    /// - An I²C error if communication with the sensor fails.
+    pub async fn single_shot_measurement(
+        &mut self,
+        repeatability: Repeatability,
+    ) -> Result<Measurement, Sht31Error<E>> {
+        self.send_command(repeatability.single_shot_opcode())
+            .await?;
+        self.delay
+            .delay_ms(repeatability.measurement_time_ms())
+            .await;
+
+        let mut buf = [0u8; 6];
+        self.i2c.read(self.address as u8, &mut buf).await?;
+
+        Self::decode_measurement(buf)
+    }
+
+    /// Starts periodic measurement with the given repeatability and rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the I²C configuration write fails.
+    pub async fn start_periodic_measurement(
+        &mut self,
+        repeatability: Repeatability,
+        rate: MeasurementRate,
+    ) -> Result<(), Sht31Error<E>> {
+        let command = rate.periodic_opcode(repeatability);
+        self.send_command(command).await?;
+        self.periodic_command = Some(command);
+        self.suspended_command = None;
+
+        Ok(())
+    }
+
+    /// Reads the most recent value from a periodic measurement.
+    ///
+    /// The sensor does not acknowledge the read when no new measurement
+    /// is available since the last one, resulting in an I²C error.
+    ///
+    /// # Errors
+    ///
+    /// - [`Sht31Error::PeriodicMeasurementNotStarted`] if the caller
+    ///   attempts to read before starting the periodic measurement mode.
+    /// - [`Sht31Error::CrcMismatch`] if the received data fails checksum
+    ///   validation.
+    /// - An I²C error if communication with the sensor fails.
+    pub async fn read_periodic_measurement(&mut self) -> Result<Measurement, Sht31Error<E>> {
+        if self.periodic_command.is_none() {
+            return Err(Sht31Error::PeriodicMeasurementNotStarted);
+        }
+
+        let mut buf = [0u8; 6];
+        self.i2c
+            .write_read(self.address as u8, &FETCH_DATA.to_be_bytes(), &mut buf)
+            .await?;
+
+        Self::decode_measurement(buf)
+    }
+
+    /// Stops the periodic measurement, bringing the sensor back to
+    /// single-shot mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the I²C communication with the sensor fails.
+    pub async fn stop_periodic_measurement(&mut self) -> Result<(), Sht31Error<E>> {
+        self.send_command(BREAK).await?;
+        self.periodic_command = None;
+        self.delay.delay_ms(COMMAND_RECOVERY_MS).await;
+
+        Ok(())
+    }
+
+    fn decode_measurement(buf: [u8; 6]) -> Result<Measurement, Sht31Error<E>> {
+        // Temperature word, its checksum, humidity word, and its checksum.
+        let temperature = Self::checked_word(buf[0], buf[1], buf[2])?;
+        let humidity = Self::checked_word(buf[3], buf[4], buf[5])?;
+
+        Ok(Measurement {
+            humidity: Self::decode_humidity(humidity),
+            temperature: Self::decode_temperature(temperature),
+        })
+    }
+
+    #[inline]
+    fn checked_word(high: u8, low: u8, checksum: u8) -> Result<u16, Sht31Error<E>> {
+        if crc8(&[high, low]) == checksum {
+            Ok(u16::from_be_bytes([high, low]))
+        } else {
+            Err(Sht31Error::CrcMismatch)
+        }
+    }
+
+    #[inline]
+    fn decode_humidity(raw: u16) -> f32 {
+        // RH = 100 * raw / (2^16 - 1)
+        100.0 * f32::from(raw) / 65535.0
+    }
+
+    #[inline]
+    fn decode_temperature(raw: u16) -> f32 {
+        // T = -45 + 175 * raw / (2^16 - 1)
+        -45.0 + 175.0 * f32::from(raw) / 65535.0
+    }
+
+    #[inline]
+    async fn send_command(&mut self, command: u16) -> Result<(), Sht31Error<E>> {
+        self.i2c
+            .write(self.address as u8, &command.to_be_bytes())
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// The sensor idles between single-shot measurements, hence only a running
+/// periodic measurement is stopped on sleep, and restarted on wake.
+impl<I2C, E, D> LowPower for Sht31<I2C, D>
+where
+    I2C: I2c<u8, Error = E>,
+    D: DelayNs,
+{
+    type Error = Sht31Error<E>;
+
+    async fn sleep(&mut self) -> Result<(), Self::Error> {
+        if let Some(command) = self.periodic_command {
+            self.stop_periodic_measurement().await?;
+            self.suspended_command = Some(command);
+        }
+
+        Ok(())
+    }
+
+    async fn wake(&mut self) -> Result<(), Self::Error> {
+        if let Some(command) = self.suspended_command.take() {
+            self.send_command(command).await?;
+            self.periodic_command = Some(command);
+        }
+
+        Ok(())
+    }
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    let mut crc = CRC_INIT;
+
+    for byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ CRC_POLYNOMIAL
+            } else {
+                crc << 1
+            };
+        }
+    }
+
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+    use std::vec;
+
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    // Temperature word 0x6666 (25.0 °C) and humidity word 0x8000 (50.0% RH),
+    // each followed by its checksum.
+    const MEASUREMENT: [u8; 6] = [0x66, 0x66, 0x93, 0x80, 0x00, 0xA2];
+
+    #[test]
+    fn test_crc8() {
+        // Example from the datasheet.
+        assert_eq!(crc8(&[0xBE, 0xEF]), 0x92);
+    }
+
+    #[tokio::test]
+    async fn test_single_shot_measurement() {
+        let expectations = [
+            I2cTransaction::write(0x44, vec![0x24, 0x00]), // High repeatability.
+            I2cTransaction::read(0x44, MEASUREMENT.to_vec()),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut sht31 = Sht31::new(i2c, delay, Address::Low);
+
+        let measurement = sht31
+            .single_shot_measurement(Repeatability::High)
+            .await
+            .unwrap();
+        assert!((measurement.temperature - 25.0).abs() < 0.01);
+        assert!((measurement.humidity - 50.0).abs() < 0.01);
+
+        sht31.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_single_shot_measurement_crc_mismatch() {
+        let mut data = MEASUREMENT;
+        data[5] ^= 0xFF; // Corrupt the humidity checksum.
+
+        let expectations = [
+            I2cTransaction::write(0x44, vec![0x24, 0x00]), // High repeatability.
+            I2cTransaction::read(0x44, data.to_vec()),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut sht31 = Sht31::new(i2c, delay, Address::Low);
+
+        let err = sht31
+            .single_shot_measurement(Repeatability::High)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Sht31Error::CrcMismatch));
+
+        sht31.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_periodic_measurement_flow() {
+        let expectations = [
+            I2cTransaction::write(0x45, vec![0x21, 0x30]), // 1 mps, high repeatability.
+            I2cTransaction::write_read(0x45, vec![0xE0, 0x00], MEASUREMENT.to_vec()),
+            I2cTransaction::write(0x45, vec![0x30, 0x93]), // Break.
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut sht31 = Sht31::new(i2c, delay, Address::High);
+
+        sht31
+            .start_periodic_measurement(Repeatability::High, MeasurementRate::OnePerSecond)
+            .await
+            .unwrap();
+
+        let measurement = sht31.read_periodic_measurement().await.unwrap();
+        assert!((measurement.temperature - 25.0).abs() < 0.01);
+        assert!((measurement.humidity - 50.0).abs() < 0.01);
+
+        sht31.stop_periodic_measurement().await.unwrap();
+
+        let err = sht31.read_periodic_measurement().await.unwrap_err();
+        assert!(matches!(err, Sht31Error::PeriodicMeasurementNotStarted));
+
+        sht31.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_low_power_single_shot() {
+        // No transaction: the sensor already idles between measurements.
+        let i2c = I2cMock::new(&[]);
+        let delay = NoopDelay::new();
+        let mut sht31 = Sht31::new(i2c, delay, Address::Low);
+
+        sht31.sleep().await.unwrap();
+        sht31.wake().await.unwrap();
+
+        sht31.i2c.done();
+    }
+
+    #[tokio::test]
+    async fn test_low_power_periodic() {
+        let expectations = [
+            I2cTransaction::write(0x44, vec![0x27, 0x2A]), // 10 mps, low repeatability.
+            I2cTransaction::write(0x44, vec![0x30, 0x93]), // Sleep: break.
+            I2cTransaction::write(0x44, vec![0x27, 0x2A]), // Wake: restart.
+            I2cTransaction::write_read(0x44, vec![0xE0, 0x00], MEASUREMENT.to_vec()),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let delay = NoopDelay::new();
+        let mut sht31 = Sht31::new(i2c, delay, Address::Low);
+
+        sht31
+            .start_periodic_measurement(Repeatability::Low, MeasurementRate::TenPerSecond)
+            .await
+            .unwrap();
+
+        sht31.sleep().await.unwrap();
+        let err = sht31.read_periodic_measurement().await.unwrap_err();
+        assert!(matches!(err, Sht31Error::PeriodicMeasurementNotStarted));
+
+        sht31.wake().await.unwrap();
+        assert!(sht31.read_periodic_measurement().await.is_ok());
+
+        sht31.i2c.done();
+    }
+}
@@ -0,0 +1,414 @@
+//! # Soft I²C Bus
+//!
+//! This module provides an architecture-agnostic, bit-banged I²C controller
+//! built on top of two GPIO pins, for boards which lack a spare hardware I²C
+//! peripheral.
+//!
+//! The bus implements both the `embedded-hal` and `embedded-hal-async`
+//! `I2c` traits, so it can be used as the bus of any I²C driver, such as the
+//! `BH1750` one.
+//!
+//! # Timing
+//!
+//! Each half of a clock period lasts
+//! [`HALF_PERIOD_US`](crate::soft_i2c::HALF_PERIOD_US) microseconds, so the
+//! bus runs at **at most** 100 kHz (I²C standard mode).
+//! The effective speed is lower, since the time spent toggling the GPIOs
+//! adds up to each half period.
+//!
+//! The bus is driven synchronously to respect the protocol timings,
+//! hence the asynchronous implementation blocks the executor for the whole
+//! duration of a transaction.
+//!
+//! # Wiring
+//!
+//! - The SDA pin must be configured as an **open-drain** input/output pin,
+//!   so that setting it high releases the line and lets a target pull it low.
+//! - The SCL pin is only driven by the controller, hence targets which
+//!   stretch the clock are **not** supported.
+//! - Both lines require pull-up resistors.
+
+use core::result::Result;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::i2c::{ErrorKind, ErrorType, NoAcknowledgeSource, Operation};
+
+/// Duration, in microseconds, of half a clock period.
+pub const HALF_PERIOD_US: u32 = 5;
+
+/// Errors that may occur when driving the soft I²C bus.
+#[derive(Debug, Copy, Clone)]
+pub enum SoftI2cError<E> {
+    /// Error related to GPIO pin I/O operations.
+    Pin(E),
+    /// The target did not acknowledge the address or a data byte.
+    NoAcknowledge(NoAcknowledgeSource),
+}
+
+impl<E> From<E> for SoftI2cError<E> {
+    fn from(e: E) -> Self {
+        SoftI2cError::Pin(e)
+    }
+}
+
+impl<E: core::fmt::Debug> embedded_hal::i2c::Error for SoftI2cError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Pin(_) => ErrorKind::Bus,
+            Self::NoAcknowledge(source) => ErrorKind::NoAcknowledge(*source),
+        }
+    }
+}
+
+/// A bit-banged I²C bus controller.
+pub struct SoftI2c<SDA, SCL, D>
+where
+    SDA: InputPin + OutputPin,
+    SCL: OutputPin,
+    D: DelayNs,
+{
+    sda: SDA,
+    scl: SCL,
+    delay: D,
+}
+
+impl<SDA, SCL, E, D> SoftI2c<SDA, SCL, D>
+where
+    SDA: InputPin<Error = E> + OutputPin<Error = E>,
+    SCL: OutputPin<Error = E>,
+    D: DelayNs,
+{
+    /// Creates a [`SoftI2c`] bus with the given SDA and SCL pins and
+    /// delay provider.
+    #[must_use]
+    pub fn new(sda: SDA, scl: SCL, delay: D) -> Self {
+        Self { sda, scl, delay }
+    }
+
+    /// Releases the SDA and SCL pins and the delay provider.
+    pub fn release(self) -> (SDA, SCL, D) {
+        (self.sda, self.scl, self.delay)
+    }
+
+    fn run(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), SoftI2cError<E>> {
+        let result = self.run_operations(address, operations);
+
+        // Always release the bus, even when the target does not acknowledge.
+        match result {
+            Err(SoftI2cError::Pin(e)) => Err(SoftI2cError::Pin(e)),
+            result => {
+                self.stop()?;
+                result
+            }
+        }
+    }
+
+    fn run_operations(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), SoftI2cError<E>> {
+        let mut previous_is_read = None;
+
+        for index in 0..operations.len() {
+            let is_read = matches!(operations[index], Operation::Read(_));
+
+            // A start condition, followed by the address, is only sent when
+            // the transfer direction changes.
+            if previous_is_read != Some(is_read) {
+                self.start()?;
+                if !self.write_byte((address << 1) | u8::from(is_read))? {
+                    return Err(SoftI2cError::NoAcknowledge(NoAcknowledgeSource::Address));
+                }
+            }
+
+            // The last byte of a sequence of reads must not be acknowledged.
+            let next_is_read = matches!(operations.get(index + 1), Some(Operation::Read(_)));
+
+            match &mut operations[index] {
+                Operation::Write(bytes) => {
+                    for &byte in *bytes {
+                        if !self.write_byte(byte)? {
+                            return Err(SoftI2cError::NoAcknowledge(NoAcknowledgeSource::Data));
+                        }
+                    }
+                }
+                Operation::Read(buffer) => {
+                    let last = buffer.len().saturating_sub(1);
+                    for (position, byte) in buffer.iter_mut().enumerate() {
+                        *byte = self.read_byte(next_is_read || position != last)?;
+                    }
+                }
+            }
+
+            previous_is_read = Some(is_read);
+        }
+
+        Ok(())
+    }
+
+    fn start(&mut self) -> Result<(), E> {
+        // SDA goes low while SCL is high.
+        self.sda.set_high()?;
+        self.scl.set_high()?;
+        self.delay.delay_us(HALF_PERIOD_US);
+        self.sda.set_low()?;
+        self.delay.delay_us(HALF_PERIOD_US);
+        self.scl.set_low()?;
+        self.delay.delay_us(HALF_PERIOD_US);
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), E> {
+        // SDA goes high while SCL is high.
+        self.sda.set_low()?;
+        self.delay.delay_us(HALF_PERIOD_US);
+        self.scl.set_high()?;
+        self.delay.delay_us(HALF_PERIOD_US);
+        self.sda.set_high()?;
+        self.delay.delay_us(HALF_PERIOD_US);
+
+        Ok(())
+    }
+
+    fn write_bit(&mut self, bit: bool) -> Result<(), E> {
+        if bit {
+            self.sda.set_high()?;
+        } else {
+            self.sda.set_low()?;
+        }
+        self.delay.delay_us(HALF_PERIOD_US);
+        self.scl.set_high()?;
+        self.delay.delay_us(HALF_PERIOD_US);
+        self.scl.set_low()?;
+
+        Ok(())
+    }
+
+    fn read_bit(&mut self) -> Result<bool, E> {
+        // Release SDA so that the target can drive it.
+        self.sda.set_high()?;
+        self.delay.delay_us(HALF_PERIOD_US);
+        self.scl.set_high()?;
+        self.delay.delay_us(HALF_PERIOD_US);
+        let bit = self.sda.is_high()?;
+        self.scl.set_low()?;
+
+        Ok(bit)
+    }
+
+    // Writes a byte, MSB first, and returns whether the target acknowledged it.
+    fn write_byte(&mut self, byte: u8) -> Result<bool, E> {
+        for shift in (0..8).rev() {
+            self.write_bit((byte >> shift) & 1 == 1)?;
+        }
+
+        // The target acknowledges by pulling SDA low.
+        Ok(!self.read_bit()?)
+    }
+
+    // Reads a byte, MSB first, and acknowledges it if requested.
+    fn read_byte(&mut self, ack: bool) -> Result<u8, E> {
+        let mut byte = 0;
+        for _ in 0..8 {
+            byte = (byte << 1) | u8::from(self.read_bit()?);
+        }
+
+        self.write_bit(!ack)?;
+
+        Ok(byte)
+    }
+}
+
+impl<SDA, SCL, E, D> ErrorType for SoftI2c<SDA, SCL, D>
+where
+    SDA: InputPin<Error = E> + OutputPin<Error = E>,
+    SCL: OutputPin<Error = E>,
+    E: core::fmt::Debug,
+    D: DelayNs,
+{
+    type Error = SoftI2cError<E>;
+}
+
+impl<SDA, SCL, E, D> embedded_hal::i2c::I2c<u8> for SoftI2c<SDA, SCL, D>
+where
+    SDA: InputPin<Error = E> + OutputPin<Error = E>,
+    SCL: OutputPin<Error = E>,
+    E: core::fmt::Debug,
+    D: DelayNs,
+{
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.run(address, operations)
+    }
+}
+
+impl<SDA, SCL, E, D> embedded_hal_async::i2c::I2c<u8> for SoftI2c<SDA, SCL, D>
+where
+    SDA: InputPin<Error = E> + OutputPin<Error = E>,
+    SCL: OutputPin<Error = E>,
+    E: core::fmt::Debug,
+    D: DelayNs,
+{
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.run(address, operations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+    use std::vec::Vec;
+
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+
+    #[cfg(feature = "bh1750")]
+    use crate::bh1750::{Address, Bh1750, Resolution};
+
+    // Expected transactions on the SDA and SCL pins.
+    #[derive(Default)]
+    struct Bus {
+        sda: Vec<PinTransaction>,
+        scl: Vec<PinTransaction>,
+    }
+
+    const fn state(bit: bool) -> State {
+        if bit { State::High } else { State::Low }
+    }
+
+    impl Bus {
+        fn start(&mut self) {
+            self.sda.push(PinTransaction::set(State::High));
+            self.scl.push(PinTransaction::set(State::High));
+            self.sda.push(PinTransaction::set(State::Low));
+            self.scl.push(PinTransaction::set(State::Low));
+        }
+
+        fn stop(&mut self) {
+            self.sda.push(PinTransaction::set(State::Low));
+            self.scl.push(PinTransaction::set(State::High));
+            self.sda.push(PinTransaction::set(State::High));
+        }
+
+        fn write_bit(&mut self, bit: bool) {
+            self.sda.push(PinTransaction::set(state(bit)));
+            self.scl.push(PinTransaction::set(State::High));
+            self.scl.push(PinTransaction::set(State::Low));
+        }
+
+        fn read_bit(&mut self, bit: bool) {
+            self.sda.push(PinTransaction::set(State::High));
+            self.scl.push(PinTransaction::set(State::High));
+            self.sda.push(PinTransaction::get(state(bit)));
+            self.scl.push(PinTransaction::set(State::Low));
+        }
+
+        fn write_byte(&mut self, byte: u8, ack: bool) {
+            for shift in (0..8).rev() {
+                self.write_bit((byte >> shift) & 1 == 1);
+            }
+            // The target pulls SDA low to acknowledge.
+            self.read_bit(!ack);
+        }
+
+        fn read_byte(&mut self, byte: u8, ack: bool) {
+            for shift in (0..8).rev() {
+                self.read_bit((byte >> shift) & 1 == 1);
+            }
+            // The controller pulls SDA low to acknowledge.
+            self.write_bit(!ack);
+        }
+
+        fn pins(&self) -> (PinMock, PinMock) {
+            (PinMock::new(&self.sda), PinMock::new(&self.scl))
+        }
+    }
+
+    #[test]
+    fn test_write_read() {
+        let mut bus = Bus::default();
+        bus.start();
+        bus.write_byte(0x23 << 1, true); // Write address.
+        bus.write_byte(0x10, true);
+        bus.start(); // Repeated start.
+        bus.write_byte((0x23 << 1) | 1, true); // Read address.
+        bus.read_byte(0xAB, true);
+        bus.read_byte(0xCD, false); // Last byte is not acknowledged.
+        bus.stop();
+
+        let (mut sda, mut scl) = bus.pins();
+        let mut i2c = SoftI2c::new(sda.clone(), scl.clone(), NoopDelay::new());
+
+        let mut buffer = [0u8; 2];
+        embedded_hal::i2c::I2c::write_read(&mut i2c, 0x23, &[0x10], &mut buffer).unwrap();
+        assert_eq!(buffer, [0xAB, 0xCD]);
+
+        sda.done();
+        scl.done();
+    }
+
+    #[test]
+    fn test_address_not_acknowledged() {
+        let mut bus = Bus::default();
+        bus.start();
+        bus.write_byte(0x23 << 1, false);
+        bus.stop(); // The bus is released anyway.
+
+        let (mut sda, mut scl) = bus.pins();
+        let mut i2c = SoftI2c::new(sda.clone(), scl.clone(), NoopDelay::new());
+
+        let result = embedded_hal::i2c::I2c::write(&mut i2c, 0x23, &[0x10]);
+        assert!(matches!(
+            result,
+            Err(SoftI2cError::NoAcknowledge(NoAcknowledgeSource::Address))
+        ));
+
+        sda.done();
+        scl.done();
+    }
+
+    #[cfg(feature = "bh1750")]
+    #[tokio::test]
+    async fn test_bh1750_one_time_measurement() {
+        let raw: u16 = 1200;
+
+        let mut bus = Bus::default();
+        // One-time measurement in high resolution mode.
+        bus.start();
+        bus.write_byte(0x23 << 1, true);
+        bus.write_byte(0x20, true);
+        bus.stop();
+        // Measurement result.
+        bus.start();
+        bus.write_byte((0x23 << 1) | 1, true);
+        bus.read_byte((raw >> 8) as u8, true);
+        bus.read_byte((raw & 0xFF) as u8, false);
+        bus.stop();
+
+        let (mut sda, mut scl) = bus.pins();
+        let i2c = SoftI2c::new(sda.clone(), scl.clone(), NoopDelay::new());
+        let mut bh1750 = Bh1750::new(i2c, NoopDelay::new(), Address::Low);
+
+        let lux = bh1750.one_time_measurement(Resolution::High).await.unwrap();
+        assert!((lux - 1000.0).abs() < 0.01);
+
+        sda.done();
+        scl.done();
+    }
+}
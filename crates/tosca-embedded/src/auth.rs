@@ -0,0 +1,129 @@
+use alloc::string::String;
+
+// Scheme prefix of a basic authentication `Authorization` header.
+const BASIC_SCHEME: &str = "Basic ";
+
+// Standard base64 alphabet.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// HTTP basic authentication credentials.
+pub struct BasicAuth {
+    // The expected `Authorization` header value.
+    expected: String,
+}
+
+impl BasicAuth {
+    /// Creates a [`BasicAuth`] with the given credentials.
+    #[must_use]
+    pub fn new(user: &str, password: &str) -> Self {
+        let mut expected = String::from(BASIC_SCHEME);
+        encode_base64(user.as_bytes(), b":", password.as_bytes(), &mut expected);
+        Self { expected }
+    }
+
+    /// Checks the value of an `Authorization` header.
+    ///
+    /// The comparison runs in constant time with respect to the received
+    /// value, so that the credentials cannot be guessed by timing
+    /// the responses.
+    #[must_use]
+    pub fn is_authorized(&self, header: Option<&str>) -> bool {
+        header.is_some_and(|header| constant_time_eq(self.expected.as_bytes(), header.as_bytes()))
+    }
+}
+
+// Compares two byte slices, always inspecting all the expected bytes.
+fn constant_time_eq(expected: &[u8], received: &[u8]) -> bool {
+    let mut diff = expected.len() ^ received.len();
+    for (index, byte) in expected.iter().enumerate() {
+        let other = received.get(index).copied().unwrap_or(0);
+        diff |= usize::from(byte ^ other);
+    }
+    diff == 0
+}
+
+// Encodes the concatenation of the given parts in base64.
+fn encode_base64(first: &[u8], separator: &[u8], second: &[u8], output: &mut String) {
+    let mut bytes = first
+        .iter()
+        .chain(separator)
+        .chain(second)
+        .copied()
+        .peekable();
+
+    while bytes.peek().is_some() {
+        let mut chunk = [0u8; 3];
+        let mut len = 0;
+        for slot in &mut chunk {
+            if let Some(byte) = bytes.next() {
+                *slot = byte;
+                len += 1;
+            }
+        }
+
+        let group = u32::from(chunk[0]) << 16 | u32::from(chunk[1]) << 8 | u32::from(chunk[2]);
+        for position in 0..4 {
+            if position <= len {
+                let index = (group >> (18 - 6 * position)) & 0x3f;
+                output.push(char::from(BASE64_ALPHABET[index as usize]));
+            } else {
+                output.push('=');
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::{BasicAuth, constant_time_eq, encode_base64};
+
+    fn base64(first: &str, second: &str) -> String {
+        let mut output = String::new();
+        encode_base64(first.as_bytes(), b":", second.as_bytes(), &mut output);
+        output
+    }
+
+    #[test]
+    fn test_encode_base64() {
+        // Example of the basic authentication specification (RFC 7617).
+        assert_eq!(
+            base64("Aladdin", "open sesame"),
+            "QWxhZGRpbjpvcGVuIHNlc2FtZQ=="
+        );
+
+        // One, two and no padding characters.
+        assert_eq!(base64("a", ""), "YTo=");
+        assert_eq!(base64("ab", "c"), "YWI6Yw==");
+        assert_eq!(base64("ab", "cd"), "YWI6Y2Q=");
+        assert_eq!(base64("abc", "de"), "YWJjOmRl");
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(constant_time_eq(b"", b""));
+
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret!"));
+        assert!(!constant_time_eq(b"secret", b"secre"));
+        assert!(!constant_time_eq(b"secret", b""));
+        // Missing bytes are not confused with zero bytes.
+        assert!(!constant_time_eq(b"ab\0", b"ab"));
+    }
+
+    #[test]
+    fn test_authorization() {
+        let auth = BasicAuth::new("Aladdin", "open sesame");
+
+        assert!(auth.is_authorized(Some("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==")));
+
+        assert!(!auth.is_authorized(None));
+        assert!(!auth.is_authorized(Some("")));
+        assert!(!auth.is_authorized(Some("QWxhZGRpbjpvcGVuIHNlc2FtZQ==")));
+        assert!(!auth.is_authorized(Some("Basic QWxhZGRpbjpvcGVuIHNlc2FtZA==")));
+        assert!(!auth.is_authorized(Some("Bearer QWxhZGRpbjpvcGVuIHNlc2FtZQ==")));
+    }
+}
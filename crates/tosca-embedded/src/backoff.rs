@@ -0,0 +1,82 @@
+/// Exponential backoff between consecutive failed connection attempts.
+///
+/// The delay starts at the initial delay, doubles after each failed
+/// attempt and is capped at the maximum delay.
+pub struct ReconnectBackoff {
+    initial_delay: u64,
+    maximum_delay: u64,
+    failed_attempts: u32,
+}
+
+impl ReconnectBackoff {
+    /// Creates a [`ReconnectBackoff`] with the given initial and maximum
+    /// delays.
+    #[must_use]
+    pub const fn new(initial_delay: u64, maximum_delay: u64) -> Self {
+        Self {
+            initial_delay,
+            maximum_delay,
+            failed_attempts: 0,
+        }
+    }
+
+    /// Records a failed attempt, returning the delay before the next one.
+    pub fn failed(&mut self) -> u64 {
+        let delay = 1u64
+            .checked_shl(self.failed_attempts)
+            .map_or(self.maximum_delay, |factor| {
+                self.initial_delay.saturating_mul(factor)
+            })
+            .min(self.maximum_delay);
+        self.failed_attempts = self.failed_attempts.saturating_add(1);
+        delay
+    }
+
+    /// Returns the number of consecutive failed attempts.
+    #[must_use]
+    pub const fn failed_attempts(&self) -> u32 {
+        self.failed_attempts
+    }
+
+    /// Resets the backoff after a successful attempt.
+    pub const fn reset(&mut self) {
+        self.failed_attempts = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReconnectBackoff;
+
+    #[test]
+    fn test_delay_doubles_up_to_maximum() {
+        let mut backoff = ReconnectBackoff::new(2, 60);
+
+        for delay in [2, 4, 8, 16, 32, 60, 60] {
+            assert_eq!(backoff.failed(), delay);
+        }
+        assert_eq!(backoff.failed_attempts(), 7);
+    }
+
+    #[test]
+    fn test_many_failed_attempts() {
+        let mut backoff = ReconnectBackoff::new(2, 60);
+
+        // The delay never overflows, however many attempts fail.
+        for _ in 0..200 {
+            assert!(backoff.failed() <= 60);
+        }
+        assert_eq!(backoff.failed(), 60);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut backoff = ReconnectBackoff::new(2, 60);
+        let _ = backoff.failed();
+        let _ = backoff.failed();
+
+        backoff.reset();
+        assert_eq!(backoff.failed_attempts(), 0);
+        assert_eq!(backoff.failed(), 2);
+    }
+}
@@ -0,0 +1,142 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use embedded_io_async::Read;
+
+/// Errors that may occur when reading a request body.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BodyError<E> {
+    /// The body exceeds the maximum size.
+    TooLarge,
+    /// The body ended before the expected number of bytes, after the
+    /// contained number of bytes.
+    Truncated(usize),
+    /// Failed to read from the reader.
+    Read(E),
+}
+
+/// Reads a request body of the given length.
+///
+/// A single read might return only a part of the body, hence the reader is
+/// read until the whole body has been received.
+///
+/// # Errors
+///
+/// - The body length exceeds the maximum size, in which case nothing is
+///   read
+/// - The body ends before the given length
+/// - Failed to read from the reader
+pub async fn read_body<R: Read>(
+    reader: &mut R,
+    content_length: usize,
+    maximum_size: usize,
+) -> Result<Vec<u8>, BodyError<R::Error>> {
+    if content_length > maximum_size {
+        return Err(BodyError::TooLarge);
+    }
+
+    let mut bytes = vec![0; content_length];
+    let mut received = 0;
+    while received < content_length {
+        let read = reader
+            .read(&mut bytes[received..])
+            .await
+            .map_err(BodyError::Read)?;
+
+        if read == 0 {
+            return Err(BodyError::Truncated(received));
+        }
+
+        received += read;
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embassy_futures::block_on;
+
+    use embedded_io_async::{ErrorType, Read};
+
+    use super::{BodyError, read_body};
+
+    // A reader returning its body in the given chunks, one for each read.
+    struct ChunkedReader<'a> {
+        chunks: &'a [&'a [u8]],
+        reads: usize,
+    }
+
+    impl<'a> ChunkedReader<'a> {
+        const fn new(chunks: &'a [&'a [u8]]) -> Self {
+            Self { chunks, reads: 0 }
+        }
+    }
+
+    impl ErrorType for ChunkedReader<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for ChunkedReader<'_> {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let Some((chunk, chunks)) = self.chunks.split_first() else {
+                return Ok(0);
+            };
+            let len = buf.len().min(chunk.len());
+            buf[..len].copy_from_slice(&chunk[..len]);
+            self.chunks = chunks;
+            self.reads += 1;
+            Ok(len)
+        }
+    }
+
+    const BODY: &[u8] = br#"{"brightness":5}"#;
+
+    #[test]
+    fn test_body_under_limit() {
+        let mut reader = ChunkedReader::new(&[BODY]);
+
+        assert_eq!(
+            block_on(read_body(&mut reader, BODY.len(), BODY.len())),
+            Ok(BODY.to_vec())
+        );
+    }
+
+    #[test]
+    fn test_body_over_limit() {
+        let mut reader = ChunkedReader::new(&[BODY]);
+
+        assert_eq!(
+            block_on(read_body(&mut reader, BODY.len(), BODY.len() - 1)),
+            Err(BodyError::TooLarge)
+        );
+        // The body is rejected before being read.
+        assert_eq!(reader.reads, 0);
+    }
+
+    #[test]
+    fn test_body_in_two_chunks() {
+        let (first, second) = BODY.split_at(6);
+        let chunks = [first, second];
+        let mut reader = ChunkedReader::new(&chunks);
+
+        assert_eq!(
+            block_on(read_body(&mut reader, BODY.len(), BODY.len())),
+            Ok(BODY.to_vec())
+        );
+        assert_eq!(reader.reads, 2);
+    }
+
+    #[test]
+    fn test_truncated_body() {
+        let chunks = [&BODY[..6]];
+        let mut reader = ChunkedReader::new(&chunks);
+
+        assert_eq!(
+            block_on(read_body(&mut reader, BODY.len(), BODY.len())),
+            Err(BodyError::Truncated(6))
+        );
+    }
+}
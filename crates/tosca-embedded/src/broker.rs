@@ -0,0 +1,101 @@
+/// Credentials to authenticate to a broker.
+#[derive(Clone, Copy)]
+pub struct BrokerCredentials {
+    username: &'static str,
+    password: &'static str,
+}
+
+impl BrokerCredentials {
+    /// Creates [`BrokerCredentials`] from a `username` and a `password`.
+    ///
+    /// An empty `username` or `password` is not sent to the broker.
+    #[must_use]
+    pub const fn new(username: &'static str, password: &'static str) -> Self {
+        Self { username, password }
+    }
+
+    /// Returns the username to send to the broker, if any.
+    #[must_use]
+    pub const fn username(&self) -> Option<&'static str> {
+        non_empty(self.username)
+    }
+
+    /// Returns the password to send to the broker, if any.
+    #[must_use]
+    pub const fn password(&self) -> Option<&'static str> {
+        non_empty(self.password)
+    }
+}
+
+/// Adds the credentials to a client configuration through the given
+/// setters.
+///
+/// Empty fields are not sent, so missing or empty credentials connect
+/// anonymously.
+pub fn apply_credentials<C>(
+    config: &mut C,
+    credentials: Option<BrokerCredentials>,
+    add_username: impl FnOnce(&mut C, &'static str),
+    add_password: impl FnOnce(&mut C, &'static str),
+) {
+    let Some(credentials) = credentials else {
+        return;
+    };
+
+    if let Some(username) = credentials.username() {
+        add_username(config, username);
+    }
+
+    if let Some(password) = credentials.password() {
+        add_password(config, password);
+    }
+}
+
+const fn non_empty(value: &'static str) -> Option<&'static str> {
+    if value.is_empty() { None } else { Some(value) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BrokerCredentials, apply_credentials};
+
+    #[derive(Default)]
+    struct MockConfig {
+        username: Option<&'static str>,
+        password: Option<&'static str>,
+    }
+
+    fn configure(credentials: Option<BrokerCredentials>) -> MockConfig {
+        let mut config = MockConfig::default();
+        apply_credentials(
+            &mut config,
+            credentials,
+            |config, username| config.username = Some(username),
+            |config, password| config.password = Some(password),
+        );
+        config
+    }
+
+    #[test]
+    fn test_credentials_forwarded() {
+        let config = configure(Some(BrokerCredentials::new("device", "secret")));
+        assert_eq!(config.username, Some("device"));
+        assert_eq!(config.password, Some("secret"));
+
+        // A username without password.
+        let config = configure(Some(BrokerCredentials::new("device", "")));
+        assert_eq!(config.username, Some("device"));
+        assert_eq!(config.password, None);
+    }
+
+    #[test]
+    fn test_anonymous_connection() {
+        // Without credentials.
+        let config = configure(None);
+        assert_eq!((config.username, config.password), (None, None));
+
+        // With empty credentials.
+        let config = configure(Some(BrokerCredentials::new("", "")));
+        assert_eq!((config.username, config.password), (None, None));
+    }
+}
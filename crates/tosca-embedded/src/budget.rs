@@ -0,0 +1,305 @@
+use core::cell::Cell;
+
+use embassy_time::Instant;
+
+use embedded_io_async::{Read, Write};
+
+use log::warn;
+
+use crate::rate_limit::{RequestWindow, TOO_MANY_REQUESTS_RESPONSE};
+
+// Response sent to a client which has spent the byte budget of its
+// connection.
+//
+// It is written on the raw socket, since the budget might be spent while
+// the server is still parsing the request headers.
+const BUDGET_EXCEEDED_RESPONSE: &[u8] =
+    b"HTTP/1.1 413 Payload Too Large\r\nConnection: close\r\nContent-Length: 0\r\n\r\n";
+
+/// The budget of a connection.
+///
+/// It limits the number of bytes read on a connection, across all of its
+/// requests, as well as the number of requests per second sent over it.
+/// Once the budget is spent, the client is answered with a rejection
+/// response and the connection is reported as closed.
+///
+/// The budget is shared between the halves of a split socket, hence it is
+/// updated through a shared reference.
+pub struct ConnectionBudget {
+    // Remaining bytes, `None` when the budget is unlimited.
+    remaining: Cell<Option<usize>>,
+    // Requests window, `None` when the requests are not limited.
+    requests: Cell<Option<RequestWindow>>,
+    // Whether the next read starts a new request.
+    new_request: Cell<bool>,
+    // Whether the budget has been spent.
+    exhausted: Cell<bool>,
+    // Response written when the connection is rejected.
+    rejection: Cell<&'static [u8]>,
+    // Whether the rejection response has already been written.
+    rejected: Cell<bool>,
+}
+
+impl ConnectionBudget {
+    /// Creates a [`ConnectionBudget`] with the given number of bytes and
+    /// requests per second.
+    ///
+    /// `None` disables the respective limit.
+    #[must_use]
+    pub const fn new(budget: Option<usize>, max_requests_per_second: Option<u32>) -> Self {
+        Self {
+            remaining: Cell::new(budget),
+            requests: Cell::new(match max_requests_per_second {
+                Some(max_per_second) => Some(RequestWindow::new(max_per_second)),
+                None => None,
+            }),
+            new_request: Cell::new(true),
+            exhausted: Cell::new(false),
+            rejection: Cell::new(BUDGET_EXCEEDED_RESPONSE),
+            rejected: Cell::new(false),
+        }
+    }
+
+    // Counts the request started by a read, returning whether the
+    // connection is still within its requests limit.
+    //
+    // An HTTP/1.1 client waits for a response before sending a new request
+    // on the same connection, so the first read following a response starts
+    // a new request.
+    fn allow_request(&self) -> bool {
+        if self.exhausted.get() {
+            return false;
+        }
+        if !self.new_request.replace(false) {
+            return true;
+        }
+        let Some(mut window) = self.requests.get() else {
+            return true;
+        };
+
+        let allowed = window.allow(Instant::now());
+        self.requests.set(Some(window));
+        if !allowed {
+            warn!("Connection requests limit exceeded, closing the connection");
+            self.exhausted.set(true);
+            self.rejection.set(TOO_MANY_REQUESTS_RESPONSE);
+        }
+        allowed
+    }
+
+    // Returns the number of bytes which can be read into a buffer of the
+    // given length, or `None` when the budget has been spent.
+    fn allowed_len(&self, len: usize) -> Option<usize> {
+        match self.remaining.get() {
+            None => Some(len),
+            Some(0) => {
+                if !self.exhausted.replace(true) {
+                    warn!("Connection byte budget exceeded, closing the connection");
+                }
+                None
+            }
+            Some(remaining) => Some(len.min(remaining)),
+        }
+    }
+
+    fn consume(&self, read: usize) {
+        if let Some(remaining) = self.remaining.get() {
+            self.remaining.set(Some(remaining.saturating_sub(read)));
+        }
+    }
+
+    /// Returns whether the budget has been spent.
+    #[must_use]
+    pub fn is_spent(&self) -> bool {
+        self.exhausted.get() || self.remaining.get() == Some(0)
+    }
+
+    /// Reads from the given reader within the budget.
+    ///
+    /// Returns `None` when the budget has been spent.
+    ///
+    /// # Errors
+    ///
+    /// Failed to read from the reader.
+    pub async fn read<R: Read>(
+        &self,
+        io: &mut R,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, R::Error> {
+        if !self.allow_request() {
+            return Ok(None);
+        }
+        let Some(len) = self.allowed_len(buf.len()) else {
+            return Ok(None);
+        };
+        let read = io.read(&mut buf[..len]).await?;
+        self.consume(read);
+        Ok(Some(read))
+    }
+
+    /// Reads from the given socket within the budget.
+    ///
+    /// Once the budget has been spent, the client is answered with the
+    /// rejection response, and the connection is reported as closed
+    /// by returning `0`.
+    ///
+    /// # Errors
+    ///
+    /// Failed to read from or to write into the socket.
+    pub async fn read_or_reject<T: Read + Write>(
+        &self,
+        io: &mut T,
+        buf: &mut [u8],
+    ) -> Result<usize, T::Error> {
+        if let Some(read) = self.read(io, buf).await? {
+            return Ok(read);
+        }
+
+        self.reject(io).await?;
+        Ok(0)
+    }
+
+    /// Writes into the given writer.
+    ///
+    /// Once the budget has been spent, the first write is replaced by the
+    /// rejection response, while all the following ones are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Failed to write into the writer.
+    pub async fn write<W: Write>(&self, io: &mut W, buf: &[u8]) -> Result<usize, W::Error> {
+        if !self.exhausted.get() {
+            self.new_request.set(true);
+            return io.write(buf).await;
+        }
+        self.reject(io).await?;
+        Ok(buf.len())
+    }
+
+    async fn reject<W: Write>(&self, io: &mut W) -> Result<(), W::Error> {
+        if !self.rejected.replace(true) {
+            io.write_all(self.rejection.get()).await?;
+            io.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use core::convert::Infallible;
+
+    use embassy_futures::block_on;
+
+    use embedded_io_async::{ErrorType, Read, Write};
+
+    use crate::rate_limit::TOO_MANY_REQUESTS_RESPONSE;
+
+    use super::{BUDGET_EXCEEDED_RESPONSE, ConnectionBudget};
+
+    // A socket reading from a fixed input and recording the written bytes.
+    struct MockSocket {
+        input: &'static [u8],
+        output: Vec<u8>,
+    }
+
+    impl MockSocket {
+        const fn new(input: &'static [u8]) -> Self {
+            Self {
+                input,
+                output: Vec::new(),
+            }
+        }
+    }
+
+    impl ErrorType for MockSocket {
+        type Error = Infallible;
+    }
+
+    impl Read for MockSocket {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let len = buf.len().min(self.input.len());
+            buf[..len].copy_from_slice(&self.input[..len]);
+            self.input = &self.input[len..];
+            Ok(len)
+        }
+    }
+
+    impl Write for MockSocket {
+        async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.output.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    #[test]
+    fn test_pipelining_past_budget() {
+        block_on(async {
+            // Three pipelined bodies of 8 bytes, over a budget of 20 bytes.
+            let mut socket = MockSocket::new(b"body-onebody-twobody-three");
+            let budget = ConnectionBudget::new(Some(20), None);
+            let mut buf = [0; 8];
+
+            assert_eq!(budget.read_or_reject(&mut socket, &mut buf).await, Ok(8));
+            assert_eq!(budget.write(&mut socket, b"OK").await, Ok(2));
+
+            assert_eq!(budget.read_or_reject(&mut socket, &mut buf).await, Ok(8));
+            assert_eq!(budget.read_or_reject(&mut socket, &mut buf).await, Ok(4));
+            assert!(budget.is_spent());
+
+            // The connection is rejected and reported as closed.
+            assert_eq!(budget.read_or_reject(&mut socket, &mut buf).await, Ok(0));
+            assert_eq!(socket.output, [b"OK", BUDGET_EXCEEDED_RESPONSE].concat());
+
+            // The rejection is written once, and any following response
+            // is discarded.
+            assert_eq!(budget.write(&mut socket, b"OK").await, Ok(2));
+            assert_eq!(budget.read_or_reject(&mut socket, &mut buf).await, Ok(0));
+            assert_eq!(socket.output, [b"OK", BUDGET_EXCEEDED_RESPONSE].concat());
+        });
+    }
+
+    #[test]
+    fn test_requests_over_limit() {
+        block_on(async {
+            let mut socket = MockSocket::new(b"request-onerequest-two");
+            let budget = ConnectionBudget::new(None, Some(1));
+            let mut buf = [0; 11];
+
+            // Reads within the same request are not counted again.
+            assert_eq!(
+                budget.read_or_reject(&mut socket, &mut buf[..5]).await,
+                Ok(5)
+            );
+            assert_eq!(
+                budget.read_or_reject(&mut socket, &mut buf[5..]).await,
+                Ok(6)
+            );
+            assert_eq!(budget.write(&mut socket, b"OK").await, Ok(2));
+
+            // A second request within the same second is answered with a 429.
+            assert_eq!(budget.read_or_reject(&mut socket, &mut buf).await, Ok(0));
+            assert_eq!(socket.output, [b"OK", TOO_MANY_REQUESTS_RESPONSE].concat());
+            assert!(budget.is_spent());
+        });
+    }
+
+    #[test]
+    fn test_unlimited_budget() {
+        block_on(async {
+            let mut socket = MockSocket::new(b"body-onebody-two");
+            let budget = ConnectionBudget::new(None, None);
+            let mut buf = [0; 8];
+
+            for _ in 0..2 {
+                assert_eq!(budget.read_or_reject(&mut socket, &mut buf).await, Ok(8));
+                assert_eq!(budget.write(&mut socket, b"OK").await, Ok(2));
+            }
+
+            assert!(!budget.is_spent());
+            assert_eq!(socket.output, b"OKOK");
+        });
+    }
+}
@@ -0,0 +1,111 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use tosca::route::RestKind;
+
+/// The Cross-Origin Resource Sharing (CORS) headers of the responses.
+pub struct Cors {
+    origin: &'static str,
+    methods: String,
+}
+
+impl Cors {
+    /// Creates a [`Cors`] allowing the given origin to access the routes
+    /// with the given REST kinds.
+    ///
+    /// `GET` and `OPTIONS` are always allowed, since they are needed to
+    /// retrieve the device description and to answer preflight requests.
+    pub fn new(origin: &'static str, rest_kinds: impl IntoIterator<Item = RestKind>) -> Self {
+        let rest_kinds = rest_kinds.into_iter().collect::<Vec<RestKind>>();
+
+        let mut methods = String::from("GET");
+        for rest_kind in [RestKind::Put, RestKind::Post, RestKind::Delete] {
+            if rest_kinds.contains(&rest_kind) {
+                methods.push_str(", ");
+                methods.push_str(&rest_kind.to_string());
+            }
+        }
+        methods.push_str(", OPTIONS");
+
+        Self { origin, methods }
+    }
+
+    /// Adds the CORS headers to the given response headers.
+    pub fn add_headers<'a>(&'a self, headers: &mut Vec<(&'a str, &'a str)>) {
+        headers.push(("Access-Control-Allow-Origin", self.origin));
+        headers.push(("Access-Control-Allow-Methods", &self.methods));
+        headers.push(("Access-Control-Allow-Headers", "Content-Type"));
+
+        // A response for a specific origin depends on the request origin,
+        // so caches must not reuse it for other origins. A wildcard origin
+        // is the same for every request.
+        if self.origin != "*" {
+            headers.push(("Vary", "Origin"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use tosca::route::RestKind;
+
+    use super::Cors;
+
+    fn headers(cors: &Cors) -> Vec<(&str, &str)> {
+        let mut headers = Vec::new();
+        cors.add_headers(&mut headers);
+        headers
+    }
+
+    #[test]
+    fn test_methods_from_routes() {
+        let cors = Cors::new(
+            "*",
+            [
+                RestKind::Delete,
+                RestKind::Put,
+                RestKind::Put,
+                RestKind::Get,
+            ],
+        );
+        assert_eq!(
+            headers(&cors)[1],
+            ("Access-Control-Allow-Methods", "GET, PUT, DELETE, OPTIONS")
+        );
+
+        // A device without routes still answers description and preflight
+        // requests.
+        let cors = Cors::new("*", []);
+        assert_eq!(
+            headers(&cors)[1],
+            ("Access-Control-Allow-Methods", "GET, OPTIONS")
+        );
+    }
+
+    #[test]
+    fn test_wildcard_origin() {
+        assert_eq!(
+            headers(&Cors::new("*", [RestKind::Post])),
+            [
+                ("Access-Control-Allow-Origin", "*"),
+                ("Access-Control-Allow-Methods", "GET, POST, OPTIONS"),
+                ("Access-Control-Allow-Headers", "Content-Type"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_specific_origin() {
+        let cors = Cors::new("http://dashboard.local", [RestKind::Post]);
+        let headers = headers(&cors);
+
+        // Caches must not reuse a response for another origin.
+        assert_eq!(
+            headers[0],
+            ("Access-Control-Allow-Origin", "http://dashboard.local")
+        );
+        assert_eq!(headers.last(), Some(&("Vary", "Origin")));
+    }
+}
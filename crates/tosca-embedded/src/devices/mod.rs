@@ -0,0 +1,39 @@
+/// A `sensor` device.
+pub mod sensor;
+/// A `thermostat` device.
+pub mod thermostat;
+
+use tosca::device::DeviceDescription;
+use tosca::hazards::Hazard;
+use tosca::response::ResponseKind;
+use tosca::route::Route;
+
+use log::error;
+
+/// Adds a [`Route`] to a device description, removing the hazards not
+/// allowed for the device and setting the kind of its responses.
+///
+/// Returns `false`, leaving the description unchanged, when the route
+/// already exists.
+pub fn add_route(
+    device_data: &mut DeviceDescription,
+    route: Route,
+    allowed_hazards: &[Hazard],
+    response_kind: ResponseKind,
+) -> bool {
+    let route_config = route
+        .remove_prohibited_hazards(allowed_hazards)
+        .serialize_data()
+        .change_response_kind(response_kind);
+
+    if device_data.route_configs.contains(&route_config) {
+        error!(
+            "The route with prefix `{}` already exists!",
+            route_config.data.path
+        );
+        return false;
+    }
+
+    device_data.route_configs.add(route_config);
+    true
+}
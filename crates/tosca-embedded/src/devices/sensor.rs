@@ -0,0 +1,89 @@
+use tosca::device::{DeviceDescription, DeviceKind, DeviceKindId};
+use tosca::hazards::Hazard;
+use tosca::route::RouteConfigs;
+
+/// Default main route.
+pub const MAIN_ROUTE: &str = "/sensor";
+
+/// Allowed hazards.
+pub const ALLOWED_HAZARDS: &[Hazard] = &[Hazard::ElectricEnergyConsumption, Hazard::LogUsageTime];
+
+/// Returns the description of a sensor without routes.
+///
+/// A sensor has no mandatory routes, so a board exposing only its
+/// measurements can register just the routes it needs.
+#[must_use]
+pub fn description() -> DeviceDescription {
+    DeviceDescription::new(
+        DeviceKindId::from(&DeviceKind::Sensor),
+        MAIN_ROUTE,
+        RouteConfigs::new(),
+        0,
+    )
+    .text_description("A sensor device.")
+}
+
+#[cfg(test)]
+mod tests {
+    use tosca::device::{DeviceKind, DeviceKindId};
+    use tosca::hazards::Hazard;
+    use tosca::response::ResponseKind;
+    use tosca::route::Route;
+
+    use crate::devices::add_route;
+
+    use super::{ALLOWED_HAZARDS, description};
+
+    fn temperature_route() -> Route {
+        Route::get("Temperature", "/temperature")
+            .with_array_of_hazards([Hazard::LogUsageTime, Hazard::FireHazard])
+    }
+
+    #[test]
+    fn test_sensor_with_serial_route() {
+        let mut device_data = description();
+
+        assert_eq!(
+            device_data.data.kind,
+            DeviceKindId::from(&DeviceKind::Sensor)
+        );
+        assert_eq!(device_data.main_route, "/sensor");
+        assert_eq!(device_data.mandatory_routes, 0);
+        assert!(device_data.route_configs.is_empty());
+
+        assert!(add_route(
+            &mut device_data,
+            temperature_route(),
+            ALLOWED_HAZARDS,
+            ResponseKind::Serial,
+        ));
+
+        let route_config = device_data.route_configs.iter().next().unwrap();
+        assert_eq!(route_config.data.path, "/temperature");
+        assert_eq!(route_config.response_kind, ResponseKind::Serial);
+
+        // The hazards not allowed for a sensor are removed.
+        assert_eq!(route_config.data.hazards.len(), 1);
+        assert!(route_config.data.hazards.contains(&Hazard::LogUsageTime));
+    }
+
+    #[test]
+    fn test_duplicate_route_discarded() {
+        let mut device_data = description();
+
+        assert!(add_route(
+            &mut device_data,
+            temperature_route(),
+            ALLOWED_HAZARDS,
+            ResponseKind::Serial,
+        ));
+        assert!(!add_route(
+            &mut device_data,
+            temperature_route(),
+            ALLOWED_HAZARDS,
+            ResponseKind::Info,
+        ));
+
+        assert_eq!(device_data.route_configs.len(), 1);
+    }
+}
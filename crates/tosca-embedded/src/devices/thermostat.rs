@@ -0,0 +1,94 @@
+use tosca::device::{DeviceDescription, DeviceKind, DeviceKindId};
+use tosca::hazards::Hazard;
+use tosca::route::{Route, RouteConfigs, ThermostatSetRoute};
+
+/// Default main route.
+pub const MAIN_ROUTE: &str = "/thermostat";
+
+/// Name of the target temperature parameter.
+pub const TEMPERATURE_PARAMETER: &str = "temperature";
+
+/// Allowed hazards.
+pub const ALLOWED_HAZARDS: &[Hazard] = &[Hazard::ElectricEnergyConsumption, Hazard::SpoiledFood];
+
+/// Returns the description of a thermostat without routes.
+///
+/// The set temperature route is its only mandatory route.
+#[must_use]
+pub fn description() -> DeviceDescription {
+    DeviceDescription::new(
+        DeviceKindId::from(&DeviceKind::Thermostat),
+        MAIN_ROUTE,
+        RouteConfigs::new(),
+        1,
+    )
+    .text_description("A thermostat device.")
+}
+
+/// Adds the target temperature parameter to the set temperature route,
+/// constrained by the given `(minimum, maximum, step)` range.
+#[must_use]
+pub fn set_temperature_route(route: ThermostatSetRoute, range: (f64, f64, f64)) -> Route {
+    let parameters = route.parameters().clone();
+    route
+        .with_parameters(parameters.rangef64(TEMPERATURE_PARAMETER, range))
+        .into_route()
+}
+
+#[cfg(test)]
+mod tests {
+    use tosca::device::{DeviceKind, DeviceKindId};
+    use tosca::hazards::Hazard;
+    use tosca::parameters::ParameterKind;
+    use tosca::response::ResponseKind;
+    use tosca::route::{RestKind, Route, ThermostatSetRoute};
+
+    use crate::devices::add_route;
+
+    use super::{ALLOWED_HAZARDS, TEMPERATURE_PARAMETER, description, set_temperature_route};
+
+    #[test]
+    fn test_thermostat_mandatory_route() {
+        let mut device_data = description();
+
+        assert_eq!(
+            device_data.data.kind,
+            DeviceKindId::from(&DeviceKind::Thermostat)
+        );
+        assert_eq!(device_data.main_route, "/thermostat");
+        assert_eq!(device_data.mandatory_routes, 1);
+
+        let route = ThermostatSetRoute::put("Set temperature")
+            .with_array_of_hazards([Hazard::SpoiledFood, Hazard::FireHazard]);
+        assert!(add_route(
+            &mut device_data,
+            set_temperature_route(route, (5., 30., 0.5)),
+            ALLOWED_HAZARDS,
+            ResponseKind::Ok,
+        ));
+
+        // An optional route can still be added.
+        assert!(add_route(
+            &mut device_data,
+            Route::get("Temperature", "/temperature"),
+            ALLOWED_HAZARDS,
+            ResponseKind::Serial,
+        ));
+
+        let route_config = device_data.route_configs.iter().next().unwrap();
+        assert_eq!(route_config.data.path, "/set-temperature");
+        assert_eq!(route_config.rest_kind, RestKind::Put);
+        assert_eq!(route_config.response_kind, ResponseKind::Ok);
+
+        // The target temperature is constrained by the given range.
+        assert!(matches!(
+            route_config.data.parameters.get(TEMPERATURE_PARAMETER),
+            Some(ParameterKind::RangeF64 { min, max, step, .. })
+                if (*min, *max, *step) == (5., 30., 0.5)
+        ));
+
+        // The hazards not allowed for a thermostat are removed.
+        assert_eq!(route_config.data.hazards.len(), 1);
+        assert!(route_config.data.hazards.contains(&Hazard::SpoiledFood));
+    }
+}
@@ -0,0 +1,365 @@
+use alloc::vec::Vec;
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::mutex::Mutex;
+use embassy_sync::signal::Signal;
+
+use embassy_time::{Duration, with_timeout};
+
+use embedded_io_async::Write;
+
+use log::error;
+
+use tosca::events::{Events, sse_frame};
+
+/// A Server-Sent Events comment written over an idle events stream.
+///
+/// Clients ignore comments, while a failed write reveals a closed
+/// connection.
+pub const KEEP_ALIVE_FRAME: &[u8] = b": keep-alive\n\n";
+
+/// All possible errors while publishing events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventsError {
+    /// No event of the given value type has the given name.
+    NotFound,
+}
+
+impl EventsError {
+    /// Returns the error description.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::NotFound => "No event with the given name",
+        }
+    }
+}
+
+impl core::fmt::Display for EventsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.description().fmt(f)
+    }
+}
+
+impl core::error::Error for EventsError {}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for bool {}
+    impl Sealed for u8 {}
+    impl Sealed for i32 {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// A value which can be published through [`EventsHub::publish_now`].
+///
+/// This trait is sealed and implemented for all event value types.
+pub trait PublishValue: private::Sealed + Copy {
+    #[doc(hidden)]
+    fn update_by_name(self, events: &mut Events, name: &str) -> bool;
+}
+
+impl PublishValue for bool {
+    fn update_by_name(self, events: &mut Events, name: &str) -> bool {
+        events.update_bool_value_by_name(name, self)
+    }
+}
+
+impl PublishValue for u8 {
+    fn update_by_name(self, events: &mut Events, name: &str) -> bool {
+        events.update_u8_value_by_name(name, self)
+    }
+}
+
+impl PublishValue for i32 {
+    fn update_by_name(self, events: &mut Events, name: &str) -> bool {
+        events.update_i32_value_by_name(name, self)
+    }
+}
+
+impl PublishValue for f32 {
+    fn update_by_name(self, events: &mut Events, name: &str) -> bool {
+        events.update_f32_value_by_name(name, self)
+    }
+}
+
+impl PublishValue for f64 {
+    fn update_by_name(self, events: &mut Events, name: &str) -> bool {
+        events.update_f64_value_by_name(name, self)
+    }
+}
+
+/// The events of a device, together with the signals enabling their
+/// transmission to each broker and over the HTTP events stream.
+pub struct EventsHub<const BROKERS: usize> {
+    events: Mutex<CriticalSectionRawMutex, Events>,
+    write_on_network: [Signal<CriticalSectionRawMutex, u8>; BROKERS],
+    write_on_stream: Signal<CriticalSectionRawMutex, u8>,
+}
+
+impl<const BROKERS: usize> Default for EventsHub<BROKERS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BROKERS: usize> EventsHub<BROKERS> {
+    /// Creates an [`EventsHub`] without events.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            events: Mutex::new(Events::empty()),
+            write_on_network: [const { Signal::new() }; BROKERS],
+            write_on_stream: Signal::new(),
+        }
+    }
+
+    /// Returns the events.
+    #[must_use]
+    pub const fn events(&self) -> &Mutex<CriticalSectionRawMutex, Events> {
+        &self.events
+    }
+
+    /// Returns the signals enabling the network transmission, one for each
+    /// broker.
+    #[must_use]
+    pub const fn network_signals(&self) -> &[Signal<CriticalSectionRawMutex, u8>; BROKERS] {
+        &self.write_on_network
+    }
+
+    /// Notifies all brokers and the HTTP events stream that the events
+    /// have changed.
+    ///
+    /// Each broker is signaled independently, so a broker which cannot be
+    /// reached does not prevent the publication to the other ones.
+    pub fn notify_changed(&self) {
+        for signal in &self.write_on_network {
+            signal.signal(1);
+        }
+        self.write_on_stream.signal(1);
+    }
+
+    /// Updates the value of the event with the given name and immediately
+    /// notifies that the events have changed.
+    ///
+    /// # Errors
+    ///
+    /// Fails when no event of the given value type has the given name.
+    pub async fn publish_now<T: PublishValue>(
+        &self,
+        name: &str,
+        value: T,
+    ) -> Result<(), EventsError> {
+        // The lock will be released at the end of this scope.
+        let found = { value.update_by_name(&mut *self.events.lock().await, name) };
+
+        if !found {
+            return Err(EventsError::NotFound);
+        }
+
+        self.notify_changed();
+        Ok(())
+    }
+
+    /// Returns the current events as a Server-Sent Events frame.
+    ///
+    /// Any pending change notification for the stream is discarded, since
+    /// the frame already contains the latest events values.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the events cannot be serialized.
+    pub async fn stream_frame(&self) -> Result<Vec<u8>, serde_json::Error> {
+        self.write_on_stream.reset();
+        // The lock will be released at the end of this scope.
+        let json_data = { serde_json::to_vec(&*self.events.lock().await) };
+        json_data.map(|data| sse_frame(&data))
+    }
+
+    /// Waits until the events change.
+    pub async fn wait_for_change(&self) {
+        let _ = self.write_on_stream.wait().await;
+    }
+
+    /// Writes the events over a Server-Sent Events stream until writing
+    /// fails.
+    ///
+    /// The current events are written first, then one frame for each
+    /// change. When the events do not change within the keep-alive
+    /// interval, a [`KEEP_ALIVE_FRAME`] is written, so that a client which
+    /// closed the connection is detected without waiting for a change.
+    ///
+    /// # Errors
+    ///
+    /// Returns the write error which ends the stream, usually because the
+    /// client closed the connection.
+    pub async fn stream<W: Write>(
+        &self,
+        writer: &mut W,
+        keep_alive: Duration,
+    ) -> Result<(), W::Error> {
+        loop {
+            match self.stream_frame().await {
+                Ok(frame) => {
+                    writer.write_all(&frame).await?;
+                    writer.flush().await?;
+                }
+                Err(e) => error!("Error retrieving data: {e}"),
+            }
+
+            while with_timeout(keep_alive, self.wait_for_change())
+                .await
+                .is_err()
+            {
+                writer.write_all(KEEP_ALIVE_FRAME).await?;
+                writer.flush().await?;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use embassy_futures::block_on;
+    use embassy_futures::join::join;
+    use embassy_futures::yield_now;
+
+    use embassy_time::Duration;
+
+    use embedded_io_async::{ErrorKind, ErrorType, Write};
+
+    use tosca::events::Event;
+
+    use super::{EventsError, EventsHub, KEEP_ALIVE_FRAME};
+
+    // A client accepting a fixed number of writes, then closing the
+    // connection.
+    struct MockClient {
+        writes_left: usize,
+        output: Vec<u8>,
+    }
+
+    impl MockClient {
+        const fn new(writes_left: usize) -> Self {
+            Self {
+                writes_left,
+                output: Vec::new(),
+            }
+        }
+    }
+
+    impl ErrorType for MockClient {
+        type Error = ErrorKind;
+    }
+
+    impl Write for MockClient {
+        async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            if self.writes_left == 0 {
+                return Err(ErrorKind::ConnectionReset);
+            }
+            self.writes_left -= 1;
+            self.output.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    fn hub() -> EventsHub<2> {
+        let hub = EventsHub::new();
+        block_on(async {
+            hub.events()
+                .lock()
+                .await
+                .add_bool_event(Event::bool("door"));
+        });
+        hub
+    }
+
+    #[test]
+    fn test_publish_now_raises_signals() {
+        let hub = hub();
+
+        block_on(hub.publish_now("door", true)).unwrap();
+
+        // The event value is updated.
+        assert!(block_on(hub.events().lock()).bool_events_as_slice()[0].value);
+
+        // All broker signals fire right away.
+        for signal in hub.network_signals() {
+            assert!(signal.signaled());
+        }
+
+        // The events stream is woken up too.
+        block_on(hub.wait_for_change());
+    }
+
+    #[test]
+    fn test_publish_now_unknown_event() {
+        let hub = hub();
+
+        // Neither an unknown name nor a value of another type is published.
+        assert_eq!(
+            block_on(hub.publish_now("window", true)),
+            Err(EventsError::NotFound)
+        );
+        assert_eq!(
+            block_on(hub.publish_now("door", 1u8)),
+            Err(EventsError::NotFound)
+        );
+
+        for signal in hub.network_signals() {
+            assert!(!signal.signaled());
+        }
+    }
+
+    #[test]
+    fn test_stream_frame_discards_notification() {
+        let hub = hub();
+
+        hub.notify_changed();
+        assert!(block_on(hub.stream_frame()).unwrap().starts_with(b"data: "));
+
+        // The frame already contains the latest values, so the stream is
+        // not woken up again, while the brokers are still signaled.
+        assert!(!hub.write_on_stream.signaled());
+        assert!(hub.network_signals()[0].signaled());
+    }
+
+    #[test]
+    fn test_stream_ends_on_idle_closed_connection() {
+        let hub = hub();
+        // The first frame and a keep-alive are written, then the client
+        // disconnects without any event change.
+        let mut client = MockClient::new(2);
+
+        assert_eq!(
+            block_on(hub.stream(&mut client, Duration::from_millis(1))),
+            Err(ErrorKind::ConnectionReset)
+        );
+        assert!(client.output.starts_with(b"data: "));
+        assert!(client.output.ends_with(KEEP_ALIVE_FRAME));
+    }
+
+    #[test]
+    fn test_stream_writes_changes() {
+        let hub = hub();
+        // Only the first frame is written, then the client disconnects.
+        let mut client = MockClient::new(1);
+
+        let (result, ()) = block_on(join(
+            hub.stream(&mut client, Duration::from_secs(60)),
+            async {
+                yield_now().await;
+                hub.publish_now("door", true).await.unwrap();
+            },
+        ));
+
+        // The change wakes the stream up, which ends on the failed write.
+        assert_eq!(result, Err(ErrorKind::ConnectionReset));
+        assert!(client.output.starts_with(b"data: "));
+        assert!(!client.output.ends_with(KEEP_ALIVE_FRAME));
+    }
+}
@@ -0,0 +1,122 @@
+use core::fmt::Display;
+
+use log::error;
+
+/// A broker the events are published to.
+pub trait BrokerPublisher {
+    /// Endpoint of the broker, advertised to the controllers.
+    type Endpoint;
+    /// Error returned when the broker fails to start.
+    type Error: Display;
+
+    /// Starts publishing the events to the broker, returning its endpoint.
+    fn start(self) -> impl Future<Output = Result<Self::Endpoint, Self::Error>>;
+}
+
+/// Starts publishing the events to all the given brokers.
+///
+/// A broker which fails to start does not prevent the publication to the
+/// other ones.
+///
+/// # Errors
+///
+/// When no broker starts, the error of the last broker is returned, or
+/// `None` when there are no brokers at all.
+pub async fn start_publishers<P: BrokerPublisher>(
+    publishers: impl IntoIterator<Item = P>,
+) -> Result<P::Endpoint, Option<P::Error>> {
+    let mut first_endpoint = None;
+    let mut last_error = None;
+    for publisher in publishers {
+        match publisher.start().await {
+            Ok(endpoint) => {
+                if first_endpoint.is_none() {
+                    first_endpoint = Some(endpoint);
+                }
+            }
+            Err(e) => {
+                error!("Impossible to publish events to a broker: {e}");
+                last_error = Some(e);
+            }
+        }
+    }
+
+    first_endpoint.ok_or(last_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::RefCell;
+
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use embassy_futures::block_on;
+
+    use super::{BrokerPublisher, start_publishers};
+
+    // A publisher recording the brokers which have been started.
+    struct MockPublisher<'a> {
+        port: u16,
+        reachable: bool,
+        started: &'a RefCell<Vec<u16>>,
+    }
+
+    impl BrokerPublisher for MockPublisher<'_> {
+        type Endpoint = u16;
+        type Error = &'static str;
+
+        async fn start(self) -> Result<Self::Endpoint, Self::Error> {
+            if !self.reachable {
+                return Err("Unreachable broker");
+            }
+            self.started.borrow_mut().push(self.port);
+            Ok(self.port)
+        }
+    }
+
+    fn publishers<'a>(
+        brokers: &[(u16, bool)],
+        started: &'a RefCell<Vec<u16>>,
+    ) -> Vec<MockPublisher<'a>> {
+        brokers
+            .iter()
+            .map(|&(port, reachable)| MockPublisher {
+                port,
+                reachable,
+                started,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_failing_broker() {
+        let started = RefCell::new(Vec::new());
+
+        let endpoint = block_on(start_publishers(publishers(
+            &[(1883, false), (1884, true), (1885, true)],
+            &started,
+        )));
+
+        // The other brokers keep publishing, and the first one of them
+        // is advertised.
+        assert_eq!(endpoint.ok(), Some(1884));
+        assert_eq!(*started.borrow(), vec![1884, 1885]);
+    }
+
+    #[test]
+    fn test_no_started_broker() {
+        let started = RefCell::new(Vec::new());
+
+        // The error of the unreachable broker is returned.
+        assert_eq!(
+            block_on(start_publishers(publishers(&[(1883, false)], &started))),
+            Err(Some("Unreachable broker"))
+        );
+        assert_eq!(
+            block_on(start_publishers(publishers(&[], &started))),
+            Err(None)
+        );
+        assert!(started.borrow().is_empty());
+    }
+}
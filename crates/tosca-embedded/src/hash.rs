@@ -0,0 +1,23 @@
+/// Computes the FNV-1a hash of the given bytes.
+///
+/// It is not a cryptographic hash: it is only meant to detect corrupted data
+/// and to identify identical byte sequences.
+#[must_use]
+pub fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fnv1a;
+
+    #[test]
+    fn test_fnv1a() {
+        // Reference values of the FNV-1a 32-bit hash.
+        assert_eq!(fnv1a(b""), 0x811c_9dc5);
+        assert_eq!(fnv1a(b"a"), 0xe40c_292c);
+        assert_eq!(fnv1a(b"foobar"), 0xbf9c_f968);
+    }
+}
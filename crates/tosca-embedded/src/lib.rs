@@ -0,0 +1,53 @@
+//! `tosca-embedded` is a library crate providing the hardware-agnostic
+//! building blocks of `tosca` firmware running on microcontrollers.
+//!
+//! It contains the parts of a firmware which do not depend on a specific
+//! board, so that they are shared by the board-specific crates, such as
+//! `tosca-esp32c3`, and tested on the host.
+
+#![no_std]
+
+extern crate alloc;
+
+/// The HTTP basic authentication.
+pub mod auth;
+/// The backoff between consecutive connection attempts.
+pub mod backoff;
+/// The request bodies.
+pub mod body;
+/// The credentials to authenticate to an events broker.
+pub mod broker;
+/// The budget of a connection.
+pub mod budget;
+/// The Cross-Origin Resource Sharing (CORS) headers.
+pub mod cors;
+/// The building blocks of the device kinds.
+pub mod devices;
+/// The events shared between the publishers and the events stream.
+pub mod events;
+/// The publication of the events to multiple brokers.
+pub mod fanout;
+/// Hashing of byte sequences.
+pub mod hash;
+/// The `mDNS-SD` discovery service configuration.
+pub mod mdns;
+/// The network addresses.
+pub mod net;
+/// The parameters of the request bodies.
+pub mod parameters;
+/// The `Wi-Fi` provisioning process.
+pub mod provisioning;
+/// The limit of requests per second sent over a connection.
+pub mod rate_limit;
+/// The matching of requests against the device routes.
+pub mod route;
+/// The graceful shutdown of the server.
+pub mod shutdown;
+/// The device state.
+pub mod state;
+/// The byte streams sent over a connection.
+pub mod stream;
+/// The throttle of the `mDNS` replies.
+pub mod throttle;
+/// The validators of the route parameters.
+pub mod validators;
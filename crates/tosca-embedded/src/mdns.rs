@@ -0,0 +1,385 @@
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+// Hostname
+const HOSTNAME: &str = "tosca";
+// Service name
+const SERVICE: &str = "tosca";
+// Service type
+const SERVICE_TYPE: &str = "_tosca";
+// Transport protocol
+const TRANSPORT_PROTOCOL: &str = "_udp";
+// Time-to-live for answers in seconds
+const TIME_TO_LIVE: u32 = 60;
+
+/// Maximum time-to-live for answers in seconds.
+///
+/// RFC 6762 recommends 75 minutes as time-to-live for records which are not
+/// related to a host, so longer values are clamped to it.
+pub const MAXIMUM_TIME_TO_LIVE: u32 = 4500;
+
+/// Clamps a time-to-live value in the `[1, MAXIMUM_TIME_TO_LIVE]` range.
+#[must_use]
+pub const fn clamp_time_to_live(seconds: u32) -> u32 {
+    if seconds == 0 {
+        1
+    } else if seconds > MAXIMUM_TIME_TO_LIVE {
+        MAXIMUM_TIME_TO_LIVE
+    } else {
+        seconds
+    }
+}
+
+/// Returns the `TXT` properties describing a running server.
+///
+/// The device kind and main route let controllers filter the discovered
+/// devices without requesting their description. The `scheme` property is
+/// only added for `HTTPS` servers, so a user-defined one is preserved
+/// otherwise.
+#[must_use]
+pub fn server_properties(
+    kind: &'static str,
+    main_route: &'static str,
+    is_https: bool,
+) -> Vec<(&'static str, &'static str)> {
+    let mut properties = vec![("kind", kind), ("route", main_route)];
+    if is_https {
+        properties.push(("scheme", "https"));
+    }
+    properties
+}
+
+/// The records of a host answering `mDNS` queries.
+#[derive(Debug, PartialEq, Eq)]
+pub struct HostRecord {
+    /// Hostname.
+    pub hostname: &'static str,
+    /// `IPV4` address, unspecified when not advertised.
+    pub ipv4: Ipv4Addr,
+    /// `IPV6` address, unspecified when not advertised.
+    pub ipv6: Ipv6Addr,
+    /// Time-to-live of the answers in seconds.
+    pub time_to_live: u32,
+}
+
+/// The records of a service answering `mDNS-SD` queries.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ServiceRecord {
+    /// Service name.
+    pub name: &'static str,
+    /// Service type.
+    pub service_type: &'static str,
+    /// Transport protocol.
+    pub protocol: &'static str,
+    /// Service port.
+    pub port: u16,
+    /// Service subtypes.
+    pub service_subtypes: &'static [&'static str],
+    /// `TXT` properties.
+    pub properties: &'static [(&'static str, &'static str)],
+}
+
+/// The configuration of an `mDNS-SD` discovery service.
+///
+/// Time-to-live values are clamped with [`clamp_time_to_live`].
+#[derive(Clone, Copy)]
+pub struct MdnsConfig {
+    hostname: &'static str,
+    service: &'static str,
+    service_type: &'static str,
+    host_time_to_live: u32,
+    service_time_to_live: u32,
+    properties: &'static [(&'static str, &'static str)],
+    service_subtypes: &'static [&'static str],
+    throttle_window_ms: Option<u64>,
+}
+
+impl Default for MdnsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MdnsConfig {
+    /// Creates a [`MdnsConfig`] with the default values.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            hostname: HOSTNAME,
+            service: SERVICE,
+            service_type: SERVICE_TYPE,
+            host_time_to_live: TIME_TO_LIVE,
+            service_time_to_live: TIME_TO_LIVE,
+            properties: &[],
+            service_subtypes: &[],
+            throttle_window_ms: None,
+        }
+    }
+
+    /// Sets the hostname.
+    #[must_use]
+    pub const fn hostname(mut self, hostname: &'static str) -> Self {
+        self.hostname = hostname;
+        self
+    }
+
+    /// Sets the service name.
+    #[must_use]
+    pub const fn service(mut self, service: &'static str) -> Self {
+        self.service = service;
+        self
+    }
+
+    /// Sets the service type.
+    #[must_use]
+    pub const fn service_type(mut self, service_type: &'static str) -> Self {
+        self.service_type = service_type;
+        self
+    }
+
+    /// Sets the time-to-live of both host and service answers.
+    #[must_use]
+    pub const fn time_to_live(mut self, seconds: u32) -> Self {
+        self.host_time_to_live = clamp_time_to_live(seconds);
+        self.service_time_to_live = clamp_time_to_live(seconds);
+        self
+    }
+
+    /// Sets the time-to-live of the host answers.
+    #[must_use]
+    pub const fn host_time_to_live(mut self, seconds: u32) -> Self {
+        self.host_time_to_live = clamp_time_to_live(seconds);
+        self
+    }
+
+    /// Sets the time-to-live of the service answers.
+    #[must_use]
+    pub const fn service_time_to_live(mut self, seconds: u32) -> Self {
+        self.service_time_to_live = clamp_time_to_live(seconds);
+        self
+    }
+
+    /// Sets the `TXT` properties.
+    #[must_use]
+    pub const fn properties(mut self, properties: &'static [(&'static str, &'static str)]) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    /// Sets the service subtypes.
+    #[must_use]
+    pub const fn service_subtypes(mut self, service_subtypes: &'static [&'static str]) -> Self {
+        self.service_subtypes = service_subtypes;
+        self
+    }
+
+    /// Sets the window, in milliseconds, within which an identical reply
+    /// is multicast at most once.
+    ///
+    /// A value of `0` disables throttling.
+    #[must_use]
+    pub const fn throttle_responses(mut self, window_ms: u64) -> Self {
+        self.throttle_window_ms = if window_ms == 0 {
+            None
+        } else {
+            Some(window_ms)
+        };
+        self
+    }
+
+    /// Adds properties to the configured ones, replacing any configured
+    /// property with the same key.
+    #[must_use]
+    pub fn append_properties(mut self, properties: &[(&'static str, &'static str)]) -> Self {
+        let mut merged = self
+            .properties
+            .iter()
+            .filter(|(key, _)| properties.iter().all(|(other_key, _)| other_key != key))
+            .copied()
+            .collect::<Vec<_>>();
+        merged.extend_from_slice(properties);
+        // The properties are built once, when the server starts, and live
+        // as long as the mDNS task.
+        self.properties = merged.leak();
+        self
+    }
+
+    /// Returns the window of the replies throttle, if any.
+    #[must_use]
+    pub const fn throttle_window_ms(&self) -> Option<u64> {
+        self.throttle_window_ms
+    }
+
+    /// Returns the host answering with the device addresses, and the host
+    /// providing the time-to-live of the service answers.
+    ///
+    /// An unspecified `IPV6` address prevents the first host from
+    /// answering with `IPV6` address records. The second host has
+    /// unspecified addresses, so that it never answers with address
+    /// records, which are already provided by the first one.
+    #[must_use]
+    pub const fn host_records(
+        &self,
+        ipv4: Ipv4Addr,
+        ipv6: Option<Ipv6Addr>,
+    ) -> (HostRecord, HostRecord) {
+        (
+            HostRecord {
+                hostname: self.hostname,
+                ipv4,
+                ipv6: match ipv6 {
+                    Some(ipv6) => ipv6,
+                    None => Ipv6Addr::UNSPECIFIED,
+                },
+                time_to_live: self.host_time_to_live,
+            },
+            HostRecord {
+                hostname: self.hostname,
+                ipv4: Ipv4Addr::UNSPECIFIED,
+                ipv6: Ipv6Addr::UNSPECIFIED,
+                time_to_live: self.service_time_to_live,
+            },
+        )
+    }
+
+    /// Returns the service answering on the given port.
+    #[must_use]
+    pub const fn service_record(&self, port: u16) -> ServiceRecord {
+        ServiceRecord {
+            name: self.service,
+            service_type: self.service_type,
+            protocol: TRANSPORT_PROTOCOL,
+            port,
+            service_subtypes: self.service_subtypes,
+            properties: self.properties,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::net::{Ipv4Addr, Ipv6Addr};
+
+    use tosca::device::{DeviceKind, DeviceKindTrait};
+
+    use super::{MAXIMUM_TIME_TO_LIVE, MdnsConfig, clamp_time_to_live, server_properties};
+
+    const IPV4: Ipv4Addr = Ipv4Addr::new(192, 168, 1, 10);
+    const IPV6: Ipv6Addr = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+
+    #[test]
+    fn test_clamp_time_to_live() {
+        // Below the minimum.
+        assert_eq!(clamp_time_to_live(0), 1);
+
+        // At the limits.
+        assert_eq!(clamp_time_to_live(1), 1);
+        assert_eq!(
+            clamp_time_to_live(MAXIMUM_TIME_TO_LIVE),
+            MAXIMUM_TIME_TO_LIVE
+        );
+
+        // Within the limits.
+        assert_eq!(clamp_time_to_live(120), 120);
+
+        // Above the maximum.
+        assert_eq!(
+            clamp_time_to_live(MAXIMUM_TIME_TO_LIVE + 1),
+            MAXIMUM_TIME_TO_LIVE
+        );
+        assert_eq!(clamp_time_to_live(u32::MAX), MAXIMUM_TIME_TO_LIVE);
+    }
+
+    #[test]
+    fn test_separate_time_to_live() {
+        let config = MdnsConfig::new()
+            .host_time_to_live(90)
+            .service_time_to_live(3600);
+
+        let (host, service_host) = config.host_records(IPV4, Some(IPV6));
+
+        // The host answers with the device addresses and the host TTL.
+        assert_eq!(host.time_to_live, 90);
+        assert_eq!((host.ipv4, host.ipv6), (IPV4, IPV6));
+
+        // The service answers take the service TTL, without addresses.
+        assert_eq!(service_host.time_to_live, 3600);
+        assert_eq!(
+            (service_host.ipv4, service_host.ipv6),
+            (Ipv4Addr::UNSPECIFIED, Ipv6Addr::UNSPECIFIED)
+        );
+    }
+
+    #[test]
+    fn test_time_to_live_plumbing() {
+        // The default TTL is shared by host and service answers.
+        let (host, service_host) = MdnsConfig::new().host_records(IPV4, None);
+        assert_eq!((host.time_to_live, service_host.time_to_live), (60, 60));
+        assert_eq!(host.ipv6, Ipv6Addr::UNSPECIFIED);
+
+        // Out of range values are clamped.
+        let (host, service_host) = MdnsConfig::new().time_to_live(0).host_records(IPV4, None);
+        assert_eq!((host.time_to_live, service_host.time_to_live), (1, 1));
+
+        let (host, service_host) = MdnsConfig::new()
+            .host_time_to_live(u32::MAX)
+            .service_time_to_live(MAXIMUM_TIME_TO_LIVE + 1)
+            .host_records(IPV4, None);
+        assert_eq!(
+            (host.time_to_live, service_host.time_to_live),
+            (MAXIMUM_TIME_TO_LIVE, MAXIMUM_TIME_TO_LIVE)
+        );
+    }
+
+    #[test]
+    fn test_service_subtypes_and_properties() {
+        // By default, the service has neither subtypes nor properties.
+        let service = MdnsConfig::new().service_record(80);
+        assert!(service.service_subtypes.is_empty());
+        assert!(service.properties.is_empty());
+
+        let service = MdnsConfig::new()
+            .service_subtypes(&["_light", "_dimmable"])
+            .properties(&[("room", "kitchen")])
+            .service_record(80);
+
+        assert_eq!(service.service_subtypes, ["_light", "_dimmable"]);
+        assert_eq!(service.properties, [("room", "kitchen")]);
+        assert_eq!(
+            (service.name, service.service_type, service.protocol),
+            ("tosca", "_tosca", "_udp")
+        );
+        assert_eq!(service.port, 80);
+    }
+
+    #[test]
+    fn test_light_server_properties() {
+        let properties = server_properties(DeviceKind::Light.name(), "/light", false);
+        assert_eq!(properties, [("kind", "Light"), ("route", "/light")]);
+
+        // The server properties replace the user-defined ones with the same
+        // key, while a user-defined scheme is preserved.
+        let service = MdnsConfig::new()
+            .properties(&[("room", "kitchen"), ("kind", "lamp"), ("scheme", "https")])
+            .append_properties(&properties)
+            .service_record(80);
+        assert_eq!(
+            service.properties,
+            [
+                ("room", "kitchen"),
+                ("scheme", "https"),
+                ("kind", "Light"),
+                ("route", "/light")
+            ]
+        );
+
+        // An HTTPS server advertises its scheme.
+        let properties = server_properties(DeviceKind::Light.name(), "/light", true);
+        assert_eq!(
+            properties,
+            [("kind", "Light"), ("route", "/light"), ("scheme", "https")]
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// Selects the socket address the server binds.
+///
+/// The IPV4 address is used by default. When IPV6 is enabled and the stack
+/// has an IPV6 address, the unspecified IPV6 address is bound instead,
+/// so that the server accepts connections on both its IPV4 and IPV6
+/// addresses.
+#[must_use]
+pub fn select_bind_address(
+    ipv4: Ipv4Addr,
+    ipv6: Option<Ipv6Addr>,
+    ipv6_enabled: bool,
+    port: u16,
+) -> SocketAddr {
+    let address = match ipv6 {
+        Some(_) if ipv6_enabled => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        _ => IpAddr::V4(ipv4),
+    };
+    SocketAddr::new(address, port)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    use super::select_bind_address;
+
+    const IPV4: Ipv4Addr = Ipv4Addr::new(192, 168, 1, 10);
+    const IPV6: Ipv6Addr = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+
+    #[test]
+    fn test_bind_ipv4_by_default() {
+        assert_eq!(
+            select_bind_address(IPV4, Some(IPV6), false, 80),
+            SocketAddr::new(IpAddr::V4(IPV4), 80)
+        );
+    }
+
+    #[test]
+    fn test_bind_ipv6() {
+        assert_eq!(
+            select_bind_address(IPV4, Some(IPV6), true, 80),
+            SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 80)
+        );
+    }
+
+    #[test]
+    fn test_bind_ipv6_unavailable() {
+        assert_eq!(
+            select_bind_address(IPV4, None, true, 80),
+            SocketAddr::new(IpAddr::V4(IPV4), 80)
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use log::info;
+
+use tosca::parameters::{
+    ParameterKind, ParameterPayload, ParameterValue, ParametersPayloads, ParametersValues,
+};
+use tosca::route::RouteConfig;
+
+/// Errors that may occur when parsing the parameters of a request.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParametersError {
+    /// The body is not a sequence of parameters.
+    ///
+    /// Contains the description of the deserialization error.
+    Deserialization(String),
+    /// The parameters do not match the ones of the route.
+    ///
+    /// Contains the description of the mismatch.
+    InvalidData(String),
+}
+
+/// Parses the parameters of a route from a JSON request body.
+///
+/// `PUT`, `POST`, and `DELETE` routes receive their parameters in the
+/// request body, hence they are all parsed in the same way.
+///
+/// A value outside the bounds of its parameter kind is clamped within them
+/// or, in strict mode, rejected.
+///
+/// # Errors
+///
+/// - The body is not a sequence of parameters
+/// - Some route parameters are missing, or some parameters do not belong
+///   to the route
+/// - A value does not match the kind of its parameter
+/// - In strict mode, a value lies outside the bounds of its parameter kind
+pub fn parse_body_parameters(
+    route_config: &RouteConfig,
+    bytes: &[u8],
+    strict: bool,
+) -> Result<ParametersPayloads<'static>, ParametersError> {
+    let route_parameters = serde_json::from_slice::<ParametersValues<'_>>(bytes)
+        .map_err(|e| ParametersError::Deserialization(e.to_string()))?;
+
+    info!("Route parameters: {route_parameters:?}");
+
+    // Reject the request up-front when some parameters are missing,
+    // reporting all of them at once.
+    let missing_parameters = route_config
+        .data
+        .parameters
+        .missing_parameters(&route_parameters);
+    if !missing_parameters.is_empty() {
+        return Err(ParametersError::InvalidData(format!(
+            "Passed body is incomplete, missing parameters: {missing_parameters:?}"
+        )));
+    }
+
+    let mut parameters_payloads = ParametersPayloads::new();
+    for (parameter_name, parameter_value) in route_parameters {
+        let parameter_kind = route_config
+            .data
+            .parameters
+            .get(&parameter_name)
+            .ok_or_else(|| {
+                ParametersError::InvalidData(format!("Parameter `{parameter_name}` not found"))
+            })?;
+
+        if !parameter_value.match_kind(parameter_kind) {
+            return Err(ParametersError::InvalidData(format!(
+                "Found type `{}` for `{parameter_name}`, expected type `{}`",
+                parameter_value.as_type(),
+                parameter_kind.as_type(),
+            )));
+        }
+
+        let parameter_value =
+            bound_parameter_value(&parameter_name, parameter_value, parameter_kind, strict)?;
+
+        parameters_payloads.add(
+            parameter_name,
+            ParameterPayload::new(parameter_kind.clone(), parameter_value),
+        );
+    }
+
+    Ok(parameters_payloads)
+}
+
+/// Clamps a parameter value to the bounds of its kind or, in strict mode,
+/// rejects it when it lies outside them.
+///
+/// # Errors
+///
+/// In strict mode, the value lies outside the bounds of its parameter kind.
+pub fn bound_parameter_value(
+    parameter_name: &str,
+    parameter_value: ParameterValue,
+    parameter_kind: &ParameterKind,
+    strict: bool,
+) -> Result<ParameterValue, ParametersError> {
+    if !strict {
+        return Ok(parameter_value.clamp(parameter_kind));
+    }
+
+    if !parameter_value.is_within_bounds(parameter_kind) {
+        return Err(ParametersError::InvalidData(format!(
+            "Value `{parameter_value}` for `{parameter_name}` is out of the allowed bounds"
+        )));
+    }
+
+    Ok(parameter_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use tosca::parameters::{ParameterValue, Parameters};
+    use tosca::route::{RestKind, Route, RouteConfig};
+
+    use crate::route::{RouteMatch, match_route};
+
+    use super::{ParametersError, parse_body_parameters};
+
+    fn reset_route() -> RouteConfig {
+        Route::delete("Reset", "/reset")
+            .with_parameters(Parameters::new().u8_with_limits("delay", 0, 0, 10))
+            .serialize_data()
+    }
+
+    fn delay(bytes: &[u8], strict: bool) -> Result<ParameterValue, ParametersError> {
+        let mut payloads = parse_body_parameters(&reset_route(), bytes, strict)?;
+        Ok(payloads.extract("delay").unwrap().value)
+    }
+
+    #[test]
+    fn test_delete_with_parameters() {
+        let route = reset_route();
+
+        // A DELETE request is dispatched to the route...
+        assert_eq!(
+            match_route(
+                "/light",
+                [(route.data.path.as_ref(), route.rest_kind)],
+                Some(RestKind::Delete),
+                "/light/reset",
+            ),
+            RouteMatch::Found(0)
+        );
+
+        // ...and its JSON body reaches the handler as parameters.
+        assert_eq!(delay(br#"{"delay":5}"#, true), Ok(ParameterValue::U8(5)));
+    }
+
+    #[test]
+    fn test_body_parameters_bounds() {
+        // Out of bounds values are clamped, unless in strict mode.
+        assert_eq!(delay(br#"{"delay":50}"#, false), Ok(ParameterValue::U8(10)));
+        assert!(matches!(
+            delay(br#"{"delay":50}"#, true),
+            Err(ParametersError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_body_parameters() {
+        assert!(matches!(
+            delay(b"not json", true),
+            Err(ParametersError::Deserialization(_))
+        ));
+
+        for body in [
+            b"{}".as_slice(),
+            br#"{"delay":true}"#,
+            br#"{"delay":5,"other":1}"#,
+        ] {
+            assert!(matches!(
+                delay(body, true),
+                Err(ParametersError::InvalidData(_))
+            ));
+        }
+    }
+}
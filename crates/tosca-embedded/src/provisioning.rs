@@ -0,0 +1,237 @@
+use alloc::string::String;
+
+use serde::Deserialize;
+
+// Maximum length, in bytes, of a `Wi-Fi` SSID.
+const MAXIMUM_SSID_LENGTH: usize = 32;
+
+// Length bounds, in bytes, of a `WPA2` passphrase.
+const MINIMUM_PASSWORD_LENGTH: usize = 8;
+const MAXIMUM_PASSWORD_LENGTH: usize = 64;
+
+/// Errors that may occur during the provisioning process.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ProvisioningError {
+    /// Empty SSID or SSID longer than 32 bytes.
+    InvalidSsid,
+    /// Password shorter than 8 bytes or longer than 64 bytes.
+    InvalidPassword,
+    /// The device has already taken its credentials.
+    AlreadyProvisioned,
+}
+
+impl ProvisioningError {
+    /// Returns the error description.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::InvalidSsid => "Invalid Wi-Fi SSID length",
+            Self::InvalidPassword => "Invalid Wi-Fi password length",
+            Self::AlreadyProvisioned => "The device has already been provisioned",
+        }
+    }
+}
+
+impl core::fmt::Display for ProvisioningError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.description().fmt(f)
+    }
+}
+
+impl core::error::Error for ProvisioningError {}
+
+/// `Wi-Fi` credentials received during provisioning.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    ssid: String,
+    password: String,
+}
+
+impl Credentials {
+    /// Creates [`Credentials`] from an SSID and a `WPA2` password.
+    ///
+    /// # Errors
+    ///
+    /// - Empty SSID or SSID longer than 32 bytes
+    /// - Password shorter than 8 bytes or longer than 64 bytes
+    pub fn new(ssid: &str, password: &str) -> Result<Self, ProvisioningError> {
+        if ssid.is_empty() || ssid.len() > MAXIMUM_SSID_LENGTH {
+            return Err(ProvisioningError::InvalidSsid);
+        }
+
+        if !(MINIMUM_PASSWORD_LENGTH..=MAXIMUM_PASSWORD_LENGTH).contains(&password.len()) {
+            return Err(ProvisioningError::InvalidPassword);
+        }
+
+        Ok(Self {
+            ssid: ssid.into(),
+            password: password.into(),
+        })
+    }
+
+    /// Returns the SSID.
+    #[must_use]
+    pub fn ssid(&self) -> &str {
+        &self.ssid
+    }
+
+    /// Returns the password.
+    #[must_use]
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+}
+
+/// Credentials as sent to the provisioning route.
+#[derive(Deserialize)]
+pub struct CredentialsPayload {
+    ssid: String,
+    password: String,
+}
+
+impl CredentialsPayload {
+    /// Validates the received credentials.
+    ///
+    /// # Errors
+    ///
+    /// The credentials are not valid, see [`Credentials::new`].
+    pub fn into_credentials(self) -> Result<Credentials, ProvisioningError> {
+        Credentials::new(&self.ssid, &self.password)
+    }
+}
+
+/// The `Wi-Fi` mode of a device being provisioned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvisioningMode {
+    /// The device runs its own access point, waiting for credentials.
+    AccessPoint,
+    /// The device has taken the credentials and connects to
+    /// an access point as a station.
+    Station,
+}
+
+/// Credentials handling and mode transitions of the provisioning process.
+///
+/// Credentials can be replaced as long as the device is an access point,
+/// then they are taken once, switching the device to station mode.
+pub struct Provisioning {
+    mode: ProvisioningMode,
+    credentials: Option<Credentials>,
+}
+
+impl Default for Provisioning {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Provisioning {
+    /// Creates a [`Provisioning`] process, starting as an access point.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            mode: ProvisioningMode::AccessPoint,
+            credentials: None,
+        }
+    }
+
+    /// Returns the current [`ProvisioningMode`].
+    #[must_use]
+    pub const fn mode(&self) -> ProvisioningMode {
+        self.mode
+    }
+
+    /// Stores the given credentials, replacing the previous ones.
+    ///
+    /// # Errors
+    ///
+    /// The device has already switched to station mode.
+    pub fn store(&mut self, credentials: Credentials) -> Result<(), ProvisioningError> {
+        if self.mode == ProvisioningMode::Station {
+            return Err(ProvisioningError::AlreadyProvisioned);
+        }
+        self.credentials = Some(credentials);
+        Ok(())
+    }
+
+    /// Takes the stored credentials, if any, switching to station mode.
+    pub fn switch_to_station(&mut self) -> Option<Credentials> {
+        let credentials = self.credentials.take()?;
+        self.mode = ProvisioningMode::Station;
+        Some(credentials)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Credentials, CredentialsPayload, Provisioning, ProvisioningError, ProvisioningMode,
+    };
+
+    const PASSWORD: &str = "password";
+
+    #[test]
+    fn test_credentials_validation() {
+        assert!(Credentials::new("network", PASSWORD).is_ok());
+        assert!(Credentials::new(&"s".repeat(32), &"p".repeat(64)).is_ok());
+
+        assert!(matches!(
+            Credentials::new("", PASSWORD),
+            Err(ProvisioningError::InvalidSsid)
+        ));
+        assert!(matches!(
+            Credentials::new(&"s".repeat(33), PASSWORD),
+            Err(ProvisioningError::InvalidSsid)
+        ));
+        assert!(matches!(
+            Credentials::new("network", "short"),
+            Err(ProvisioningError::InvalidPassword)
+        ));
+        assert!(matches!(
+            Credentials::new("network", &"p".repeat(65)),
+            Err(ProvisioningError::InvalidPassword)
+        ));
+    }
+
+    #[test]
+    fn test_credentials_payload() {
+        let payload: CredentialsPayload =
+            serde_json::from_str(r#"{"ssid":"network","password":"password"}"#).unwrap();
+        let credentials = payload.into_credentials().unwrap();
+        assert_eq!(credentials.ssid(), "network");
+        assert_eq!(credentials.password(), PASSWORD);
+
+        let payload: CredentialsPayload =
+            serde_json::from_str(r#"{"ssid":"","password":"password"}"#).unwrap();
+        assert!(payload.into_credentials().is_err());
+    }
+
+    #[test]
+    fn test_provisioning_transitions() {
+        let mut provisioning = Provisioning::new();
+        assert_eq!(provisioning.mode(), ProvisioningMode::AccessPoint);
+
+        // No credentials have been received yet.
+        assert!(provisioning.switch_to_station().is_none());
+        assert_eq!(provisioning.mode(), ProvisioningMode::AccessPoint);
+
+        // Credentials are replaced until they are taken.
+        provisioning
+            .store(Credentials::new("first", PASSWORD).unwrap())
+            .unwrap();
+        provisioning
+            .store(Credentials::new("second", PASSWORD).unwrap())
+            .unwrap();
+
+        let credentials = provisioning.switch_to_station().unwrap();
+        assert_eq!(credentials.ssid(), "second");
+        assert_eq!(provisioning.mode(), ProvisioningMode::Station);
+
+        // Once taken, other credentials are rejected.
+        assert_eq!(
+            provisioning.store(Credentials::new("third", PASSWORD).unwrap()),
+            Err(ProvisioningError::AlreadyProvisioned)
+        );
+        assert!(provisioning.switch_to_station().is_none());
+    }
+}
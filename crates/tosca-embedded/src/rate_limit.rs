@@ -0,0 +1,86 @@
+use embassy_time::{Duration, Instant};
+
+// Length of the window in which requests are counted.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Response sent to a client which has exceeded the requests limit of its
+/// connection.
+///
+/// It is written on the raw socket, as the byte budget rejection, and asks
+/// the client to wait for the next window before sending a new request.
+pub const TOO_MANY_REQUESTS_RESPONSE: &[u8] = b"HTTP/1.1 429 Too Many Requests\r\n\
+    Retry-After: 1\r\nConnection: close\r\nContent-Length: 0\r\n\r\n";
+
+/// Limits the number of requests per second sent over a connection.
+///
+/// Each connection owns its window, so a new connection never inherits the
+/// requests counted for a previous client.
+#[derive(Clone, Copy)]
+pub struct RequestWindow {
+    max_per_second: u32,
+    start: Option<Instant>,
+    count: u32,
+}
+
+impl RequestWindow {
+    /// Creates a [`RequestWindow`] allowing the given number of requests
+    /// per second.
+    #[must_use]
+    pub const fn new(max_per_second: u32) -> Self {
+        Self {
+            max_per_second,
+            start: None,
+            count: 0,
+        }
+    }
+
+    /// Records a request received at the given instant, returning whether
+    /// it is within the limit.
+    pub fn allow(&mut self, now: Instant) -> bool {
+        if self
+            .start
+            .is_none_or(|start| now.saturating_duration_since(start) >= WINDOW)
+        {
+            self.start = Some(now);
+            self.count = 0;
+        }
+
+        self.count = self.count.saturating_add(1);
+        self.count <= self.max_per_second
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embassy_time::Instant;
+
+    use super::RequestWindow;
+
+    #[test]
+    fn test_requests_over_limit() {
+        let mut window = RequestWindow::new(3);
+
+        // Requests sent faster than the limit are answered with a 429.
+        for ms in 0..3 {
+            assert!(window.allow(Instant::from_millis(ms * 10)));
+        }
+        for ms in 3..6 {
+            assert!(!window.allow(Instant::from_millis(ms * 10)));
+        }
+
+        // Requests are served again once the window has elapsed.
+        assert!(window.allow(Instant::from_millis(1000)));
+        assert!(window.allow(Instant::from_millis(1100)));
+    }
+
+    #[test]
+    fn test_new_connection_window() {
+        let mut window = RequestWindow::new(1);
+        assert!(window.allow(Instant::from_millis(0)));
+        assert!(!window.allow(Instant::from_millis(1)));
+
+        // A new connection starts with an empty window.
+        let mut window = RequestWindow::new(1);
+        assert!(window.allow(Instant::from_millis(2)));
+    }
+}
@@ -0,0 +1,181 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use tosca::route::RestKind;
+
+/// Outcome of matching a request against the device routes.
+#[derive(Debug, PartialEq)]
+pub enum RouteMatch {
+    /// Index of the route matching the request.
+    Found(usize),
+    /// Methods of the routes matching the request path, but not its method.
+    NotAllowed(Vec<RestKind>),
+    /// No route matches the request path.
+    NotFound,
+}
+
+/// Matches a request path, without its trailing '/', against the routes of
+/// a device.
+///
+/// The path must start with the main route, followed by the segments of
+/// a route path. Any remaining segment is a route parameter.
+///
+/// The request method is `None` when it does not correspond to any
+/// [`RestKind`], hence it never matches a route.
+pub fn match_route<'a>(
+    main_route: &str,
+    routes: impl IntoIterator<Item = (&'a str, RestKind)>,
+    method: Option<RestKind>,
+    path: &str,
+) -> RouteMatch {
+    let mut segments = path.split_terminator('/');
+
+    // The first segment of a route is always an empty path, because
+    // **each** path begins with a '/'. If it is equal to the whole path,
+    // the route makes use of a wrong separator.
+    match segments.next() {
+        Some(empty_path) if empty_path != path => {}
+        _ => return RouteMatch::NotFound,
+    }
+
+    // Skip the '/' placed before the main route.
+    if segments.next() != Some(&main_route[1..]) {
+        return RouteMatch::NotFound;
+    }
+
+    let mut allowed_methods = Vec::new();
+    for (index, (route_path, rest_kind)) in routes.into_iter().enumerate() {
+        // Compare route segments with the corresponding path segments.
+        // If all segments match in order, this is the correct route.
+        let mut path_segments = segments.clone();
+        if !route_path[1..]
+            .split_terminator('/')
+            .all(|segment| path_segments.next() == Some(segment))
+        {
+            continue;
+        }
+
+        // If the request REST method is different from the route
+        // method, save the route method and skip to the next route.
+        if method != Some(rest_kind) {
+            allowed_methods.push(rest_kind);
+            continue;
+        }
+
+        return RouteMatch::Found(index);
+    }
+
+    // When no route matches, the path exists only if some routes
+    // matched it with another method.
+    if allowed_methods.is_empty() {
+        RouteMatch::NotFound
+    } else {
+        RouteMatch::NotAllowed(allowed_methods)
+    }
+}
+
+/// Lists the given methods as the value of an `Allow` header.
+///
+/// The methods are listed once each, in a fixed order.
+#[must_use]
+pub fn allow_header(rest_kinds: &[RestKind]) -> String {
+    [
+        RestKind::Get,
+        RestKind::Put,
+        RestKind::Post,
+        RestKind::Delete,
+    ]
+    .into_iter()
+    .filter(|rest_kind| rest_kinds.contains(rest_kind))
+    .map(|rest_kind| rest_kind.to_string())
+    .collect::<Vec<String>>()
+    .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use tosca::route::RestKind;
+
+    use super::{RouteMatch, allow_header, match_route};
+
+    const ROUTES: [(&str, RestKind); 4] = [
+        ("/on", RestKind::Put),
+        ("/off", RestKind::Put),
+        ("/brightness", RestKind::Get),
+        ("/brightness", RestKind::Post),
+    ];
+
+    #[test]
+    fn test_route_dispatch() {
+        assert_eq!(
+            match_route("/light", ROUTES, Some(RestKind::Put), "/light/off"),
+            RouteMatch::Found(1)
+        );
+
+        // The remaining segments are the route parameters.
+        assert_eq!(
+            match_route("/light", ROUTES, Some(RestKind::Get), "/light/brightness/5"),
+            RouteMatch::Found(2)
+        );
+        assert_eq!(
+            match_route("/light", ROUTES, Some(RestKind::Post), "/light/brightness"),
+            RouteMatch::Found(3)
+        );
+    }
+
+    #[test]
+    fn test_route_method_not_allowed() {
+        assert_eq!(
+            match_route("/light", ROUTES, Some(RestKind::Get), "/light/on"),
+            RouteMatch::NotAllowed(vec![RestKind::Put])
+        );
+        assert_eq!(
+            match_route(
+                "/light",
+                ROUTES,
+                Some(RestKind::Delete),
+                "/light/brightness"
+            ),
+            RouteMatch::NotAllowed(vec![RestKind::Get, RestKind::Post])
+        );
+
+        // A method without a REST kind never matches a route.
+        assert_eq!(
+            match_route("/light", ROUTES, None, "/light/off"),
+            RouteMatch::NotAllowed(vec![RestKind::Put])
+        );
+    }
+
+    #[test]
+    fn test_route_not_found() {
+        for path in ["/light/toggle", "/fan/on", "/light", "light/on", ""] {
+            assert_eq!(
+                match_route("/light", ROUTES, Some(RestKind::Put), path),
+                RouteMatch::NotFound
+            );
+        }
+    }
+
+    #[test]
+    fn test_allow_header() {
+        // An unsupported method sent to a GET-only route.
+        let RouteMatch::NotAllowed(allowed_methods) = match_route(
+            "/light",
+            [("/brightness", RestKind::Get)],
+            Some(RestKind::Post),
+            "/light/brightness",
+        ) else {
+            panic!("The route method should not be allowed");
+        };
+        assert_eq!(allow_header(&allowed_methods), "GET");
+
+        // Methods are listed once, in a fixed order.
+        assert_eq!(
+            allow_header(&[RestKind::Delete, RestKind::Get, RestKind::Delete]),
+            "GET, DELETE"
+        );
+        assert_eq!(allow_header(&[]), "");
+    }
+}
@@ -0,0 +1,73 @@
+use embassy_futures::select::{Either, select};
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+
+use log::info;
+
+/// Runs the server future until it ends or the shutdown signal is raised,
+/// in which case the server future is dropped and `Ok(())` is returned.
+///
+/// # Errors
+///
+/// Returns the error of the server future when it fails before the
+/// shutdown signal is raised.
+pub async fn until_shutdown<F, E>(
+    server: F,
+    shutdown: &Signal<CriticalSectionRawMutex, ()>,
+) -> Result<(), E>
+where
+    F: Future<Output = Result<(), E>>,
+{
+    match select(server, shutdown.wait()).await {
+        Either::First(result) => result,
+        Either::Second(()) => {
+            info!("Shutdown signal received, stopping the server");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::future::pending;
+
+    use embassy_futures::block_on;
+    use embassy_futures::join::join;
+    use embassy_futures::yield_now;
+
+    use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+    use embassy_sync::signal::Signal;
+
+    use super::until_shutdown;
+
+    #[test]
+    fn test_shutdown_terminates_loop() {
+        let shutdown = Signal::<CriticalSectionRawMutex, ()>::new();
+
+        // A server loop which never ends on its own.
+        let server = pending::<Result<(), &str>>();
+
+        let (result, ()) = block_on(join(until_shutdown(server, &shutdown), async {
+            yield_now().await;
+            shutdown.signal(());
+        }));
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_server_result_returned() {
+        let shutdown = Signal::<CriticalSectionRawMutex, ()>::new();
+
+        // The server loop result is returned when it ends first.
+        assert_eq!(
+            block_on(until_shutdown(async { Err("accept failed") }, &shutdown)),
+            Err("accept failed")
+        );
+        assert_eq!(
+            block_on(until_shutdown(async { Ok::<(), ()>(()) }, &shutdown)),
+            Ok(())
+        );
+    }
+}
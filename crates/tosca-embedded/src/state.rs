@@ -0,0 +1,560 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::mutex::Mutex;
+
+use embedded_storage::Storage;
+
+use log::{error, info};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::hash::fnv1a;
+
+/// The device state.
+///
+/// The state can **only** be accessed and modified within a route handler.
+pub struct State<S>(pub S);
+
+/// A trait for retrieving the substates of the device [`State`].
+pub trait ValueFromRef {
+    /// Retrieves the internal value from its reference.
+    #[must_use]
+    fn value_from_ref(&self) -> Self;
+}
+
+impl ValueFromRef for () {
+    fn value_from_ref(&self) -> Self {}
+}
+
+/// A part of the device [`State`] which is shared among route handlers.
+///
+/// The [`State`] is handed to each route handler through
+/// [`ValueFromRef`], so a plain value is copied out of the state
+/// and any change made by a handler is lost once it returns.
+/// A [`SharedState`] instead refers to a single value protected by a mutex:
+/// its copies share that value, so a change made by a handler is observed
+/// by all the following requests.
+///
+/// ```ignore
+/// async fn increment(
+///     State(counter): State<SharedState<u32>>,
+///     _parameters: ParametersPayloads,
+/// ) -> Result<SerialResponse, ErrorResponse> {
+///     let value = counter.update(|value| {
+///         *value += 1;
+///         *value
+///     }).await;
+///     Ok(SerialResponse::new(value))
+/// }
+/// ```
+pub struct SharedState<T: 'static>(&'static Mutex<CriticalSectionRawMutex, T>);
+
+impl<T: 'static> Clone for SharedState<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for SharedState<T> {}
+
+impl<T: 'static> ValueFromRef for SharedState<T> {
+    fn value_from_ref(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> SharedState<T> {
+    /// Creates a [`SharedState`] with the given initial value.
+    ///
+    /// This method should be called **once** at boot, since the value
+    /// lives for the whole firmware execution.
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        Self(Box::leak(Box::new(Mutex::new(value))))
+    }
+
+    /// Returns a copy of the current value.
+    pub async fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.0.lock().await.clone()
+    }
+
+    /// Sets a new value.
+    pub async fn set(&self, value: T) {
+        *self.0.lock().await = value;
+    }
+
+    /// Updates the value through the given function, returning its result.
+    ///
+    /// The value is locked for the whole function execution, so concurrent
+    /// updates are never lost.
+    pub async fn update<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
+        f(&mut *self.0.lock().await)
+    }
+}
+
+/// Errors that may occur when storing a [`PersistentState`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StorageError {
+    /// Failed to read from the storage.
+    Read,
+    /// Failed to write into the storage.
+    Write,
+    /// The state exceeds the storage capacity.
+    Capacity,
+    /// The storage address is out of the addressable range.
+    Address,
+    /// Failed to serialize the state.
+    Serialization,
+}
+
+impl StorageError {
+    /// Returns the error description.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Read => "Failed to read from the storage",
+            Self::Write => "Failed to write into the storage",
+            Self::Capacity => "The state exceeds the storage capacity",
+            Self::Address => "The storage address is out of range",
+            Self::Serialization => "Failed to serialize the persistent state",
+        }
+    }
+}
+
+impl core::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.description().fmt(f)
+    }
+}
+
+impl core::error::Error for StorageError {}
+
+/// A storage backend for a [`PersistentState`].
+///
+/// Implementing this trait on an in-memory type allows to exercise
+/// a [`PersistentState`] without a flash memory.
+pub trait StateStore {
+    /// Loads the stored bytes.
+    ///
+    /// Returns `None` when no valid bytes have been stored yet.
+    ///
+    /// # Errors
+    ///
+    /// Failed to read from the storage.
+    fn load(&mut self) -> Result<Option<Vec<u8>>, StorageError>;
+
+    /// Stores the given bytes, replacing the previous ones.
+    ///
+    /// # Errors
+    ///
+    /// Failed to write into the storage.
+    fn save(&mut self, bytes: &[u8]) -> Result<(), StorageError>;
+}
+
+// Identifies a record written by a `FlashStore`.
+const RECORD_MAGIC: [u8; 4] = *b"TSST";
+
+// Record header: magic, data length, and data checksum.
+const RECORD_HEADER_SIZE: usize = 12;
+
+/// A [`StateStore`] which saves a single record into a region of a flash
+/// memory.
+///
+/// Any flash driver implementing the `embedded-storage` [`Storage`] trait
+/// can be used, such as the `FlashStorage` of the `esp-storage` crate.
+///
+/// Each record is stored together with a checksum, so that a record
+/// corrupted by a reset during a write is discarded at boot.
+///
+/// ## Wear
+///
+/// The record is always rewritten in place, hence **no** wear leveling is
+/// performed. Flash sectors withstand a limited number of erase cycles,
+/// typically around 100 000, and each save erases the whole sector.
+/// Save only meaningful changes, such as a light being turned on,
+/// and never values which change periodically, such as sensor readings.
+///
+/// The region must be reserved to the store, for example through a
+/// dedicated data partition, and it must not overlap the firmware.
+pub struct FlashStore<F: Storage> {
+    flash: F,
+    offset: u32,
+    capacity: usize,
+}
+
+impl<F: Storage> FlashStore<F> {
+    /// Creates a [`FlashStore`] which uses `capacity` bytes of the flash,
+    /// starting from the given offset.
+    ///
+    /// The offset should be aligned to a flash sector, so that saves
+    /// do not erase unrelated data.
+    #[must_use]
+    pub const fn new(flash: F, offset: u32, capacity: usize) -> Self {
+        Self {
+            flash,
+            offset,
+            capacity,
+        }
+    }
+
+    // Returns the flash address of the given offset within the region.
+    fn address(&self, offset: usize) -> Result<u32, StorageError> {
+        u32::try_from(offset)
+            .ok()
+            .and_then(|offset| self.offset.checked_add(offset))
+            .ok_or(StorageError::Address)
+    }
+
+    fn read(&mut self, offset: usize, bytes: &mut [u8]) -> Result<(), StorageError> {
+        let address = self.address(offset)?;
+        self.flash
+            .read(address, bytes)
+            .map_err(|_| StorageError::Read)
+    }
+}
+
+impl<F: Storage> StateStore for FlashStore<F> {
+    fn load(&mut self) -> Result<Option<Vec<u8>>, StorageError> {
+        let mut header = [0; RECORD_HEADER_SIZE];
+        self.read(0, &mut header)?;
+
+        if header[0..4] != RECORD_MAGIC {
+            return Ok(None);
+        }
+
+        let length = usize::try_from(u32::from_le_bytes([
+            header[4], header[5], header[6], header[7],
+        ]))
+        .unwrap_or(usize::MAX);
+        if length > self.capacity.saturating_sub(RECORD_HEADER_SIZE) {
+            return Ok(None);
+        }
+
+        let mut data = vec![0; length];
+        self.read(RECORD_HEADER_SIZE, &mut data)?;
+
+        if fnv1a(&data) != u32::from_le_bytes([header[8], header[9], header[10], header[11]]) {
+            error!("Discarding a corrupted state record");
+            return Ok(None);
+        }
+
+        Ok(Some(data))
+    }
+
+    fn save(&mut self, bytes: &[u8]) -> Result<(), StorageError> {
+        if RECORD_HEADER_SIZE + bytes.len() > self.capacity {
+            return Err(StorageError::Capacity);
+        }
+        let length = u32::try_from(bytes.len()).map_err(|_| StorageError::Capacity)?;
+
+        let mut record = Vec::with_capacity(RECORD_HEADER_SIZE + bytes.len());
+        record.extend_from_slice(&RECORD_MAGIC);
+        record.extend_from_slice(&length.to_le_bytes());
+        record.extend_from_slice(&fnv1a(bytes).to_le_bytes());
+        record.extend_from_slice(bytes);
+
+        self.flash
+            .write(self.offset, &record)
+            .map_err(|_| StorageError::Write)
+    }
+}
+
+/// A part of the device [`State`] which is restored across reboots.
+///
+/// The value is loaded from a [`StateStore`] when the device boots, and
+/// saved into it each time it is changed through [`PersistentState::set`]
+/// or [`PersistentState::update`].
+///
+/// Copies of a [`PersistentState`] share the same value, so it can be
+/// stored into the device [`State`] and retrieved by each route handler.
+///
+/// Each change writes into the storage, hence the write-frequency caveats
+/// of the store, such as the ones of [`FlashStore`], apply.
+pub struct PersistentState<T: 'static, B: 'static> {
+    value: &'static Mutex<CriticalSectionRawMutex, T>,
+    store: &'static Mutex<CriticalSectionRawMutex, B>,
+}
+
+impl<T: 'static, B: 'static> Clone for PersistentState<T, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, B: 'static> Copy for PersistentState<T, B> {}
+
+impl<T: 'static, B: 'static> ValueFromRef for PersistentState<T, B> {
+    fn value_from_ref(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, B> PersistentState<T, B>
+where
+    T: Serialize + DeserializeOwned + PartialEq + Clone + 'static,
+    B: StateStore + 'static,
+{
+    /// Creates a [`PersistentState`], restoring its value from the given
+    /// [`StateStore`].
+    ///
+    /// When the store contains no valid value, the default value is used.
+    ///
+    /// This method should be called **once** at boot, since the value and
+    /// the store live for the whole firmware execution.
+    pub fn load(mut store: B, default: T) -> Self {
+        let value = restore_value(&mut store, default);
+
+        Self {
+            value: Box::leak(Box::new(Mutex::new(value))),
+            store: Box::leak(Box::new(Mutex::new(store))),
+        }
+    }
+
+    /// Returns the current value.
+    pub async fn get(&self) -> T {
+        self.value.lock().await.clone()
+    }
+
+    /// Sets a new value and saves it into the store.
+    ///
+    /// Nothing is written when the value does not change.
+    ///
+    /// # Errors
+    ///
+    /// - Failed to serialize the value
+    /// - Failed to write into the store
+    pub async fn set(&self, value: T) -> Result<(), StorageError> {
+        self.update(|current| *current = value).await
+    }
+
+    /// Updates the value through the given function and saves it into
+    /// the store.
+    ///
+    /// Nothing is written when the value does not change.
+    ///
+    /// # Errors
+    ///
+    /// - Failed to serialize the value
+    /// - Failed to write into the store
+    pub async fn update<F: FnOnce(&mut T)>(&self, f: F) -> Result<(), StorageError> {
+        let mut value = self.value.lock().await;
+
+        let previous = value.clone();
+        f(&mut *value);
+
+        save_if_changed(&mut *self.store.lock().await, &previous, &value)
+    }
+}
+
+// Restores a value from the given store, falling back to the default value
+// when the store contains no valid value.
+fn restore_value<T: DeserializeOwned>(store: &mut impl StateStore, default: T) -> T {
+    match store.load() {
+        Ok(Some(bytes)) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+            error!("Failed to restore the persistent state: {e}");
+            default
+        }),
+        Ok(None) => {
+            info!("No persistent state found, using the default value");
+            default
+        }
+        Err(e) => {
+            error!("Failed to load the persistent state: {e}");
+            default
+        }
+    }
+}
+
+// Saves a value into the given store, unless it is equal to the previous one.
+fn save_if_changed<T: Serialize + PartialEq>(
+    store: &mut impl StateStore,
+    previous: &T,
+    value: &T,
+) -> Result<(), StorageError> {
+    if value == previous {
+        return Ok(());
+    }
+
+    let bytes = serde_json::to_vec(value).map_err(|_| StorageError::Serialization)?;
+
+    store.save(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use embassy_futures::{block_on, join::join, yield_now};
+
+    use embedded_storage::{ReadStorage, Storage};
+
+    use super::{
+        FlashStore, RECORD_HEADER_SIZE, SharedState, StateStore, StorageError, ValueFromRef,
+        restore_value, save_if_changed,
+    };
+
+    // An in-memory store, counting the performed saves.
+    #[derive(Default)]
+    struct MemoryStore {
+        bytes: Option<Vec<u8>>,
+        saves: usize,
+    }
+
+    impl StateStore for MemoryStore {
+        fn load(&mut self) -> Result<Option<Vec<u8>>, StorageError> {
+            Ok(self.bytes.clone())
+        }
+
+        fn save(&mut self, bytes: &[u8]) -> Result<(), StorageError> {
+            self.bytes = Some(bytes.to_vec());
+            self.saves += 1;
+            Ok(())
+        }
+    }
+
+    // A flash memory backed by a RAM buffer.
+    struct RamFlash(Vec<u8>);
+
+    impl ReadStorage for RamFlash {
+        type Error = ();
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+            let offset = usize::try_from(offset).map_err(|_| ())?;
+            let stored = self.0.get(offset..offset + bytes.len()).ok_or(())?;
+            bytes.copy_from_slice(stored);
+            Ok(())
+        }
+
+        fn capacity(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    impl Storage for RamFlash {
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+            let offset = usize::try_from(offset).map_err(|_| ())?;
+            let stored = self.0.get_mut(offset..offset + bytes.len()).ok_or(())?;
+            stored.copy_from_slice(bytes);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_shared_state_copies() {
+        block_on(async {
+            let counter = SharedState::new(0u32);
+
+            // Each route invocation receives its own copy of the state.
+            for expected in 1..=2 {
+                let value = counter
+                    .value_from_ref()
+                    .update(|value| {
+                        *value += 1;
+                        *value
+                    })
+                    .await;
+                assert_eq!(value, expected);
+            }
+
+            assert_eq!(counter.get().await, 2);
+        });
+    }
+
+    #[test]
+    fn test_shared_state_locking() {
+        block_on(async {
+            let state = SharedState::new(0u32);
+            let guard = state.0.lock().await;
+
+            let (value, ()) = join(
+                state.update(|value| {
+                    *value += 1;
+                    *value
+                }),
+                async move {
+                    yield_now().await;
+                    // The update waits until the lock is released.
+                    assert_eq!(*guard, 0);
+                    drop(guard);
+                },
+            )
+            .await;
+
+            assert_eq!(value, 1);
+            assert_eq!(state.get().await, 1);
+        });
+    }
+
+    #[test]
+    fn test_load_default_value() {
+        // An empty store.
+        assert_eq!(restore_value(&mut MemoryStore::default(), 7u8), 7);
+
+        // A store containing an invalid value.
+        let mut store = MemoryStore {
+            bytes: Some(b"not json".to_vec()),
+            saves: 0,
+        };
+        assert_eq!(restore_value(&mut store, 7u8), 7);
+    }
+
+    #[test]
+    fn test_save_and_load_value() {
+        let mut store = MemoryStore::default();
+
+        save_if_changed(&mut store, &false, &true).unwrap();
+        assert_eq!(store.saves, 1);
+        assert!(restore_value(&mut store, false));
+
+        // An unchanged value is not written.
+        save_if_changed(&mut store, &true, &true).unwrap();
+        assert_eq!(store.saves, 1);
+    }
+
+    #[test]
+    fn test_flash_store_record() {
+        let mut store = FlashStore::new(RamFlash(vec![0xFF; 64]), 0, 64);
+
+        // An erased flash contains no record.
+        assert!(store.load().unwrap().is_none());
+
+        store.save(b"[1,2,3]").unwrap();
+        assert_eq!(store.load().unwrap(), Some(b"[1,2,3]".to_vec()));
+        assert_eq!(restore_value(&mut store, Vec::<u8>::new()), vec![1, 2, 3]);
+
+        // A record larger than the capacity is rejected.
+        assert_eq!(store.save(&[0; 64]), Err(StorageError::Capacity));
+    }
+
+    #[test]
+    fn test_flash_store_address_overflow() {
+        let store = FlashStore::new(RamFlash(vec![0xFF; 64]), u32::MAX - 4, 64);
+
+        assert_eq!(store.address(4), Ok(u32::MAX));
+        assert_eq!(store.address(5), Err(StorageError::Address));
+        assert_eq!(store.address(usize::MAX), Err(StorageError::Address));
+
+        // A read outside of the flash fails.
+        let mut store = FlashStore::new(RamFlash(vec![0xFF; 64]), 60, 64);
+        assert_eq!(store.load(), Err(StorageError::Read));
+    }
+
+    #[test]
+    fn test_flash_store_checksum_mismatch() {
+        let mut store = FlashStore::new(RamFlash(vec![0xFF; 64]), 0, 64);
+        store.save(b"[1,2,3]").unwrap();
+
+        // Corrupt a data byte, as an interrupted write would do.
+        store.flash.0[RECORD_HEADER_SIZE + 1] = b'9';
+
+        assert!(store.load().unwrap().is_none());
+        assert_eq!(restore_value(&mut store, vec![0u8]), vec![0]);
+    }
+}
@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+
+use embedded_io_async::Write;
+
+/// Writes the chunks returned by an asynchronous producer, which is called
+/// repeatedly until it returns `None`.
+///
+/// Each chunk is flushed as soon as it is written. Empty chunks are
+/// skipped, since an empty chunk terminates a chunked body.
+///
+/// # Errors
+///
+/// Returns the error of the first failed write.
+pub async fn write_chunks<W, F, Fut>(writer: &mut W, mut producer: F) -> Result<(), W::Error>
+where
+    W: Write,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Option<Vec<u8>>>,
+{
+    while let Some(chunk) = producer().await {
+        if chunk.is_empty() {
+            continue;
+        }
+
+        writer.write_all(&chunk).await?;
+        writer.flush().await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use alloc::vec::Vec;
+
+    use embassy_futures::block_on;
+
+    use embedded_io_async::{ErrorType, Write};
+
+    use super::write_chunks;
+
+    // A connection recording each flushed chunk.
+    #[derive(Default)]
+    struct MockConnection {
+        pending: Vec<u8>,
+        chunks: Vec<Vec<u8>>,
+    }
+
+    impl ErrorType for MockConnection {
+        type Error = Infallible;
+    }
+
+    impl Write for MockConnection {
+        async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.pending.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        async fn flush(&mut self) -> Result<(), Self::Error> {
+            self.chunks.push(core::mem::take(&mut self.pending));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_multi_chunk_stream() {
+        let mut frames = [b"frame-1".as_slice(), b"", b"frame-2", b"frame-3"].into_iter();
+        let mut connection = MockConnection::default();
+
+        block_on(write_chunks(&mut connection, || {
+            let frame = frames.next().map(<[u8]>::to_vec);
+            async move { frame }
+        }))
+        .unwrap();
+
+        // The chunks are read back in order, without the empty one.
+        assert_eq!(
+            connection.chunks,
+            [b"frame-1".as_slice(), b"frame-2", b"frame-3"]
+        );
+        assert!(connection.pending.is_empty());
+    }
+
+    #[test]
+    fn test_empty_stream() {
+        let mut connection = MockConnection::default();
+
+        block_on(write_chunks(&mut connection, || async { None })).unwrap();
+
+        assert!(connection.chunks.is_empty());
+    }
+}
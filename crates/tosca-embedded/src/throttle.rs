@@ -0,0 +1,97 @@
+use embassy_time::{Duration, Instant};
+
+use crate::hash::fnv1a;
+
+// Number of distinct replies remembered by the responses throttle.
+const THROTTLED_REPLIES: usize = 4;
+
+/// Remembers the recently multicast replies, so that an identical reply
+/// is not multicast again within the throttle window.
+///
+/// Replies are identified by a fingerprint of their bytes, since the same
+/// query always produces the same multicast reply.
+pub struct ResponseThrottle {
+    window: Duration,
+    replies: [Option<(u32, Instant)>; THROTTLED_REPLIES],
+}
+
+impl ResponseThrottle {
+    /// Creates a [`ResponseThrottle`] with the given window.
+    #[must_use]
+    pub const fn new(window: Duration) -> Self {
+        Self {
+            window,
+            replies: [None; THROTTLED_REPLIES],
+        }
+    }
+
+    /// Returns whether the reply can be sent at the given time, recording
+    /// it when it can.
+    pub fn allow(&mut self, reply: &[u8], now: Instant) -> bool {
+        let fingerprint = fnv1a(reply);
+
+        let is_recent = |sent_at: Instant| {
+            now.checked_duration_since(sent_at)
+                .is_some_and(|elapsed| elapsed < self.window)
+        };
+
+        if self
+            .replies
+            .iter()
+            .flatten()
+            .any(|(sent, sent_at)| *sent == fingerprint && is_recent(*sent_at))
+        {
+            return false;
+        }
+
+        // Replace a free slot, otherwise the oldest reply.
+        if let Some(slot) = self
+            .replies
+            .iter_mut()
+            .min_by_key(|slot| slot.as_ref().map(|(_, sent_at)| *sent_at))
+        {
+            *slot = Some((fingerprint, now));
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embassy_time::{Duration, Instant};
+
+    use super::{ResponseThrottle, THROTTLED_REPLIES};
+
+    #[test]
+    fn test_burst_of_queries() {
+        let mut throttle = ResponseThrottle::new(Duration::from_millis(1000));
+
+        // Only the first reply of a burst of identical queries is sent.
+        assert!(throttle.allow(b"reply", Instant::from_millis(0)));
+        for ms in 1..100 {
+            assert!(!throttle.allow(b"reply", Instant::from_millis(ms)));
+        }
+
+        // A different reply is not throttled.
+        assert!(throttle.allow(b"other reply", Instant::from_millis(100)));
+
+        // Once the window has elapsed, the reply is sent again.
+        assert!(throttle.allow(b"reply", Instant::from_millis(1000)));
+        assert!(!throttle.allow(b"reply", Instant::from_millis(1500)));
+    }
+
+    #[test]
+    fn test_oldest_reply_replaced() {
+        let mut throttle = ResponseThrottle::new(Duration::from_millis(1000));
+
+        // Fill all slots, then record one more reply, replacing the oldest.
+        for reply in (0u8..).take(THROTTLED_REPLIES + 1) {
+            assert!(throttle.allow(&[reply], Instant::from_millis(u64::from(reply))));
+        }
+
+        // The oldest reply has been forgotten, the others are still throttled.
+        assert!(throttle.allow(&[0], Instant::from_millis(10)));
+        assert!(!throttle.allow(&[2], Instant::from_millis(10)));
+    }
+}
@@ -0,0 +1,143 @@
+use alloc::vec::Vec;
+
+use tosca::route::RouteConfigs;
+
+/// The validators of the route parameters.
+///
+/// Each validator is associated with the index of its route, and runs
+/// after the parameters type checks, but before the route handler.
+pub struct Validators<V>(Vec<(usize, V)>);
+
+impl<V> Default for Validators<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> Validators<V> {
+    /// Creates empty [`Validators`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Registers a validator for the route with the given path, replacing
+    /// any previous validator of the same route.
+    ///
+    /// Returns `false` and discards the validator when no route has the
+    /// given path.
+    pub fn register(
+        &mut self,
+        route_configs: &RouteConfigs,
+        route_path: &str,
+        validator: V,
+    ) -> bool {
+        let Some(index) = route_configs
+            .iter()
+            .position(|route_config| route_config.data.path == route_path)
+        else {
+            return false;
+        };
+
+        self.0.retain(|(route_index, _)| *route_index != index);
+        self.0.push((index, validator));
+        true
+    }
+
+    /// Returns the validator of the route with the given index, if any.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&V> {
+        self.0
+            .iter()
+            .find(|(route_index, _)| *route_index == index)
+            .map(|(_, validator)| validator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+
+    use tosca::parameters::{ParameterValue, Parameters, ParametersPayloads};
+    use tosca::route::{Route, RouteConfigs};
+
+    use crate::parameters::parse_body_parameters;
+
+    use super::Validators;
+
+    struct LightState {
+        max_brightness: u8,
+    }
+
+    type Validator = Box<dyn Fn(&LightState, &ParametersPayloads<'_>) -> Result<(), &'static str>>;
+
+    fn route_configs() -> RouteConfigs {
+        let mut route_configs = RouteConfigs::new();
+        route_configs.add(
+            Route::put("Brightness", "/brightness")
+                .with_parameters(Parameters::new().u8_with_limits("brightness", 0, 0, 100))
+                .serialize_data(),
+        );
+        route_configs.add(Route::put("On", "/on").serialize_data());
+        route_configs
+    }
+
+    // Validates the brightness against the maximum stored in the state.
+    fn brightness_validator() -> Validator {
+        Box::new(
+            |state, payloads| match payloads.get("brightness").map(|payload| &payload.value) {
+                Some(ParameterValue::U8(brightness)) if *brightness > state.max_brightness => {
+                    Err("Brightness over the maximum brightness")
+                }
+                _ => Ok(()),
+            },
+        )
+    }
+
+    fn validate(validators: &Validators<Validator>, body: &[u8]) -> Result<(), &'static str> {
+        let route_configs = route_configs();
+        let route_config = route_configs.iter().next().unwrap();
+        // The type and range checks run first.
+        let payloads = parse_body_parameters(route_config, body, true).unwrap();
+
+        let state = LightState { max_brightness: 50 };
+        validators
+            .get(0)
+            .map_or(Ok(()), |validator| validator(&state, &payloads))
+    }
+
+    #[test]
+    fn test_state_relative_bound() {
+        let mut validators = Validators::new();
+        assert!(validators.register(&route_configs(), "/brightness", brightness_validator()));
+
+        // Within the range of the parameter and below the state bound.
+        assert_eq!(validate(&validators, br#"{"brightness":30}"#), Ok(()));
+        assert_eq!(validate(&validators, br#"{"brightness":50}"#), Ok(()));
+
+        // Within the range of the parameter, but over the state bound.
+        assert_eq!(
+            validate(&validators, br#"{"brightness":80}"#),
+            Err("Brightness over the maximum brightness")
+        );
+    }
+
+    #[test]
+    fn test_register_validators() {
+        let route_configs = route_configs();
+        let mut validators: Validators<Validator> = Validators::new();
+
+        // A validator of an unknown route is discarded.
+        assert!(!validators.register(&route_configs, "/unknown", Box::new(|_, _| Ok(()))));
+        assert!(validators.get(0).is_none());
+
+        // A route without validator is not checked.
+        assert_eq!(validate(&validators, br#"{"brightness":80}"#), Ok(()));
+
+        // A validator replaces the previous one of the same route.
+        assert!(validators.register(&route_configs, "/brightness", Box::new(|_, _| Err("first"))));
+        assert!(validators.register(&route_configs, "/brightness", brightness_validator()));
+        assert_eq!(validate(&validators, br#"{"brightness":30}"#), Ok(()));
+        assert!(validators.get(1).is_none());
+    }
+}
@@ -290,7 +290,9 @@ async fn main(spawner: Spawner) {
             turn_light_off,
         )
         .stateless_ok_route(
-            Route::get("Toggle", "/toggle").description("Toggle the light on and off."),
+            Route::get("Toggle", "/toggle")
+                .description("Toggle the light on and off.")
+                .idempotent(false),
             toggle,
         )
         .build();
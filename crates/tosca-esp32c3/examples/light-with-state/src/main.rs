@@ -38,7 +38,7 @@ use tosca_esp32c3::{
     parameters::ParametersPayloads,
     response::{ErrorResponse, InfoResponse, OkResponse, SerialResponse},
     server::Server,
-    state::{State, ValueFromRef},
+    state::{SharedState, State},
     wifi::Wifi,
 };
 
@@ -61,15 +61,6 @@ static TOGGLE_CONTROLLER: AtomicBool = AtomicBool::new(false);
 // Atomic value storing the toggle interval in seconds.
 static TOGGLE_SECONDS: AtomicU32 = AtomicU32::new(1);
 
-macro_rules! mk_static {
-    ($t:ty,$val:expr) => {{
-        static STATIC_CELL: static_cell::StaticCell<$t> = static_cell::StaticCell::new();
-        #[deny(unused_attributes)]
-        let x = STATIC_CELL.uninit().write($val);
-        x
-    }};
-}
-
 // This creates a default app-descriptor required by the esp-idf bootloader.
 // For more information see: <https://docs.espressif.com/projects/esp-idf/en/stable/esp32/api-reference/system/app_image_format.html#application-description>
 esp_bootloader_esp_idf::esp_app_desc!();
@@ -208,29 +199,24 @@ async fn turn_light_off() -> Result<SerialResponse, ErrorResponse> {
     .await
 }
 
-struct RequestCounter(&'static AtomicU32);
-
-impl ValueFromRef for RequestCounter {
-    fn value_from_ref(&self) -> Self {
-        Self(self.0)
-    }
-}
-
 async fn stateful_toggle(
-    State(RequestCounter(request_counter)): State<RequestCounter>,
+    State(request_counter): State<SharedState<u32>>,
     mut parameters: ParametersPayloads,
 ) -> Result<OkResponse, ErrorResponse> {
-    // Obtain the current request counter value.
-    let old_value = request_counter.load(Ordering::Relaxed);
-    // Increment the request counter value.
-    request_counter.store(old_value + 1, Ordering::Relaxed);
+    // Increment the request counter, which is shared by all requests.
+    let request_number = request_counter
+        .update(|counter| {
+            *counter += 1;
+            *counter
+        })
+        .await;
 
-    log::info!("Request number: {request_counter:?}");
+    log::info!("Request number: {request_number}");
 
     let test_value = parameters.bool("test-value")?.value;
 
-    let seconds = parameters.u32("seconds")?;
-    let seconds = seconds.value.min(seconds.max);
+    // The server clamps the value to the bounds of the range.
+    let seconds = parameters.u32("seconds")?.value;
 
     info!("Test value: {test_value}");
     info!("Seconds: {seconds}");
@@ -304,7 +290,7 @@ async fn main(spawner: Spawner) {
         .spawn(change_led(led))
         .expect("Impossible to spawn the task to change the led");
 
-    let request_counter = RequestCounter(mk_static!(AtomicU32, AtomicU32::new(0)));
+    let request_counter = SharedState::new(0);
     let device = Light::with_state(&interfaces.ap, request_counter)
         .turn_light_on_stateless_serial(
             LightOnRoute::put("On").description("Turn light on."),
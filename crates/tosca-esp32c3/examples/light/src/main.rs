@@ -221,8 +221,8 @@ async fn toggle_with_parameters(
     mut parameters: ParametersPayloads,
 ) -> Result<OkResponse, ErrorResponse> {
     let test_value = parameters.bool("test-value")?.value;
-    let seconds = parameters.u32("seconds")?;
-    let seconds = seconds.value.min(seconds.max);
+    // The server clamps the value to the bounds of the range.
+    let seconds = parameters.u32("seconds")?.value;
 
     info!("Test value: {test_value}");
     info!("Seconds: {seconds}");
@@ -0,0 +1,161 @@
+use core::net::SocketAddr;
+
+use edge_nal::{Close, Readable, TcpAccept, TcpShutdown, TcpSplit};
+
+use embedded_io_async::{ErrorType, Read, Write};
+
+use tosca_embedded::budget::ConnectionBudget;
+
+// An acceptor limiting the number of bytes which can be read on each
+// accepted connection, across all of its requests, as well as the number
+// of requests per second sent over it.
+pub(crate) struct ByteBudget<A> {
+    acceptor: A,
+    budget: Option<usize>,
+    max_requests_per_second: Option<u32>,
+}
+
+impl<A> ByteBudget<A> {
+    pub(crate) const fn new(
+        acceptor: A,
+        budget: Option<usize>,
+        max_requests_per_second: Option<u32>,
+    ) -> Self {
+        Self {
+            acceptor,
+            budget,
+            max_requests_per_second,
+        }
+    }
+}
+
+impl<A: TcpAccept> TcpAccept for ByteBudget<A> {
+    type Error = A::Error;
+    type Socket<'a>
+        = BudgetSocket<A::Socket<'a>>
+    where
+        Self: 'a;
+
+    async fn accept(&self) -> Result<(SocketAddr, Self::Socket<'_>), Self::Error> {
+        let (address, io) = self.acceptor.accept().await?;
+        Ok((
+            address,
+            BudgetSocket {
+                io,
+                state: ConnectionBudget::new(self.budget, self.max_requests_per_second),
+            },
+        ))
+    }
+}
+
+pub(crate) struct BudgetSocket<T> {
+    io: T,
+    state: ConnectionBudget,
+}
+
+impl<T: ErrorType> ErrorType for BudgetSocket<T> {
+    type Error = T::Error;
+}
+
+impl<T: Read + Write> Read for BudgetSocket<T> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        // Once the budget is spent, answer the client, then report the
+        // connection as closed.
+        self.state.read_or_reject(&mut self.io, buf).await
+    }
+}
+
+impl<T: Write> Write for BudgetSocket<T> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.state.write(&mut self.io, buf).await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.io.flush().await
+    }
+}
+
+impl<T: Readable> Readable for BudgetSocket<T> {
+    async fn readable(&mut self) -> Result<(), Self::Error> {
+        // A spent connection is readable, so that the closing read is
+        // performed at once.
+        if self.state.is_spent() {
+            return Ok(());
+        }
+        self.io.readable().await
+    }
+}
+
+impl<T: TcpShutdown> TcpShutdown for BudgetSocket<T> {
+    async fn close(&mut self, what: Close) -> Result<(), Self::Error> {
+        self.io.close(what).await
+    }
+
+    async fn abort(&mut self) -> Result<(), Self::Error> {
+        self.io.abort().await
+    }
+}
+
+impl<T: TcpSplit> TcpSplit for BudgetSocket<T> {
+    type Read<'a>
+        = BudgetHalf<'a, T::Read<'a>>
+    where
+        Self: 'a;
+
+    type Write<'a>
+        = BudgetHalf<'a, T::Write<'a>>
+    where
+        Self: 'a;
+
+    fn split(&mut self) -> (Self::Read<'_>, Self::Write<'_>) {
+        let (read, write) = self.io.split();
+        (
+            BudgetHalf {
+                io: read,
+                state: &self.state,
+            },
+            BudgetHalf {
+                io: write,
+                state: &self.state,
+            },
+        )
+    }
+}
+
+// A half of a split `BudgetSocket`.
+//
+// A read half cannot answer the client, hence the rejection response is
+// written by the write half.
+pub(crate) struct BudgetHalf<'a, T> {
+    io: T,
+    state: &'a ConnectionBudget,
+}
+
+impl<T: ErrorType> ErrorType for BudgetHalf<'_, T> {
+    type Error = T::Error;
+}
+
+impl<T: Read> Read for BudgetHalf<'_, T> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.state.read(&mut self.io, buf).await?.unwrap_or(0))
+    }
+}
+
+impl<T: Readable> Readable for BudgetHalf<'_, T> {
+    async fn readable(&mut self) -> Result<(), Self::Error> {
+        if self.state.is_spent() {
+            return Ok(());
+        }
+        self.io.readable().await
+    }
+}
+
+impl<T: Write> Write for BudgetHalf<'_, T> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.state.write(&mut self.io, buf).await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.io.flush().await
+    }
+}
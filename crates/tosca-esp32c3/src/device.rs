@@ -1,3 +1,4 @@
+use alloc::string::String;
 use alloc::vec::Vec;
 
 use tosca::device::DeviceDescription;
@@ -5,7 +6,7 @@ use tosca::events::EventsDescription;
 use tosca::route::RouteConfigs;
 
 use crate::response::Response;
-use crate::server::{FuncIndex, Functions};
+use crate::server::{FuncIndex, Functions, Validators};
 use crate::state::{State, ValueFromRef};
 
 /// A generic `tosca` device.
@@ -21,6 +22,7 @@ where
     pub(crate) main_route: &'static str,
     pub(crate) routes_functions: Functions<S>,
     pub(crate) index_array: Vec<FuncIndex>,
+    pub(crate) validators: Validators<S>,
 }
 
 impl<S> Device<S>
@@ -35,6 +37,7 @@ where
         main_route: &'static str,
         routes_functions: Functions<S>,
         index_array: Vec<FuncIndex>,
+        validators: Validators<S>,
     ) -> Self {
         Self {
             wifi_mac,
@@ -43,6 +46,7 @@ where
             main_route,
             routes_functions,
             index_array,
+            validators,
         }
     }
 
@@ -57,10 +61,12 @@ where
         self.description.data.wifi_mac = Some(self.wifi_mac);
         InternalDevice {
             state: self.state,
+            kind: self.description.data.kind.name().into(),
             main_route: self.main_route,
             main_route_response: Response::json(&self.description),
             routes_functions: self.routes_functions,
             index_array: self.index_array,
+            validators: self.validators,
             route_configs: self.description.route_configs,
         }
     }
@@ -71,9 +77,11 @@ where
     S: ValueFromRef + Send + Sync + 'static,
 {
     pub(crate) state: State<S>,
+    pub(crate) kind: String,
     pub(crate) main_route: &'static str,
     pub(crate) main_route_response: Response,
     pub(crate) routes_functions: Functions<S>,
     pub(crate) index_array: Vec<FuncIndex>,
+    pub(crate) validators: Validators<S>,
     pub(crate) route_configs: RouteConfigs,
 }
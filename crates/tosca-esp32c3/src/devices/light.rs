@@ -1,4 +1,3 @@
-use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 
@@ -11,11 +10,14 @@ use esp_radio::wifi::WifiDevice;
 
 use log::error;
 
+use tosca_embedded::devices::add_route;
+
 use crate::device::Device;
 use crate::parameters::ParametersPayloads;
-use crate::response::{ErrorResponse, InfoResponse, OkResponse, SerialResponse};
+use crate::response::{ErrorResponse, InfoResponse, OkResponse, SerialResponse, StreamResponse};
 use crate::server::{
     FuncIndex, FuncType, Functions, InfoFn, InfoStateFn, OkFn, OkStateFn, SerialFn, SerialStateFn,
+    StreamFn, StreamStateFn, ValidatorFn, Validators,
 };
 use crate::state::{State, ValueFromRef};
 
@@ -205,6 +207,7 @@ where
     routes_functions: Functions<S>,
     device_data: DeviceDescription,
     index_array: Vec<FuncIndex>,
+    validators: Validators<S>,
 }
 
 impl<S> CompleteLight<S>
@@ -212,11 +215,17 @@ where
     S: ValueFromRef + Send + Sync + 'static,
 {
     /// Sets the main route.
+    ///
+    /// An invalid main route, such as one without a leading `/`,
+    /// is discarded and the current main route is kept.
     #[must_use]
     #[inline]
     pub fn main_route(mut self, main_route: &'static str) -> Self {
+        if let Err(e) = self.device_data.with_validated_main_route(main_route) {
+            error!("Invalid main route `{main_route}`: {e}");
+            return self;
+        }
         self.main_route = main_route;
-        self.device_data.main_route = Cow::Borrowed(main_route);
         self
     }
 
@@ -338,6 +347,70 @@ where
         })
     }
 
+    /// Adds a [`Route`] with a stateless handler that returns a
+    /// [`StreamResponse`] on success and an [`ErrorResponse`] on failure.
+    #[must_use]
+    pub fn stateless_stream_route<F, Fut>(self, route: Route, func: F) -> Self
+    where
+        F: Fn(ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<StreamResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.route_func_manager(route, ResponseKind::Stream, move |mut func_manager| {
+            let func: StreamFn =
+                Box::new(move |parameters_values| Box::pin(func(parameters_values)));
+            func_manager.routes_functions.6.push(func);
+            func_manager.index_array.push(FuncIndex::new(
+                FuncType::StreamStateless,
+                func_manager.routes_functions.6.len() - 1,
+            ));
+            func_manager
+        })
+    }
+
+    /// Adds a [`Route`] with a stateful handler that returns a
+    /// [`StreamResponse`] on success and an [`ErrorResponse`] on failure.
+    #[must_use]
+    pub fn stateful_stream_route<F, Fut>(self, route: Route, func: F) -> Self
+    where
+        F: Fn(State<S>, ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<StreamResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.route_func_manager(route, ResponseKind::Stream, move |mut func_manager| {
+            let func: StreamStateFn<S> =
+                Box::new(move |state, parameters_values| Box::pin(func(state, parameters_values)));
+            func_manager.routes_functions.7.push(func);
+            func_manager.index_array.push(FuncIndex::new(
+                FuncType::StreamStateful,
+                func_manager.routes_functions.7.len() - 1,
+            ));
+            func_manager
+        })
+    }
+
+    /// Adds a validator for the parameters of the route with the given path.
+    ///
+    /// The validator consults the device [`State`] and runs after the
+    /// parameters type checks, but before the route handler.
+    /// When it fails, its [`ErrorResponse`] is returned in place of
+    /// the handler response.
+    ///
+    /// The route must be added before its validator, otherwise the validator
+    /// is discarded.
+    #[must_use]
+    pub fn parameters_validator<F>(mut self, route_path: &str, func: F) -> Self
+    where
+        F: Fn(&S, &ParametersPayloads) -> Result<(), ErrorResponse> + Send + Sync + 'static,
+    {
+        let func: ValidatorFn<S> = Box::new(func);
+        if !self
+            .validators
+            .register(&self.device_data.route_configs, route_path, func)
+        {
+            error!("The route with prefix `{route_path}` does not exist!");
+        }
+        self
+    }
+
     /// Builds a [`Device`].
     ///
     /// **This method consumes the light.**
@@ -351,6 +424,7 @@ where
             self.main_route,
             self.routes_functions,
             self.index_array,
+            self.validators,
         )
     }
 
@@ -363,21 +437,10 @@ where
     where
         F: FnOnce(Self) -> Self,
     {
-        let route_config = route
-            .remove_prohibited_hazards(ALLOWED_HAZARDS)
-            .serialize_data()
-            .change_response_kind(response_kind);
-
-        if self.device_data.route_configs.contains(&route_config) {
-            error!(
-                "The route with prefix `{}` already exists!",
-                route_config.data.path
-            );
+        if !add_route(&mut self.device_data, route, ALLOWED_HAZARDS, response_kind) {
             return self;
         }
 
-        self.device_data.route_configs.add(route_config);
-
         add_async_function(self)
     }
 
@@ -404,9 +467,12 @@ where
                 Vec::new(),
                 Vec::new(),
                 Vec::new(),
+                Vec::new(),
+                Vec::new(),
             ),
             device_data,
             index_array: Vec::new(),
+            validators: Validators::new(),
         }
     }
 }
@@ -1,2 +1,6 @@
 /// A `light` device.
 pub mod light;
+/// A `sensor` device.
+pub mod sensor;
+/// A `thermostat` device.
+pub mod thermostat;
@@ -0,0 +1,412 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use tosca::device::DeviceDescription;
+use tosca::response::ResponseKind;
+use tosca::route::{Route, ThermostatSetRoute};
+
+use esp_radio::wifi::WifiDevice;
+
+use log::error;
+
+use tosca_embedded::devices::add_route;
+use tosca_embedded::devices::thermostat::{
+    ALLOWED_HAZARDS, MAIN_ROUTE, description, set_temperature_route,
+};
+
+use crate::device::Device;
+use crate::parameters::ParametersPayloads;
+use crate::response::{ErrorResponse, InfoResponse, OkResponse, SerialResponse, StreamResponse};
+use crate::server::{
+    FuncIndex, FuncType, Functions, InfoFn, InfoStateFn, OkFn, OkStateFn, SerialFn, SerialStateFn,
+    StreamFn, StreamStateFn, ValidatorFn, Validators,
+};
+use crate::state::{State, ValueFromRef};
+
+/// A `thermostat` device.
+///
+/// Its methods guide in the definition of a correct thermostat.
+///
+/// The initial placeholder for constructing a [`CompleteThermostat`].
+pub struct Thermostat<S = ()>(CompleteThermostat<S>)
+where
+    S: ValueFromRef + Send + Sync + 'static;
+
+impl Thermostat<()> {
+    /// Creates a [`Thermostat`] without a [`State`].
+    #[must_use]
+    #[inline]
+    pub fn new(wifi_interface: &WifiDevice<'_>) -> Self {
+        Self(CompleteThermostat::with_state(wifi_interface, ()))
+    }
+}
+
+impl<S> Thermostat<S>
+where
+    S: ValueFromRef + Send + Sync + 'static,
+{
+    /// Creates a [`Thermostat`] with a [`State`].
+    #[inline]
+    pub fn with_state(wifi_interface: &WifiDevice<'_>, state: S) -> Self {
+        Self(CompleteThermostat::with_state(wifi_interface, state))
+    }
+
+    /// Sets the target temperature using a stateless handler, returning
+    /// an [`OkResponse`] on success and an [`ErrorResponse`] on failure.
+    ///
+    /// The `temperature` parameter is added to the route, constrained by
+    /// the given `(minimum, maximum, step)` range.
+    #[must_use]
+    #[inline]
+    pub fn set_temperature_stateless_ok<F, Fut>(
+        self,
+        route: ThermostatSetRoute,
+        range: (f64, f64, f64),
+        func: F,
+    ) -> CompleteThermostat<S>
+    where
+        F: Fn(ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<OkResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.0
+            .stateless_ok_route(set_temperature_route(route, range), func)
+    }
+
+    /// Sets the target temperature using a stateful handler, returning
+    /// an [`OkResponse`] on success and an [`ErrorResponse`] on failure.
+    ///
+    /// The `temperature` parameter is added to the route, constrained by
+    /// the given `(minimum, maximum, step)` range.
+    #[must_use]
+    #[inline]
+    pub fn set_temperature_stateful_ok<F, Fut>(
+        self,
+        route: ThermostatSetRoute,
+        range: (f64, f64, f64),
+        func: F,
+    ) -> CompleteThermostat<S>
+    where
+        F: Fn(State<S>, ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<OkResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.0
+            .stateful_ok_route(set_temperature_route(route, range), func)
+    }
+
+    /// Sets the target temperature using a stateless handler, returning
+    /// a [`SerialResponse`] on success and an [`ErrorResponse`] on failure.
+    ///
+    /// The `temperature` parameter is added to the route, constrained by
+    /// the given `(minimum, maximum, step)` range.
+    #[must_use]
+    #[inline]
+    pub fn set_temperature_stateless_serial<F, Fut>(
+        self,
+        route: ThermostatSetRoute,
+        range: (f64, f64, f64),
+        func: F,
+    ) -> CompleteThermostat<S>
+    where
+        F: Fn(ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SerialResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.0
+            .stateless_serial_route(set_temperature_route(route, range), func)
+    }
+
+    /// Sets the target temperature using a stateful handler, returning
+    /// a [`SerialResponse`] on success and an [`ErrorResponse`] on failure.
+    ///
+    /// The `temperature` parameter is added to the route, constrained by
+    /// the given `(minimum, maximum, step)` range.
+    #[must_use]
+    #[inline]
+    pub fn set_temperature_stateful_serial<F, Fut>(
+        self,
+        route: ThermostatSetRoute,
+        range: (f64, f64, f64),
+        func: F,
+    ) -> CompleteThermostat<S>
+    where
+        F: Fn(State<S>, ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SerialResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.0
+            .stateful_serial_route(set_temperature_route(route, range), func)
+    }
+}
+
+/// A `thermostat` device with a method to set the target temperature.
+pub struct CompleteThermostat<S = ()>
+where
+    S: ValueFromRef + Send + Sync + 'static,
+{
+    wifi_mac: [u8; 6],
+    main_route: &'static str,
+    state: State<S>,
+    routes_functions: Functions<S>,
+    device_data: DeviceDescription,
+    index_array: Vec<FuncIndex>,
+    validators: Validators<S>,
+}
+
+impl<S> CompleteThermostat<S>
+where
+    S: ValueFromRef + Send + Sync + 'static,
+{
+    /// Sets the main route.
+    ///
+    /// An invalid main route, such as one without a leading `/`,
+    /// is discarded and the current main route is kept.
+    #[must_use]
+    #[inline]
+    pub fn main_route(mut self, main_route: &'static str) -> Self {
+        if let Err(e) = self.device_data.with_validated_main_route(main_route) {
+            error!("Invalid main route `{main_route}`: {e}");
+            return self;
+        }
+        self.main_route = main_route;
+        self
+    }
+
+    /// Adds a [`Route`] with a stateless handler that returns an [`OkResponse`]
+    /// on success and an [`ErrorResponse`] on failure.
+    #[must_use]
+    pub fn stateless_ok_route<F, Fut>(self, route: Route, func: F) -> Self
+    where
+        F: Fn(ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<OkResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.route_func_manager(route, ResponseKind::Ok, move |mut func_manager| {
+            let func: OkFn = Box::new(move |parameters_values| Box::pin(func(parameters_values)));
+            func_manager.routes_functions.0.push(func);
+            func_manager.index_array.push(FuncIndex::new(
+                FuncType::OkStateless,
+                func_manager.routes_functions.0.len() - 1,
+            ));
+            func_manager
+        })
+    }
+
+    /// Adds a [`Route`] with a stateful handler that returns an [`OkResponse`]
+    /// on success and an [`ErrorResponse`] on failure.
+    #[must_use]
+    pub fn stateful_ok_route<F, Fut>(self, route: Route, func: F) -> Self
+    where
+        F: Fn(State<S>, ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<OkResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.route_func_manager(route, ResponseKind::Ok, move |mut func_manager| {
+            let func: OkStateFn<S> =
+                Box::new(move |state, parameters_values| Box::pin(func(state, parameters_values)));
+            func_manager.routes_functions.1.push(func);
+            func_manager.index_array.push(FuncIndex::new(
+                FuncType::OkStateful,
+                func_manager.routes_functions.1.len() - 1,
+            ));
+            func_manager
+        })
+    }
+
+    /// Adds a [`Route`] with a stateless handler that returns a
+    /// [`SerialResponse`] on success and an [`ErrorResponse`] on failure.
+    #[must_use]
+    pub fn stateless_serial_route<F, Fut>(self, route: Route, func: F) -> Self
+    where
+        F: Fn(ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SerialResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.route_func_manager(route, ResponseKind::Serial, move |mut func_manager| {
+            let func: SerialFn =
+                Box::new(move |parameters_values| Box::pin(func(parameters_values)));
+            func_manager.routes_functions.2.push(func);
+            func_manager.index_array.push(FuncIndex::new(
+                FuncType::SerialStateless,
+                func_manager.routes_functions.2.len() - 1,
+            ));
+            func_manager
+        })
+    }
+
+    /// Adds a [`Route`] with a stateful handler that returns a
+    /// [`SerialResponse`] on success and an [`ErrorResponse`] on failure.
+    #[must_use]
+    pub fn stateful_serial_route<F, Fut>(self, route: Route, func: F) -> Self
+    where
+        F: Fn(State<S>, ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SerialResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.route_func_manager(route, ResponseKind::Serial, move |mut func_manager| {
+            let func: SerialStateFn<S> =
+                Box::new(move |state, parameters_values| Box::pin(func(state, parameters_values)));
+            func_manager.routes_functions.3.push(func);
+            func_manager.index_array.push(FuncIndex::new(
+                FuncType::SerialStateful,
+                func_manager.routes_functions.3.len() - 1,
+            ));
+            func_manager
+        })
+    }
+
+    /// Adds a [`Route`] with a stateless handler that returns an
+    /// [`InfoResponse`] on success and an [`ErrorResponse`] on failure.
+    #[must_use]
+    pub fn stateless_info_route<F, Fut>(self, route: Route, func: F) -> Self
+    where
+        F: Fn(ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<InfoResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.route_func_manager(route, ResponseKind::Info, move |mut func_manager| {
+            let func: InfoFn = Box::new(move |parameters_values| Box::pin(func(parameters_values)));
+            func_manager.routes_functions.4.push(func);
+            func_manager.index_array.push(FuncIndex::new(
+                FuncType::InfoStateless,
+                func_manager.routes_functions.4.len() - 1,
+            ));
+            func_manager
+        })
+    }
+
+    /// Adds a [`Route`] with a stateful handler that returns an
+    /// [`InfoResponse`] on success and an [`ErrorResponse`] on failure.
+    #[must_use]
+    pub fn stateful_info_route<F, Fut>(self, route: Route, func: F) -> Self
+    where
+        F: Fn(State<S>, ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<InfoResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.route_func_manager(route, ResponseKind::Info, move |mut func_manager| {
+            let func: InfoStateFn<S> =
+                Box::new(move |state, parameters_values| Box::pin(func(state, parameters_values)));
+            func_manager.routes_functions.5.push(func);
+            func_manager.index_array.push(FuncIndex::new(
+                FuncType::InfoStateful,
+                func_manager.routes_functions.5.len() - 1,
+            ));
+            func_manager
+        })
+    }
+
+    /// Adds a [`Route`] with a stateless handler that returns a
+    /// [`StreamResponse`] on success and an [`ErrorResponse`] on failure.
+    #[must_use]
+    pub fn stateless_stream_route<F, Fut>(self, route: Route, func: F) -> Self
+    where
+        F: Fn(ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<StreamResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.route_func_manager(route, ResponseKind::Stream, move |mut func_manager| {
+            let func: StreamFn =
+                Box::new(move |parameters_values| Box::pin(func(parameters_values)));
+            func_manager.routes_functions.6.push(func);
+            func_manager.index_array.push(FuncIndex::new(
+                FuncType::StreamStateless,
+                func_manager.routes_functions.6.len() - 1,
+            ));
+            func_manager
+        })
+    }
+
+    /// Adds a [`Route`] with a stateful handler that returns a
+    /// [`StreamResponse`] on success and an [`ErrorResponse`] on failure.
+    #[must_use]
+    pub fn stateful_stream_route<F, Fut>(self, route: Route, func: F) -> Self
+    where
+        F: Fn(State<S>, ParametersPayloads) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<StreamResponse, ErrorResponse>> + Send + Sync + 'static,
+    {
+        self.route_func_manager(route, ResponseKind::Stream, move |mut func_manager| {
+            let func: StreamStateFn<S> =
+                Box::new(move |state, parameters_values| Box::pin(func(state, parameters_values)));
+            func_manager.routes_functions.7.push(func);
+            func_manager.index_array.push(FuncIndex::new(
+                FuncType::StreamStateful,
+                func_manager.routes_functions.7.len() - 1,
+            ));
+            func_manager
+        })
+    }
+
+    /// Adds a validator for the parameters of the route with the given path.
+    ///
+    /// The validator consults the device [`State`] and runs after the
+    /// parameters type checks, but before the route handler.
+    /// When it fails, its [`ErrorResponse`] is returned in place of
+    /// the handler response.
+    ///
+    /// The route must be added before its validator, otherwise the validator
+    /// is discarded.
+    #[must_use]
+    pub fn parameters_validator<F>(mut self, route_path: &str, func: F) -> Self
+    where
+        F: Fn(&S, &ParametersPayloads) -> Result<(), ErrorResponse> + Send + Sync + 'static,
+    {
+        let func: ValidatorFn<S> = Box::new(func);
+        if !self
+            .validators
+            .register(&self.device_data.route_configs, route_path, func)
+        {
+            error!("The route with prefix `{route_path}` does not exist!");
+        }
+        self
+    }
+
+    /// Builds a [`Device`].
+    ///
+    /// **This method consumes the thermostat.**
+    #[must_use]
+    #[inline]
+    pub fn build(self) -> Device<S> {
+        Device::new(
+            self.wifi_mac,
+            self.state,
+            self.device_data,
+            self.main_route,
+            self.routes_functions,
+            self.index_array,
+            self.validators,
+        )
+    }
+
+    fn route_func_manager<F>(
+        mut self,
+        route: Route,
+        response_kind: ResponseKind,
+        add_async_function: F,
+    ) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        if !add_route(&mut self.device_data, route, ALLOWED_HAZARDS, response_kind) {
+            return self;
+        }
+
+        add_async_function(self)
+    }
+
+    #[inline]
+    fn with_state(wifi_interface: &WifiDevice<'_>, state: S) -> Self {
+        let wifi_mac = wifi_interface.mac_address();
+
+        let device_data = description();
+
+        Self {
+            wifi_mac,
+            main_route: MAIN_ROUTE,
+            state: State(state),
+            routes_functions: (
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+            ),
+            device_data,
+            index_array: Vec::new(),
+            validators: Validators::new(),
+        }
+    }
+}
@@ -3,6 +3,8 @@
 pub enum ErrorKind {
     /// Empty events manager.
     EmptyEventsManager,
+    /// Event not found.
+    EventNotFound,
     /// `DNS` error.
     Dns,
     /// `mDNS` error.
@@ -15,6 +17,8 @@ pub enum ErrorKind {
     Socket,
     /// Spawning a task error.
     SpawningTask,
+    /// Storage error.
+    Storage,
     /// Timeout error.
     Timeout,
     /// `TCP` error.
@@ -27,12 +31,14 @@ impl ErrorKind {
     const fn description(self) -> &'static str {
         match self {
             Self::EmptyEventsManager => "Empty events manager",
+            Self::EventNotFound => "Event not found",
             Self::Dns => "DNS",
             Self::MDns => "mDNS",
             Self::Mqtt => "MQTT",
             Self::Server => "Server",
             Self::Socket => "Socket",
             Self::SpawningTask => "Spawning task",
+            Self::Storage => "Storage",
             Self::Timeout => "Timeout",
             Self::Tcp => "TCP",
             Self::WiFi => "Wi-Fi",
@@ -70,6 +76,8 @@ impl core::fmt::Display for Error {
     }
 }
 
+impl core::error::Error for Error {}
+
 impl Error {
     pub(crate) fn new(kind: ErrorKind, info: &'static str) -> Self {
         Self { kind, info }
@@ -377,5 +385,23 @@ impl From<esp_radio::wifi::WifiError> for Error {
     }
 }
 
+impl From<tosca_embedded::state::StorageError> for Error {
+    fn from(e: tosca_embedded::state::StorageError) -> Self {
+        Self::new(ErrorKind::Storage, e.description())
+    }
+}
+
+impl From<tosca_embedded::events::EventsError> for Error {
+    fn from(e: tosca_embedded::events::EventsError) -> Self {
+        Self::new(ErrorKind::EventNotFound, e.description())
+    }
+}
+
+impl From<tosca_embedded::provisioning::ProvisioningError> for Error {
+    fn from(e: tosca_embedded::provisioning::ProvisioningError) -> Self {
+        Self::new(ErrorKind::WiFi, e.description())
+    }
+}
+
 /// A specialized [`Result`] type for [`Error`].
 pub type Result<T> = core::result::Result<T, Error>;
@@ -1,5 +1,7 @@
 use embassy_net::IpAddress;
 
+pub use tosca_embedded::broker::BrokerCredentials;
+
 /// Broker data.
 pub enum BrokerData {
     /// Broker `URL` and `port`.
@@ -5,9 +5,11 @@ use alloc::boxed::Box;
 
 use esp_hal::gpio::AnyPin;
 
+use log::error;
+
 use tosca::events::Event;
 
-use crate::events::EVENTS;
+use crate::events::EVENTS_HUB;
 
 use super::{Notifier, notify_network_task};
 
@@ -67,7 +69,14 @@ impl Notifier<bool> {
     pub async fn update_event(&self, value: bool) {
         // Update the bool event.
         {
-            EVENTS.lock().await.update_bool_value(self.index, value);
+            if let Err(e) = EVENTS_HUB
+                .events()
+                .lock()
+                .await
+                .update_bool_value(self.index, value)
+            {
+                error!("Impossible to update the bool event: {e}");
+            }
         }
         // Notify network task.
         notify_network_task().await;
@@ -83,7 +92,7 @@ impl Notifier<bool> {
     #[inline]
     pub(crate) async fn init_event(&self, event_bool: Event<bool>) {
         {
-            EVENTS.lock().await.add_bool_event(event_bool);
+            EVENTS_HUB.events().lock().await.add_bool_event(event_bool);
         }
     }
 }
@@ -5,9 +5,11 @@ use alloc::boxed::Box;
 
 use esp_hal::gpio::AnyPin;
 
+use log::error;
+
 use tosca::events::Event;
 
-use crate::events::EVENTS;
+use crate::events::EVENTS_HUB;
 
 use super::{Notifier, notify_network_task};
 
@@ -67,7 +69,14 @@ impl Notifier<f32> {
     pub async fn update_event(&self, value: f32) {
         // Update the f32 event.
         {
-            EVENTS.lock().await.update_f32_value(self.index, value);
+            if let Err(e) = EVENTS_HUB
+                .events()
+                .lock()
+                .await
+                .update_f32_value(self.index, value)
+            {
+                error!("Impossible to update the f32 event: {e}");
+            }
         }
         notify_network_task().await;
     }
@@ -82,7 +91,7 @@ impl Notifier<f32> {
     #[inline]
     pub(crate) async fn init_event(&self, event_f32: Event<f32>) {
         {
-            EVENTS.lock().await.add_f32_event(event_f32);
+            EVENTS_HUB.events().lock().await.add_f32_event(event_f32);
         }
     }
 }
@@ -5,9 +5,11 @@ use alloc::boxed::Box;
 
 use esp_hal::gpio::AnyPin;
 
+use log::error;
+
 use tosca::events::Event;
 
-use crate::events::EVENTS;
+use crate::events::EVENTS_HUB;
 
 use super::{Notifier, notify_network_task};
 
@@ -67,7 +69,14 @@ impl Notifier<f64> {
     pub async fn update_event(&self, value: f64) {
         // Update the f64 event.
         {
-            EVENTS.lock().await.update_f64_value(self.index, value);
+            if let Err(e) = EVENTS_HUB
+                .events()
+                .lock()
+                .await
+                .update_f64_value(self.index, value)
+            {
+                error!("Impossible to update the f64 event: {e}");
+            }
         }
         notify_network_task().await;
     }
@@ -82,7 +91,7 @@ impl Notifier<f64> {
     #[inline]
     pub(crate) async fn init_event(&self, event_f64: Event<f64>) {
         {
-            EVENTS.lock().await.add_f64_event(event_f64);
+            EVENTS_HUB.events().lock().await.add_f64_event(event_f64);
         }
     }
 }
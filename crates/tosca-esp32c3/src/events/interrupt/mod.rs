@@ -8,14 +8,14 @@ use core::marker::PhantomData;
 
 use embassy_time::Timer;
 
-use crate::events::{WAIT_FOR_MILLISECONDS, WRITE_ON_NETWORK};
+use crate::events::{EVENTS_HUB, WAIT_FOR_MILLISECONDS};
 
 #[inline]
 async fn notify_network_task() {
     // Wait for a bit after the writing operation.
     Timer::after_millis(WAIT_FOR_MILLISECONDS).await;
-    // Write over the network.
-    WRITE_ON_NETWORK.signal(1);
+    // Write over the network and the events stream.
+    EVENTS_HUB.notify_changed();
     // Wait for a bit after sending the signal.
     Timer::after_millis(WAIT_FOR_MILLISECONDS).await;
 }
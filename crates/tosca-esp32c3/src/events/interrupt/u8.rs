@@ -5,9 +5,11 @@ use alloc::boxed::Box;
 
 use esp_hal::gpio::AnyPin;
 
+use log::error;
+
 use tosca::events::Event;
 
-use crate::events::EVENTS;
+use crate::events::EVENTS_HUB;
 
 use super::{Notifier, notify_network_task};
 
@@ -67,7 +69,14 @@ impl Notifier<u8> {
     pub async fn update_event(&self, value: u8) {
         // Update the u8 event.
         {
-            EVENTS.lock().await.update_u8_value(self.index, value);
+            if let Err(e) = EVENTS_HUB
+                .events()
+                .lock()
+                .await
+                .update_u8_value(self.index, value)
+            {
+                error!("Impossible to update the u8 event: {e}");
+            }
         }
         notify_network_task().await;
     }
@@ -82,7 +91,7 @@ impl Notifier<u8> {
     #[inline]
     pub(crate) async fn init_event(&self, event_u8: Event<u8>) {
         {
-            EVENTS.lock().await.add_u8_event(event_u8);
+            EVENTS_HUB.events().lock().await.add_u8_event(event_u8);
         }
     }
 }
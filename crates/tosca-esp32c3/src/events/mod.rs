@@ -12,11 +12,11 @@ use core::net::IpAddr;
 use core::time::Duration;
 
 use alloc::boxed::Box;
+use alloc::vec::Vec;
 
 use embassy_executor::{SpawnToken, Spawner};
 use embassy_net::{IpAddress, Stack, dns::DnsQueryType};
 use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
-use embassy_sync::mutex::Mutex;
 use embassy_sync::signal::Signal;
 use embassy_time::Timer;
 
@@ -28,12 +28,17 @@ use tosca::events::{
     BrokerData as ToscaBrokerData, Event, Events, EventsDescription, PeriodicEvent, Topic,
 };
 
+use tosca_embedded::events::EventsHub;
+use tosca_embedded::fanout::{BrokerPublisher, start_publishers};
+
+pub use tosca_embedded::events::PublishValue;
+
 use crate::device::Device;
 use crate::error::{Error, ErrorKind};
 use crate::state::ValueFromRef;
 use crate::wifi::WIFI_RECONNECT_DELAY;
 
-use broker::BrokerData;
+use broker::{BrokerCredentials, BrokerData};
 use mqtt::Mqtt;
 use topic::TopicBuilder;
 
@@ -72,6 +77,9 @@ use super::events::periodic::{
 // Internal array capacity
 const CAPACITY: usize = 4;
 
+// Maximum number of brokers the events are published to
+const MAXIMUM_BROKERS: usize = 2;
+
 // Time to wait, in milliseconds, after completing a task operation
 const WAIT_FOR_MILLISECONDS: u64 = 200;
 
@@ -88,10 +96,9 @@ const LOWER_PRIORITY: u64 = (WIFI_RECONNECT_DELAY * 2) + 2;
 // connection is still active
 const PING_BROKER_AGAIN: u64 = 10;
 
-// All events to be transmitted over the network
-static EVENTS: Mutex<CriticalSectionRawMutex, Events> = Mutex::new(Events::empty());
-// Signal that enables network transmission
-static WRITE_ON_NETWORK: Signal<CriticalSectionRawMutex, u8> = Signal::new();
+// All events to be transmitted over the network, together with the signals
+// enabling their transmission to each broker and over the HTTP events stream
+pub(crate) static EVENTS_HUB: EventsHub<MAXIMUM_BROKERS> = EventsHub::new();
 
 /// Events configuration.
 ///
@@ -102,7 +109,7 @@ where
 {
     spawner: Spawner,
     stack: Stack<'static>,
-    broker: BrokerData,
+    brokers: Vec<(BrokerData, Option<BrokerCredentials>)>,
     topic: Topic,
     device: Device<S>,
 }
@@ -124,7 +131,7 @@ where
         Self {
             spawner,
             stack,
-            broker,
+            brokers: alloc::vec![(broker, None)],
             topic: TopicBuilder::new()
                 .prefix(topic_prefix)
                 .suffix("events")
@@ -133,10 +140,43 @@ where
             device,
         }
     }
+
+    /// Adds another broker to publish the same events to.
+    ///
+    /// Events are published to each broker independently, so a broker which
+    /// cannot be reached does not stop the publication to the other ones.
+    /// At most two brokers are supported, additional ones are discarded.
+    #[must_use]
+    pub fn add_broker(mut self, broker: BrokerData) -> Self {
+        if self.brokers.len() == MAXIMUM_BROKERS {
+            error!("At most {MAXIMUM_BROKERS} brokers are supported, the broker is discarded");
+            return self;
+        }
+        self.brokers.push((broker, None));
+        self
+    }
+
+    /// Sets the credentials to authenticate to the most recently
+    /// added broker.
+    ///
+    /// Without credentials, the connection to the broker is anonymous.
+    #[must_use]
+    pub fn credentials(mut self, credentials: BrokerCredentials) -> Self {
+        if let Some((_, broker_credentials)) = self.brokers.last_mut() {
+            *broker_credentials = Some(credentials);
+        }
+        self
+    }
 }
 
-#[embassy_executor::task]
-async fn write_on_network(stack: Stack<'static>, remote_endpoint: (IpAddress, u16), topic: Topic) {
+#[embassy_executor::task(pool_size = MAXIMUM_BROKERS)]
+async fn write_on_network(
+    stack: Stack<'static>,
+    remote_endpoint: (IpAddress, u16),
+    credentials: Option<BrokerCredentials>,
+    topic: Topic,
+    signal: &'static Signal<CriticalSectionRawMutex, u8>,
+) {
     // This task is scheduled to run last, so it is assigned a lower priority.
     Timer::after_secs(LOWER_PRIORITY).await;
 
@@ -144,7 +184,7 @@ async fn write_on_network(stack: Stack<'static>, remote_endpoint: (IpAddress, u1
         // Create a `MQTT` publisher.
         //
         // If an error occurs, retry creation after a specified time interval.
-        match Mqtt::new(stack, remote_endpoint).await {
+        match Mqtt::new(stack, remote_endpoint, credentials).await {
             Ok(mqtt_publisher) => {
                 info!("Created the `MQTT` publisher");
                 break mqtt_publisher;
@@ -183,7 +223,7 @@ async fn write_on_network(stack: Stack<'static>, remote_endpoint: (IpAddress, u1
             // After five consecutive ping failures, reinitialize the `MQTT`
             // publisher, as the socket may have been closed.
             if ping_failure_counter == 5 {
-                mqtt_publisher = match Mqtt::new(stack, remote_endpoint).await {
+                mqtt_publisher = match Mqtt::new(stack, remote_endpoint, credentials).await {
                     Ok(mqtt_publisher) => {
                         info!("Reinitialize the `MQTT` publisher");
                         mqtt_publisher
@@ -216,11 +256,11 @@ async fn write_on_network(stack: Stack<'static>, remote_endpoint: (IpAddress, u1
         // The lock will be released at the end of this scope.
         {
             // Wait until a signal is received.
-            let _ = WRITE_ON_NETWORK.wait().await;
+            let _ = signal.wait().await;
         }
         // The lock will be released at the end of this scope,
         // once the JSON data has been retrieved.
-        let json_data = { serde_json::to_vec(&*EVENTS.lock().await) };
+        let json_data = { serde_json::to_vec(&*EVENTS_HUB.events().lock().await) };
 
         // Serialize data
         let data = match json_data {
@@ -246,6 +286,28 @@ async fn write_on_network(stack: Stack<'static>, remote_endpoint: (IpAddress, u1
     }
 }
 
+/// A handle to publish events on demand.
+///
+/// It is obtained through [`EventsManager::publisher`] and can be freely
+/// copied into route handlers.
+#[derive(Clone, Copy)]
+pub struct EventsPublisher;
+
+impl EventsPublisher {
+    /// Updates the value of the event with the given name and immediately
+    /// queues a publish of all events over the network.
+    ///
+    /// # Errors
+    ///
+    /// Fails when no event of the given value type has the given name.
+    pub async fn publish_now<T: PublishValue>(&self, name: &str, value: T) -> Result<(), Error> {
+        EVENTS_HUB.publish_now(name, value).await.map_err(|e| {
+            error!("Impossible to publish the event `{name}`: not found");
+            e.into()
+        })
+    }
+}
+
 /// An event manager.
 ///
 /// Validates the events data and executes the corresponding tasks.
@@ -273,6 +335,16 @@ where
         }
     }
 
+    /// Returns an [`EventsPublisher`] handle.
+    ///
+    /// The handle can be moved into route handlers to publish an event value
+    /// immediately, without waiting for its notifier.
+    #[inline]
+    #[must_use]
+    pub const fn publisher(&self) -> EventsPublisher {
+        EventsPublisher
+    }
+
     /// Monitors a pin with an [`Event<bool>`] notifier.
     ///
     /// Discards the event if it matches an existing one.
@@ -1036,16 +1108,18 @@ where
         self.spawn(name, task, |events| events.add_periodic_f64_event(event))
     }
 
-    /// Runs the task that transmits events over the network.
+    /// Runs the tasks that transmit events over the network, one for
+    /// each broker.
     ///
-    /// Returns a [`Device`] updated with [`EventsDescription`] data.
+    /// Returns a [`Device`] updated with [`EventsDescription`] data, which
+    /// advertises the first reachable broker.
     ///
     /// # Errors
     ///
     /// Fails when:
     /// - The events manager is empty (no events have been inserted).
-    /// - The broker domain cannot be resolved via a `DNS` query.
-    /// - The task responsible for network transmission cannot interact with
+    /// - No broker domain can be resolved via a `DNS` query.
+    /// - No task responsible for network transmission can interact with
     ///   the scheduler or the network.
     pub async fn run_network_task(self) -> Result<Device<S>, Error> {
         if self.events.is_empty() {
@@ -1055,7 +1129,38 @@ where
             ));
         }
 
-        let remote_endpoint = match self.config.broker {
+        let brokers = self
+            .config
+            .brokers
+            .iter()
+            .zip(EVENTS_HUB.network_signals())
+            .map(|((broker, credentials), signal)| BrokerTask {
+                manager: &self,
+                broker,
+                credentials: *credentials,
+                signal,
+            });
+        let remote_endpoint = start_publishers(brokers).await.map_err(|e| {
+            e.unwrap_or_else(|| Error::new(ErrorKind::Mqtt, "No broker to publish events to"))
+        })?;
+
+        Ok(self
+            .config
+            .device
+            .events_description(EventsDescription::new(
+                ToscaBrokerData::new(IpAddr::from(remote_endpoint.0), remote_endpoint.1),
+                self.config.topic,
+                self.events,
+            )))
+    }
+
+    async fn spawn_broker_task(
+        &self,
+        broker: &BrokerData,
+        credentials: Option<BrokerCredentials>,
+        write_on_network_signal: &'static Signal<CriticalSectionRawMutex, u8>,
+    ) -> Result<(IpAddress, u16), Error> {
+        let remote_endpoint = match *broker {
             BrokerData::Url(url, port) => {
                 let address = self
                     .config
@@ -1072,17 +1177,12 @@ where
         self.config.spawner.spawn(write_on_network(
             self.config.stack,
             remote_endpoint,
+            credentials,
             self.config.topic.clone(),
+            write_on_network_signal,
         ))?;
 
-        Ok(self
-            .config
-            .device
-            .events_description(EventsDescription::new(
-                ToscaBrokerData::new(IpAddr::from(remote_endpoint.0), remote_endpoint.1),
-                self.config.topic,
-                self.events,
-            )))
+        Ok(remote_endpoint)
     }
 
     fn spawn<F, T>(mut self, name: &'static str, task: SpawnToken<T>, add_event: F) -> Self
@@ -1098,3 +1198,28 @@ where
         self
     }
 }
+
+// Publishes the events to a broker through a dedicated network task.
+struct BrokerTask<'a, S>
+where
+    S: ValueFromRef + Send + Sync + 'static,
+{
+    manager: &'a EventsManager<S>,
+    broker: &'a BrokerData,
+    credentials: Option<BrokerCredentials>,
+    signal: &'static Signal<CriticalSectionRawMutex, u8>,
+}
+
+impl<S> BrokerPublisher for BrokerTask<'_, S>
+where
+    S: ValueFromRef + Send + Sync + 'static,
+{
+    type Endpoint = (IpAddress, u16);
+    type Error = Error;
+
+    async fn start(self) -> Result<Self::Endpoint, Error> {
+        self.manager
+            .spawn_broker_task(self.broker, self.credentials, self.signal)
+            .await
+    }
+}
@@ -14,6 +14,8 @@ use rust_mqtt::utils::rng_generator::CountingRng;
 
 use log::{info, warn};
 
+use tosca_embedded::broker::{BrokerCredentials, apply_credentials};
+
 use crate::error::{Error, ErrorKind};
 
 // Timeout duration for the socket connection, in seconds.
@@ -34,6 +36,7 @@ impl Mqtt {
     pub(crate) async fn new(
         stack: Stack<'static>,
         remote_endpoint: (IpAddress, u16),
+        credentials: Option<BrokerCredentials>,
     ) -> Result<Self, Error> {
         let rx_buffer = Box::leak(Box::new([0u8; BUFFER_SIZE]));
         let tx_buffer = Box::leak(Box::new([0u8; BUFFER_SIZE]));
@@ -59,6 +62,12 @@ impl Mqtt {
         let mut config = ClientConfig::new(MqttVersion::MQTTv5, CountingRng(20000));
         config.add_max_subscribe_qos(QualityOfService::QoS1);
         config.max_packet_size = MAX_PACKET_SIZE;
+        apply_credentials(
+            &mut config,
+            credentials,
+            ClientConfig::add_username,
+            ClientConfig::add_password,
+        );
 
         let client = MqttClient::<_, MAXIMUM_MQTT_PROPERTIES, _>::new(
             socket,
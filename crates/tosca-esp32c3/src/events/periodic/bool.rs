@@ -6,9 +6,11 @@ use alloc::boxed::Box;
 
 use esp_hal::gpio::AnyPin;
 
+use log::error;
+
 use tosca::events::PeriodicEvent;
 
-use crate::events::EVENTS;
+use crate::events::EVENTS_HUB;
 
 use super::{PeriodicNotifier, notify_network_task};
 
@@ -69,10 +71,14 @@ impl PeriodicNotifier<bool> {
     pub async fn update_event(&self, value: bool) {
         // Update the periodic bool event.
         {
-            EVENTS
+            if let Err(e) = EVENTS_HUB
+                .events()
                 .lock()
                 .await
-                .update_periodic_bool_value(self.index, value);
+                .update_periodic_bool_value(self.index, value)
+            {
+                error!("Impossible to update the periodic bool event: {e}");
+            }
         }
         // Notify the network task and wait for the chosen amount of seconds.
         notify_network_task(self.time_interval.as_secs()).await;
@@ -89,7 +95,8 @@ impl PeriodicNotifier<bool> {
     #[inline]
     pub(crate) async fn init_event(&self, periodic_event_bool: PeriodicEvent<bool>) {
         {
-            EVENTS
+            EVENTS_HUB
+                .events()
                 .lock()
                 .await
                 .add_periodic_bool_event(periodic_event_bool);
@@ -6,9 +6,11 @@ use alloc::boxed::Box;
 
 use esp_hal::gpio::AnyPin;
 
+use log::error;
+
 use tosca::events::PeriodicEvent;
 
-use crate::events::EVENTS;
+use crate::events::EVENTS_HUB;
 
 use super::{PeriodicNotifier, notify_network_task};
 
@@ -69,10 +71,14 @@ impl PeriodicNotifier<i32> {
     pub async fn update_event(&self, value: i32) {
         // Update the i32 value in the shared structure.
         {
-            EVENTS
+            if let Err(e) = EVENTS_HUB
+                .events()
                 .lock()
                 .await
-                .update_periodic_i32_value(self.index, value);
+                .update_periodic_i32_value(self.index, value)
+            {
+                error!("Impossible to update the periodic i32 event: {e}");
+            }
         }
         // Notify the network task and wait for the chosen amount of seconds.
         notify_network_task(self.time_interval.as_secs()).await;
@@ -89,7 +95,8 @@ impl PeriodicNotifier<i32> {
     #[inline]
     pub(crate) async fn init_event(&self, periodic_event_i32: PeriodicEvent<i32>) {
         {
-            EVENTS
+            EVENTS_HUB
+                .events()
                 .lock()
                 .await
                 .add_periodic_i32_event(periodic_event_i32);
@@ -9,7 +9,7 @@ use core::time::Duration;
 
 use embassy_time::Timer;
 
-use crate::events::{WAIT_FOR_MILLISECONDS, WRITE_ON_NETWORK};
+use crate::events::{EVENTS_HUB, WAIT_FOR_MILLISECONDS};
 
 /// A notifier for signaling a [`tosca::events::PeriodicEvent`].
 pub struct PeriodicNotifier<T: Clone + Copy> {
@@ -22,8 +22,8 @@ pub struct PeriodicNotifier<T: Clone + Copy> {
 async fn notify_network_task(secs: u64) {
     // Wait for a bit after the writing operation.
     Timer::after_millis(WAIT_FOR_MILLISECONDS).await;
-    // Write over the network.
-    WRITE_ON_NETWORK.signal(1);
+    // Write over the network and the events stream.
+    EVENTS_HUB.notify_changed();
     // Wait for a bit after sending the signal.
     Timer::after_secs(secs).await;
 }
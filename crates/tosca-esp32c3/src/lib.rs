@@ -44,6 +44,8 @@
 
 extern crate alloc;
 
+mod budget;
+
 /// All supported device types.
 pub mod devices;
 
@@ -59,12 +61,14 @@ pub mod mdns;
 pub mod net;
 /// All route parameters.
 pub mod parameters;
+/// The `Wi-Fi` provisioning process.
+pub mod provisioning;
 /// All responses kinds along with their payloads.
 pub mod response;
 /// The firmware server.
 pub mod server;
 /// The device state.
-pub mod state;
+pub use tosca_embedded::state;
 /// The `Wi-Fi` controller.
 pub mod wifi;
 
@@ -11,29 +11,25 @@ use embassy_sync::signal::Signal;
 
 use embassy_net::Stack;
 
-use edge_mdns::HostAnswersMdnsHandler;
+use embassy_time::{Duration, Instant};
+
 use edge_mdns::buf::VecBufAccess;
 use edge_mdns::domain::base::Ttl;
 use edge_mdns::host::{Host, Service, ServiceAnswers};
 use edge_mdns::io::{self, IPV4_DEFAULT_SOCKET};
+use edge_mdns::{
+    ChainedHostAnswers, HostAnswersMdnsHandler, MdnsError, MdnsHandler, MdnsRequest, MdnsResponse,
+};
 
 use edge_nal::UdpSplit;
 use edge_nal_embassy::{Udp, UdpBuffers};
 
-use log::info;
+use log::{debug, info};
 
-use crate::error::Result;
+use tosca_embedded::mdns::{HostRecord, MdnsConfig};
+use tosca_embedded::throttle::ResponseThrottle;
 
-// Hostname
-const HOSTNAME: &str = "tosca";
-// Service name
-const SERVICE: &str = "tosca";
-// Service type
-const SERVICE_TYPE: &str = "_tosca";
-// Transport protocol
-const TRANSPORT_PROTOCOL: &str = "_udp";
-// Time-to-live for answers in seconds
-const TIME_TO_LIVE: u32 = 60;
+use crate::error::Result;
 
 // mDNS buffer pool size
 const MDNS_BUFFER_POOL_SIZE: usize = 2;
@@ -42,15 +38,53 @@ const BUFFER_LENGTH: usize = 1500;
 // Packet metadata length
 const PACKET_METADATA_LENGTH: usize = 2;
 
+// An mDNS handler which throttles the multicast replies.
+//
+// Legacy queries are answered privately to the querier, hence their
+// replies are never throttled, as well as the announcements sent without
+// a query.
+struct ThrottledHandler<H> {
+    handler: H,
+    throttle: Option<ResponseThrottle>,
+}
+
+impl<H: MdnsHandler> MdnsHandler for ThrottledHandler<H> {
+    fn handle<'a>(
+        &mut self,
+        request: MdnsRequest<'_>,
+        response_buf: &'a mut [u8],
+    ) -> core::result::Result<MdnsResponse<'a>, MdnsError> {
+        let is_multicast_query = matches!(request, MdnsRequest::Request { legacy: false, .. });
+
+        let response = self.handler.handle(request, response_buf)?;
+
+        if let (Some(throttle), MdnsResponse::Reply { data, .. }) = (&mut self.throttle, &response)
+            && is_multicast_query
+            && !throttle.allow(data, Instant::now())
+        {
+            debug!("Throttling an mDNS reply sent less than a window ago");
+            return Ok(MdnsResponse::None);
+        }
+
+        Ok(response)
+    }
+}
+
+// Converts a host record into an mDNS host.
+fn mdns_host(record: HostRecord) -> Host<'static> {
+    Host {
+        hostname: record.hostname,
+        ipv4: record.ipv4,
+        ipv6: record.ipv6,
+        ttl: Ttl::from_secs(record.time_to_live),
+    }
+}
+
 static RNG: CriticalSectionMutex<OnceCell<Rng>> = CriticalSectionMutex::new(OnceCell::new());
 
 /// The `mDNS-SD` discovery service.
 pub struct Mdns {
-    hostname: &'static str,
-    service: &'static str,
-    service_type: &'static str,
-    time_to_live: u32,
-    properties: &'static [(&'static str, &'static str)],
+    config: MdnsConfig,
     rng: Rng,
 }
 
@@ -59,11 +93,7 @@ impl Mdns {
     #[must_use]
     pub const fn new(rng: Rng) -> Self {
         Self {
-            hostname: HOSTNAME,
-            service: SERVICE,
-            service_type: SERVICE_TYPE,
-            time_to_live: TIME_TO_LIVE,
-            properties: &[],
+            config: MdnsConfig::new(),
             rng,
         }
     }
@@ -73,7 +103,7 @@ impl Mdns {
     /// An example might be `tosca`.
     #[must_use]
     pub const fn hostname(mut self, hostname: &'static str) -> Self {
-        self.hostname = hostname;
+        self.config = self.config.hostname(hostname);
         self
     }
 
@@ -84,7 +114,7 @@ impl Mdns {
     /// i.e. device
     #[must_use]
     pub const fn service(mut self, service: &'static str) -> Self {
-        self.service = service;
+        self.config = self.config.service(service);
         self
     }
 
@@ -93,24 +123,96 @@ impl Mdns {
     /// The service type searched by the client. i.e. _tosca
     #[must_use]
     pub const fn service_type(mut self, service_type: &'static str) -> Self {
-        self.service_type = service_type;
+        self.config = self.config.service_type(service_type);
         self
     }
 
     /// Time-to-live (TTL) in seconds for the discovery service responses.
+    ///
+    /// Sets the same value for both host and service answers.
+    /// A value of `0` is raised to `1` second, while values greater than
+    /// `4500` seconds (75 minutes) are clamped to `4500`.
     #[must_use]
     pub const fn time_to_live(mut self, seconds: u32) -> Self {
-        self.time_to_live = if seconds == 0 { 1 } else { seconds };
+        self.config = self.config.time_to_live(seconds);
+        self
+    }
+
+    /// Time-to-live (TTL) in seconds for the host answers, namely the
+    /// records containing the device addresses.
+    ///
+    /// The recommended range is between `60` and `120` seconds.
+    /// A value of `0` is raised to `1` second, while values greater than
+    /// `4500` seconds (75 minutes) are clamped to `4500`.
+    #[must_use]
+    pub const fn host_time_to_live(mut self, seconds: u32) -> Self {
+        self.config = self.config.host_time_to_live(seconds);
+        self
+    }
+
+    /// Time-to-live (TTL) in seconds for the service answers, namely the
+    /// `SRV`, `TXT`, and `PTR` records.
+    ///
+    /// Longer values reduce the query traffic on noisy networks, so the
+    /// recommended range is between `120` and `4500` seconds.
+    /// A value of `0` is raised to `1` second, while values greater than
+    /// `4500` seconds (75 minutes) are clamped to `4500`.
+    #[must_use]
+    pub const fn service_time_to_live(mut self, seconds: u32) -> Self {
+        self.config = self.config.service_time_to_live(seconds);
         self
     }
 
     /// Sets the service properties.
     ///
-    /// An example of property could be the server scheme.
-    /// i.e. [("scheme", "http")]
+    /// An example of property could be the device location.
+    /// i.e. [("room", "kitchen")]
+    ///
+    /// The `kind`, `route`, and `scheme` properties are set by the
+    /// [`Server`](crate::server::Server) when it runs, replacing any
+    /// property with the same key.
     #[must_use]
     pub const fn properties(mut self, properties: &'static [(&'static str, &'static str)]) -> Self {
-        self.properties = properties;
+        self.config = self.config.properties(properties);
+        self
+    }
+
+    /// Sets the service subtypes.
+    ///
+    /// Subtypes allow clients to discover only the services of a certain
+    /// kind, for example the lights among the `_tosca` services.
+    /// i.e. ["_light"]
+    ///
+    /// By default, the service has no subtypes.
+    #[must_use]
+    pub const fn service_subtypes(mut self, service_subtypes: &'static [&'static str]) -> Self {
+        self.config = self.config.service_subtypes(service_subtypes);
+        self
+    }
+
+    /// Throttles the replies to the queries flooding the responder.
+    ///
+    /// An identical reply is multicast at most once within the given
+    /// window (in milliseconds), so a discovery storm does not waste radio
+    /// time answering the same question over and over. Since multicast
+    /// replies reach all queriers, the ones asking within the window still
+    /// receive the previous reply. RFC 6762 requires a window of at least
+    /// `1000` milliseconds.
+    ///
+    /// Replies to legacy unicast queries are never throttled, as they are
+    /// sent privately to the querier. A value of `0` disables throttling,
+    /// which is the default.
+    #[must_use]
+    pub const fn throttle_responses(mut self, window_ms: u64) -> Self {
+        self.config = self.config.throttle_responses(window_ms);
+        self
+    }
+
+    // Adds properties derived from the server to the user-defined ones.
+    // These properties describe the running server, hence they replace
+    // any user-defined property with the same key.
+    pub(crate) fn append_properties(mut self, properties: &[(&'static str, &'static str)]) -> Self {
+        self.config = self.config.append_properties(properties);
         self
     }
 
@@ -118,50 +220,68 @@ impl Mdns {
         self,
         stack: Stack<'static>,
         address: Ipv4Addr,
+        address_v6: Option<Ipv6Addr>,
         port: u16,
         spawner: Spawner,
     ) -> Result<()> {
         RNG.lock(|c| _ = c.set(self.rng));
 
+        let (host, service_host) = self.config.host_records(address, address_v6);
+        let service = self.config.service_record(port);
+
         info!(
             "About to run an mDNS responder on IPV4 address `{}`. \
              It will be accessible via `{}.local`, \
              so try to run the command `ping {}.local`.",
-            address, self.hostname, self.hostname
+            address, host.hostname, host.hostname
         );
 
-        let host = Host {
-            hostname: self.hostname,
-            ipv4: address,
-            ipv6: Ipv6Addr::UNSPECIFIED,
-            ttl: Ttl::from_secs(self.time_to_live),
-        };
+        if let Some(address_v6) = address_v6 {
+            info!("The mDNS responder also advertises IPV6 address `{address_v6}`.");
+        }
 
         info!(
             "About to run a mDNS service with name `{}` and type `{}` \
              on port `{port}`.",
-            self.service, self.service_type
+            service.name, service.service_type
         );
 
         let service = Service {
-            name: self.service,
+            name: service.name,
             priority: 1,
             weight: 5,
-            service: self.service_type,
-            protocol: TRANSPORT_PROTOCOL,
-            port,
-            service_subtypes: &[],
-            txt_kvs: self.properties,
+            service: service.service_type,
+            protocol: service.protocol,
+            port: service.port,
+            service_subtypes: service.service_subtypes,
+            txt_kvs: service.properties,
         };
 
+        let throttle = self
+            .config
+            .throttle_window_ms()
+            .map(|window_ms| ResponseThrottle::new(Duration::from_millis(window_ms)));
+
         spawner
-            .spawn(run_mdns_task(stack, host, service))
+            .spawn(run_mdns_task(
+                stack,
+                mdns_host(host),
+                mdns_host(service_host),
+                service,
+                throttle,
+            ))
             .map_err(core::convert::Into::into)
     }
 }
 
 #[embassy_executor::task]
-async fn run_mdns_task(stack: Stack<'static>, host: Host<'static>, service: Service<'static>) {
+async fn run_mdns_task(
+    stack: Stack<'static>,
+    host: Host<'static>,
+    service_host: Host<'static>,
+    service: Service<'static>,
+    throttle: Option<ResponseThrottle>,
+) {
     let (recv_buf, send_buf) = (
         VecBufAccess::<NoopRawMutex, BUFFER_LENGTH>::new(),
         VecBufAccess::<NoopRawMutex, BUFFER_LENGTH>::new(),
@@ -199,9 +319,13 @@ async fn run_mdns_task(stack: Stack<'static>, host: Host<'static>, service: Serv
         &signal,
     );
 
-    mdns.run(HostAnswersMdnsHandler::new(ServiceAnswers::new(
-        &host, &service,
-    )))
+    mdns.run(ThrottledHandler {
+        handler: HostAnswersMdnsHandler::new(ChainedHostAnswers::new(
+            &host,
+            ServiceAnswers::new(&service_host, &service),
+        )),
+        throttle,
+    })
     .await
     .expect("mDNS-SD task failed");
 }
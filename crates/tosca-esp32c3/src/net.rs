@@ -1,4 +1,4 @@
-use core::net::Ipv4Addr;
+use core::net::{Ipv4Addr, Ipv6Addr};
 
 use alloc::boxed::Box;
 
@@ -7,15 +7,21 @@ use esp_hal::rng::Rng;
 use esp_radio::wifi::WifiDevice;
 
 use embassy_executor::Spawner;
-use embassy_net::{Config, DhcpConfig, Runner, Stack, StackResources};
+use embassy_net::{
+    Config, ConfigV6, DhcpConfig, Ipv4Address, Ipv4Cidr, Ipv6Cidr, Runner, Stack, StackResources,
+    StaticConfigV4, StaticConfigV6,
+};
 use embassy_time::Timer;
 
-use log::info;
+use log::{info, warn};
 
 use crate::error::Result;
 
 const MILLISECONDS_TO_WAIT: u64 = 100;
 
+// Maximum number of DNS servers of a static IP configuration.
+const MAXIMUM_DNS_SERVERS: usize = 3;
+
 // Retrieves the IPV4 address from the network stack.
 #[inline]
 pub(crate) async fn get_ip(stack: Stack<'static>) -> Ipv4Addr {
@@ -36,7 +42,15 @@ pub(crate) async fn get_ip(stack: Stack<'static>) -> Ipv4Addr {
     }
 }
 
-#[embassy_executor::task]
+// Retrieves the IPV6 address from the network stack, if any.
+#[inline]
+pub(crate) fn get_ipv6(stack: Stack<'static>) -> Option<Ipv6Addr> {
+    stack.config_v6().map(|config| config.address.address())
+}
+
+// Two stacks might run at the same time: one on the access point interface
+// while provisioning a device, and one on the station interface.
+#[embassy_executor::task(pool_size = 2)]
 async fn task(mut runner: Runner<'static, WifiDevice<'static>>) {
     runner.run().await;
 }
@@ -47,6 +61,8 @@ pub struct NetworkStack;
 impl NetworkStack {
     /// Builds the [`NetworkStack`] .
     ///
+    /// The IP configuration is obtained through `DHCP`.
+    ///
     /// # Errors
     ///
     /// Failure to spawn the network stack task.
@@ -56,6 +72,75 @@ impl NetworkStack {
         spawner: Spawner,
     ) -> Result<Stack<'static>> {
         let config = Config::dhcpv4(DhcpConfig::default());
+        Self::spawn::<SOCKET_STACK_SIZE>(rng, wifi_interface, spawner, config).await
+    }
+
+    /// Builds the [`NetworkStack`] with a static IPV6 address.
+    ///
+    /// The IPV4 configuration is obtained through `DHCP`, while the device
+    /// also takes the given IPV6 `address`, which can be served through
+    /// [`Server::ipv6`](crate::server::Server::ipv6).
+    ///
+    /// # Errors
+    ///
+    /// Failure to spawn the network stack task.
+    pub async fn build_with_ipv6<const SOCKET_STACK_SIZE: usize>(
+        rng: Rng,
+        wifi_interface: WifiDevice<'static>,
+        spawner: Spawner,
+        address: Ipv6Cidr,
+    ) -> Result<Stack<'static>> {
+        let mut config = Config::dhcpv4(DhcpConfig::default());
+        config.ipv6 = ConfigV6::Static(StaticConfigV6 {
+            address,
+            gateway: None,
+            dns_servers: Default::default(),
+        });
+        Self::spawn::<SOCKET_STACK_SIZE>(rng, wifi_interface, spawner, config).await
+    }
+
+    /// Builds the [`NetworkStack`] with a static IP configuration.
+    ///
+    /// Useful on networks without a `DHCP` server. The device takes the
+    /// given `address`, together with an optional `gateway` and
+    /// up to three `dns` servers. Additional `dns` servers are ignored.
+    ///
+    /// Both [`Self::build`] and [`Self::build_static`] spawn the same
+    /// network stack task, which is allocated in a pool with two slots.
+    /// Hence at most two network stacks can be built by a firmware, such as
+    /// one on the access point interface while provisioning a device and
+    /// one on the station interface, and building a third one returns
+    /// an error.
+    ///
+    /// # Errors
+    ///
+    /// Failure to spawn the network stack task.
+    pub async fn build_static<const SOCKET_STACK_SIZE: usize>(
+        rng: Rng,
+        wifi_interface: WifiDevice<'static>,
+        spawner: Spawner,
+        address: Ipv4Cidr,
+        gateway: Option<Ipv4Address>,
+        dns: &[Ipv4Address],
+    ) -> Result<Stack<'static>> {
+        if dns.len() > MAXIMUM_DNS_SERVERS {
+            warn!("Only the first {MAXIMUM_DNS_SERVERS} DNS servers are used");
+        }
+
+        let config = Config::ipv4_static(StaticConfigV4 {
+            address,
+            gateway,
+            dns_servers: dns.iter().copied().take(MAXIMUM_DNS_SERVERS).collect(),
+        });
+        Self::spawn::<SOCKET_STACK_SIZE>(rng, wifi_interface, spawner, config).await
+    }
+
+    async fn spawn<const SOCKET_STACK_SIZE: usize>(
+        rng: Rng,
+        wifi_interface: WifiDevice<'static>,
+        spawner: Spawner,
+        config: Config,
+    ) -> Result<Stack<'static>> {
         let seed = u64::from(rng.random()) << 32 | u64::from(rng.random());
 
         // FIXME: We need to use `Box::leak` and then `Box::new` because
@@ -114,6 +114,52 @@ impl U64Payload {
     }
 }
 
+/// An [`i32`] payload.
+pub struct I32Payload {
+    /// Value.
+    pub value: i32,
+    /// Default value.
+    pub default: i32,
+    /// Minimum value.
+    pub min: i32,
+    /// Maximum value.
+    pub max: i32,
+}
+
+impl I32Payload {
+    const fn new(value: i32, default: i32, min: i32, max: i32) -> Self {
+        Self {
+            value,
+            default,
+            min,
+            max,
+        }
+    }
+}
+
+/// An [`i64`] payload.
+pub struct I64Payload {
+    /// Value.
+    pub value: i64,
+    /// Default value.
+    pub default: i64,
+    /// Minimum value.
+    pub min: i64,
+    /// Maximum value.
+    pub max: i64,
+}
+
+impl I64Payload {
+    const fn new(value: i64, default: i64, min: i64, max: i64) -> Self {
+        Self {
+            value,
+            default,
+            min,
+            max,
+        }
+    }
+}
+
 /// A [`f32`] payload.
 pub struct F32Payload {
     /// Value.
@@ -184,6 +230,16 @@ impl<'a> CharsSequencePayload<'a> {
 pub struct ParametersPayloads(pub(crate) ToscaParametersPayloads<'static>);
 
 impl ParametersPayloads {
+    /// Retrieves the [`ParameterValue`] associated with the given
+    /// parameter name, without consuming it.
+    ///
+    /// Returns [`None`] if the parameter does not exist.
+    #[must_use]
+    #[inline]
+    pub fn value(&self, name: &'static str) -> Option<&ParameterValue> {
+        self.0.get(name).map(|payload| &payload.value)
+    }
+
     /// Retrieves the [`BoolPayload`] associated with the given parameter name.
     ///
     /// **It consumes the parameter.**
@@ -292,6 +348,52 @@ impl ParametersPayloads {
         })
     }
 
+    /// Retrieves the [`I32Payload`] associated with the given parameter name.
+    ///
+    /// **It consumes the parameter.**
+    ///
+    /// # Errors
+    ///
+    /// An [`ErrorResponse`] is returned in the following cases:
+    ///
+    /// - When the given parameter is not found
+    /// - When the given parameter has an incorrect type
+    #[inline]
+    pub fn i32(&mut self, name: &'static str) -> Result<I32Payload, ErrorResponse> {
+        self.insert(name, |payload| match (payload.value, payload.kind) {
+            (
+                ParameterValue::I32(v),
+                ParameterKind::RangeI32 {
+                    default, min, max, ..
+                },
+            ) => Ok(I32Payload::new(v, default, min, max)),
+            _ => Err(invalid_data(&format!("`{name}` is not a `i32` kind"))),
+        })
+    }
+
+    /// Retrieves the [`I64Payload`] associated with the given parameter name.
+    ///
+    /// **It consumes the parameter.**
+    ///
+    /// # Errors
+    ///
+    /// An [`ErrorResponse`] is returned in the following cases:
+    ///
+    /// - When the given parameter is not found
+    /// - When the given parameter has an incorrect type
+    #[inline]
+    pub fn i64(&mut self, name: &'static str) -> Result<I64Payload, ErrorResponse> {
+        self.insert(name, |payload| match (payload.value, payload.kind) {
+            (
+                ParameterValue::I64(v),
+                ParameterKind::RangeI64 {
+                    default, min, max, ..
+                },
+            ) => Ok(I64Payload::new(v, default, min, max)),
+            _ => Err(invalid_data(&format!("`{name}` is not a `i64` kind"))),
+        })
+    }
+
     /// Retrieves the [`F32Payload`] associated with the given parameter name.
     ///
     /// **It consumes the parameter.**
@@ -353,6 +455,8 @@ impl ParametersPayloads {
     /// Retrieves the [`CharsSequencePayload`] associated with
     /// the given parameter name.
     ///
+    /// Enumeration parameters are retrieved as sequences of characters too.
+    ///
     /// # Errors
     ///
     /// An [`ErrorResponse`] is returned in the following cases:
@@ -365,9 +469,10 @@ impl ParametersPayloads {
         name: &'static str,
     ) -> Result<CharsSequencePayload<'_>, ErrorResponse> {
         self.insert(name, |payload| match (payload.value, payload.kind) {
-            (ParameterValue::CharsSequence(s), ParameterKind::CharsSequence { default }) => {
-                Ok(CharsSequencePayload::new(s, default))
-            }
+            (
+                ParameterValue::CharsSequence(s),
+                ParameterKind::CharsSequence { default } | ParameterKind::Enum { default, .. },
+            ) => Ok(CharsSequencePayload::new(s, default)),
             _ => Err(invalid_data(&format!(
                 "`{name}` is not a `characters sequence` kind"
             ))),
@@ -0,0 +1,72 @@
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::mutex::Mutex;
+use embassy_sync::signal::Signal;
+
+pub use tosca_embedded::provisioning::{Credentials, ProvisioningMode};
+
+use tosca_embedded::provisioning::Provisioning;
+
+use crate::error::Result;
+
+// Provisioning route.
+pub(crate) const PROVISIONING_ROUTE: &str = "/provisioning";
+
+// Page served on the provisioning route, sending the credentials back
+// as JSON.
+pub(crate) const PROVISIONING_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><meta name="viewport" content="width=device-width"><title>Wi-Fi setup</title></head>
+<body>
+<form id="f">
+<p><label>SSID <input name="ssid" required maxlength="32"></label></p>
+<p><label>Password <input name="password" type="password" required minlength="8" maxlength="64"></label></p>
+<p><button>Connect</button></p>
+</form>
+<p id="r"></p>
+<script>
+document.getElementById("f").onsubmit = async (e) => {
+  e.preventDefault();
+  const d = new FormData(e.target);
+  const r = await fetch("/provisioning", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ ssid: d.get("ssid"), password: d.get("password") }),
+  });
+  document.getElementById("r").textContent = r.ok ? "Saved, the device is connecting." : "Invalid credentials.";
+};
+</script>
+</body>
+</html>
+"#;
+
+// Provisioning state shared between the server and the firmware.
+static PROVISIONING: Mutex<CriticalSectionRawMutex, Provisioning> = Mutex::new(Provisioning::new());
+
+// Notified whenever new credentials are stored.
+static CREDENTIALS_RECEIVED: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+
+// Stores the credentials received on the provisioning route.
+pub(crate) async fn store_credentials(credentials: Credentials) -> Result<()> {
+    PROVISIONING.lock().await.store(credentials)?;
+    CREDENTIALS_RECEIVED.signal(());
+    Ok(())
+}
+
+/// Returns the current [`ProvisioningMode`].
+pub async fn provisioning_mode() -> ProvisioningMode {
+    PROVISIONING.lock().await.mode()
+}
+
+/// Waits for the [`Credentials`] sent to the provisioning route,
+/// switching the device to [`ProvisioningMode::Station`].
+///
+/// Once this function returns, the provisioning route rejects any other
+/// credentials.
+pub async fn wait_for_credentials() -> Credentials {
+    loop {
+        if let Some(credentials) = PROVISIONING.lock().await.switch_to_station() {
+            return credentials;
+        }
+        CREDENTIALS_RECEIVED.wait().await;
+    }
+}
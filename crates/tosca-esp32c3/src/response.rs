@@ -1,5 +1,8 @@
+use core::pin::Pin;
+
 use alloc::borrow::Cow;
-use alloc::string::ToString;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
 use tosca::device::DeviceMetrics;
@@ -15,6 +18,9 @@ use embedded_io_async::{Read, Write};
 
 use serde::Serialize;
 
+use tosca_embedded::cors::Cors;
+use tosca_embedded::stream::write_chunks;
+
 /// A response which transmits a concise JSON message over the network to notify
 /// a controller that an operation completed successfully.
 pub struct OkResponse(Response);
@@ -77,6 +83,71 @@ impl InfoResponse {
     }
 }
 
+// Future producing the next chunk of a stream.
+type ChunkFuture = Pin<Box<dyn Future<Output = Option<Vec<u8>>> + Send + Sync + 'static>>;
+
+// Producer of the chunks of a stream.
+type ChunkProducer = Box<dyn FnMut() -> ChunkFuture + Send + Sync + 'static>;
+
+/// A response which transmits a byte stream over the network.
+///
+/// The stream is made of the chunks returned by an asynchronous producer,
+/// which is called repeatedly until it returns `None`. Each chunk is sent
+/// as soon as it is produced, through chunked transfer encoding,
+/// so a stream never needs to be held in memory as a whole.
+/// This allows, for example, a camera to push its frames.
+pub struct StreamResponse {
+    content_type: &'static str,
+    producer: ChunkProducer,
+}
+
+impl StreamResponse {
+    /// Creates a [`StreamResponse`] from a producer of byte chunks.
+    ///
+    /// The content type of the stream is `application/octet-stream`.
+    #[must_use]
+    #[inline]
+    pub fn new<F, Fut>(mut producer: F) -> Self
+    where
+        F: FnMut() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Option<Vec<u8>>> + Send + Sync + 'static,
+    {
+        Self {
+            content_type: "application/octet-stream",
+            producer: Box::new(move || Box::pin(producer())),
+        }
+    }
+
+    /// Sets the content type of the stream.
+    #[must_use]
+    #[inline]
+    pub const fn content_type(mut self, content_type: &'static str) -> Self {
+        self.content_type = content_type;
+        self
+    }
+
+    async fn write<T, const N: usize>(
+        self,
+        conn: &mut Connection<'_, T, N>,
+        cors: Option<&Cors>,
+    ) -> Result<(), Error<T::Error>>
+    where
+        T: Read + Write,
+    {
+        let mut headers: Vec<(&str, &str)> = Vec::from([
+            ("Content-Type", self.content_type),
+            ("Transfer-Encoding", "chunked"),
+        ]);
+        if let Some(cors) = cors {
+            cors.add_headers(&mut headers);
+        }
+
+        conn.initiate_response(200, Some("Ok"), &headers).await?;
+
+        write_chunks(conn, self.producer).await
+    }
+}
+
 /// A response providing details about an error encountered during a
 /// device operation.
 ///
@@ -180,6 +251,30 @@ impl Headers {
         }
     }
 
+    const fn no_content() -> Self {
+        Self {
+            status: 204,
+            message: "No Content",
+            content_type: &[],
+        }
+    }
+
+    const fn unauthorized() -> Self {
+        Self {
+            status: 401,
+            message: "Unauthorized",
+            content_type: &[("WWW-Authenticate", "Basic realm=\"tosca\"")],
+        }
+    }
+
+    const fn service_unavailable() -> Self {
+        Self {
+            status: 503,
+            message: "Service Unavailable",
+            content_type: &[],
+        }
+    }
+
     const fn json() -> Self {
         Self {
             status: 200,
@@ -188,6 +283,14 @@ impl Headers {
         }
     }
 
+    const fn html() -> Self {
+        Self {
+            status: 200,
+            message: "Ok",
+            content_type: &[("Content-Type", "text/html; charset=utf-8")],
+        }
+    }
+
     const fn json_error() -> Self {
         Self {
             status: 500,
@@ -235,6 +338,8 @@ fn json_to_response<T: Serialize>(headers: Headers, value: T) -> Response {
 pub(crate) struct Response {
     headers: Headers,
     body: Body,
+    // Methods sent in the `Allow` header of a `405` response.
+    allow: Option<String>,
 }
 
 impl From<Result<OkResponse, ErrorResponse>> for Response {
@@ -267,6 +372,46 @@ impl From<Result<InfoResponse, ErrorResponse>> for Response {
     }
 }
 
+// The response of a route handler, either a complete message or a stream.
+pub(crate) enum RouteResponse {
+    Message(Response),
+    Stream(StreamResponse),
+}
+
+impl From<Response> for RouteResponse {
+    #[inline]
+    fn from(response: Response) -> RouteResponse {
+        Self::Message(response)
+    }
+}
+
+impl From<Result<StreamResponse, ErrorResponse>> for RouteResponse {
+    #[inline]
+    fn from(result: Result<StreamResponse, ErrorResponse>) -> RouteResponse {
+        match result {
+            Ok(value) => Self::Stream(value),
+            Err(err) => Self::Message(err.0),
+        }
+    }
+}
+
+impl RouteResponse {
+    #[inline]
+    pub(crate) async fn write<T, const N: usize>(
+        self,
+        conn: &mut Connection<'_, T, N>,
+        cors: Option<&Cors>,
+    ) -> Result<(), Error<T::Error>>
+    where
+        T: Read + Write,
+    {
+        match self {
+            Self::Message(response) => response.write(conn, cors).await,
+            Self::Stream(stream) => stream.write(conn, cors).await,
+        }
+    }
+}
+
 impl Response {
     #[inline]
     pub(crate) fn json<T: Serialize>(value: &T) -> Self {
@@ -277,27 +422,33 @@ impl Response {
     pub(crate) async fn write<T, const N: usize>(
         self,
         conn: &mut Connection<'_, T, N>,
+        cors: Option<&Cors>,
     ) -> Result<(), Error<T::Error>>
     where
         T: Read + Write,
     {
-        self.write_from_ref(conn).await
+        self.write_from_ref(conn, cors).await
     }
 
     #[inline]
     pub(crate) async fn write_from_ref<T, const N: usize>(
         &self,
         conn: &mut Connection<'_, T, N>,
+        cors: Option<&Cors>,
     ) -> Result<(), Error<T::Error>>
     where
         T: Read + Write,
     {
-        conn.initiate_response(
-            self.headers.status,
-            Some(self.headers.message),
-            self.headers.content_type,
-        )
-        .await?;
+        let mut headers: Vec<(&str, &str)> = self.headers.content_type.to_vec();
+        if let Some(allow) = &self.allow {
+            headers.push(("Allow", allow));
+        }
+        if let Some(cors) = cors {
+            cors.add_headers(&mut headers);
+        }
+
+        conn.initiate_response(self.headers.status, Some(self.headers.message), &headers)
+            .await?;
 
         conn.write_all(&self.body.0).await
     }
@@ -313,7 +464,36 @@ impl Response {
         )
     }
 
+    pub(crate) fn not_allowed_with_methods(methods: String) -> Self {
+        let mut response = Self::not_allowed();
+        response.allow = Some(methods);
+        response
+    }
+
+    pub(crate) const fn no_content() -> Self {
+        Response::new(Headers::no_content(), Body::empty())
+    }
+
+    pub(crate) const fn html(page: &'static str) -> Self {
+        Response::new(Headers::html(), Body::static_ref(page.as_bytes()))
+    }
+
+    pub(crate) const fn unauthorized() -> Self {
+        Response::new(Headers::unauthorized(), Body::empty())
+    }
+
+    pub(crate) const fn service_unavailable(description: &'static str) -> Self {
+        Response::new(
+            Headers::service_unavailable(),
+            Body::static_ref(description.as_bytes()),
+        )
+    }
+
     const fn new(headers: Headers, body: Body) -> Response {
-        Self { headers, body }
+        Self {
+            headers,
+            body,
+            allow: None,
+        }
     }
 }
@@ -1,5 +1,4 @@
 use core::fmt::{Debug, Display};
-use core::net::SocketAddr;
 use core::pin::Pin;
 
 use alloc::borrow::Cow;
@@ -9,12 +8,22 @@ use alloc::str::SplitTerminator;
 use alloc::string::ToString;
 use alloc::vec::Vec;
 
+use tosca::events::EVENTS_STREAM_ROUTE;
 use tosca::parameters::{
     ParameterKind, ParameterPayload, ParameterValue, ParametersPayloads as ToscaParametersPayloads,
-    ParametersValues,
 };
 use tosca::route::{RestKind, RouteConfig};
 
+use tosca_embedded::auth::BasicAuth;
+use tosca_embedded::body::{BodyError, read_body};
+use tosca_embedded::cors::Cors;
+use tosca_embedded::mdns::server_properties;
+use tosca_embedded::net::select_bind_address;
+use tosca_embedded::parameters::{ParametersError, bound_parameter_value, parse_body_parameters};
+use tosca_embedded::provisioning::CredentialsPayload;
+use tosca_embedded::route::{RouteMatch, allow_header, match_route};
+use tosca_embedded::shutdown::until_shutdown;
+
 use edge_http::io::Body;
 use edge_http::io::server::{Connection, Handler, Server as EdgeServer};
 use edge_http::{Headers, Method};
@@ -23,17 +32,27 @@ use edge_nal_embassy::{Tcp, TcpBuffers};
 
 use embassy_executor::Spawner;
 use embassy_net::Stack;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::mutex::Mutex;
+use embassy_sync::signal::Signal;
+use embassy_time::Duration;
 
 use embedded_io_async::{Read, Write};
 
 use log::{error, info};
 
+use crate::budget::ByteBudget;
 use crate::device::{Device, InternalDevice};
 use crate::error::Error;
+use crate::events::EVENTS_HUB;
 use crate::mdns::Mdns;
-use crate::net::get_ip;
+use crate::net::{get_ip, get_ipv6};
 use crate::parameters::ParametersPayloads;
-use crate::response::{ErrorResponse, InfoResponse, OkResponse, Response, SerialResponse};
+use crate::provisioning::{PROVISIONING_PAGE, PROVISIONING_ROUTE, store_credentials};
+use crate::response::{
+    ErrorResponse, InfoResponse, OkResponse, Response, RouteResponse, SerialResponse,
+    StreamResponse,
+};
 use crate::state::{State, ValueFromRef};
 
 // Default port.
@@ -55,8 +74,16 @@ const DEFAULT_SERVER_PORT: u16 = 80;
 // at the `Server::run` call site where it arises.
 const NUMBER_OF_CLIENTS: usize = 2;
 
-// Maximum request size in bytes.
-const MAXIMUM_REQUEST_SIZE: usize = 128;
+// Default maximum request body size in bytes.
+const DEFAULT_MAXIMUM_BODY_SIZE: usize = 128;
+
+// Interval, in seconds, between the keep-alive comments sent over an idle
+// events stream.
+const EVENTS_STREAM_KEEP_ALIVE: u64 = 15;
+
+// Held for the whole lifetime of the events stream, so that at most one
+// client at a time can consume the events changes.
+static EVENTS_STREAM: Mutex<CriticalSectionRawMutex, ()> = Mutex::new(());
 
 pub(crate) type OkFn = Box<
     dyn Fn(
@@ -121,6 +148,32 @@ pub(crate) type InfoStateFn<S> = Box<
         + 'static,
 >;
 
+pub(crate) type StreamFn = Box<
+    dyn Fn(
+            ParametersPayloads,
+        ) -> Pin<
+            Box<dyn Future<Output = Result<StreamResponse, ErrorResponse>> + Send + Sync + 'static>,
+        > + Send
+        + Sync
+        + 'static,
+>;
+
+pub(crate) type StreamStateFn<S> = Box<
+    dyn Fn(
+            State<S>,
+            ParametersPayloads,
+        ) -> Pin<
+            Box<dyn Future<Output = Result<StreamResponse, ErrorResponse>> + Send + Sync + 'static>,
+        > + Send
+        + Sync
+        + 'static,
+>;
+
+pub(crate) type ValidatorFn<S> =
+    Box<dyn Fn(&S, &ParametersPayloads) -> Result<(), ErrorResponse> + Send + Sync + 'static>;
+
+pub(crate) type Validators<S> = tosca_embedded::validators::Validators<ValidatorFn<S>>;
+
 pub(crate) type Functions<S> = (
     Vec<OkFn>,
     Vec<OkStateFn<S>>,
@@ -128,6 +181,8 @@ pub(crate) type Functions<S> = (
     Vec<SerialStateFn<S>>,
     Vec<InfoFn>,
     Vec<InfoStateFn<S>>,
+    Vec<StreamFn>,
+    Vec<StreamStateFn<S>>,
 );
 
 #[derive(Clone, Copy)]
@@ -138,6 +193,8 @@ pub(crate) enum FuncType {
     SerialStateful,
     InfoStateless,
     InfoStateful,
+    StreamStateless,
+    StreamStateful,
 }
 
 #[derive(Clone, Copy)]
@@ -186,6 +243,73 @@ fn with_timeout<T>(timeout_ms: u32, io: T) -> WithTimeout<T> {
 ///   interrupted by timeouts.
 ///   See [`Server::handler_timeout()`].
 ///
+/// - **`events_stream`**
+///   Whether the device events are streamed as Server-Sent Events on the
+///   `GET {main_route}/events/stream` route.
+///   The default value is `false`.
+///   See [`Server::events_stream()`].
+///
+/// - **`connection_byte_budget`**
+///   Optional maximum number of bytes read on a single connection,
+///   across all the requests sent over it.
+///   The default value is `None`, meaning the number of bytes read on
+///   a connection is unlimited.
+///   See [`Server::connection_byte_budget()`].
+///
+/// - **`max_body_size`**
+///   Maximum size, in bytes, of a request body containing route parameters.
+///   The default value is `128`.
+///   See [`Server::max_body_size()`].
+///
+/// - **`strict_parameters`**
+///   Whether route parameter values outside their `[min, max]` bounds are
+///   rejected instead of being clamped to them.
+///   The default value is `false`.
+///   See [`Server::strict_parameters()`].
+///
+/// - **`cors`**
+///   Optional origin allowed to access the device from a browser through
+///   Cross-Origin Resource Sharing (CORS).
+///   The default value is `None`, meaning no CORS headers are sent.
+///   See [`Server::cors()`].
+///
+/// - **`basic_auth`**
+///   Optional credentials required through HTTP basic authentication
+///   to access the device routes.
+///   The default value is `None`, meaning no authentication is required.
+///   See [`Server::basic_auth()`].
+///
+/// - **`rate_limit`**
+///   Optional maximum number of requests served per second on each
+///   connection.
+///   The default value is `None`, meaning requests are not limited.
+///   See [`Server::rate_limit()`].
+///
+/// - **`provisioning`**
+///   Whether the `Wi-Fi` provisioning route is served on `/provisioning`.
+///   The default value is `false`.
+///   See [`Server::provisioning()`].
+///
+/// - **`ipv6`**
+///   Whether the server is also reachable on the `IPV6` address of
+///   the network stack, when it has one.
+///   The default value is `false`, meaning only `IPV4` is used.
+///   See [`Server::ipv6()`].
+///
+/// ## Route Parameters
+///
+/// The parameters of a `GET` route are read from the route path, while
+/// the ones of `PUT`, `POST`, and `DELETE` routes are read from
+/// a JSON request body, which is parsed in the same way for all these
+/// methods. A request for a route without parameters might have no body.
+///
+/// ## Stream Routes
+///
+/// A route returning a [`StreamResponse`] keeps its connection busy until
+/// the stream ends, with the same budget as the events stream described in
+/// [`Server::events_stream()`]. The [`Server::handler_timeout()`] also
+/// interrupts a stream when it expires.
+///
 /// ## Known Issue
 ///
 /// In `edge-net`
@@ -212,8 +336,14 @@ where
     io_timeout_ms: Option<u32>,
     // Handler timeout.
     handler_timeout_ms: Option<u32>,
+    // Maximum bytes read on a connection.
+    connection_byte_budget: Option<usize>,
+    // Maximum requests per second sent over a connection.
+    rate_limit: Option<u32>,
     // Https scheme.
     is_https: bool,
+    // IPV6 binding.
+    ipv6: bool,
 }
 
 impl<const TX_SIZE: usize, const RX_SIZE: usize, const MAXIMUM_HEADERS_COUNT: usize, S>
@@ -231,7 +361,10 @@ where
             keepalive_timeout_ms: None,
             io_timeout_ms: None,
             handler_timeout_ms: None,
+            connection_byte_budget: None,
+            rate_limit: None,
             is_https: false,
+            ipv6: false,
         }
     }
 
@@ -264,6 +397,132 @@ where
         self
     }
 
+    /// Sets the maximum number of bytes (headers and bodies) read on
+    /// a single connection.
+    ///
+    /// The budget is shared by all the requests sent over a keep-alive
+    /// connection, so a client cannot pipeline an unlimited number of
+    /// requests, even when each of them respects the maximum request size.
+    ///
+    /// Once the budget is spent, the client receives a
+    /// `413 Payload Too Large` response and the connection is closed.
+    /// A new connection starts with a full budget.
+    #[must_use]
+    pub const fn connection_byte_budget(mut self, bytes: usize) -> Self {
+        self.connection_byte_budget = Some(bytes);
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of a request body containing
+    /// route parameters.
+    ///
+    /// Requests with a larger body are rejected with an error response.
+    /// The body is buffered in memory while its parameters are parsed,
+    /// so this value bounds the memory allocated for each request.
+    #[must_use]
+    pub const fn max_body_size(mut self, bytes: usize) -> Self {
+        self.handler.max_body_size = bytes;
+        self
+    }
+
+    /// Rejects route parameter values outside their `[min, max]` bounds.
+    ///
+    /// By default, out-of-bounds values are clamped to the bounds declared
+    /// for their parameter, so handlers always receive values within them.
+    /// In strict mode, requests carrying such values receive an
+    /// invalid data response instead, and handlers are not called.
+    #[must_use]
+    pub const fn strict_parameters(mut self) -> Self {
+        self.handler.strict_parameters = true;
+        self
+    }
+
+    /// Streams the device events as Server-Sent Events on the
+    /// `GET {main_route}/events/stream` route.
+    ///
+    /// This allows controllers to receive events changes without an
+    /// `MQTT` broker. Each time an event value changes, all events are
+    /// written as a `text/event-stream` frame.
+    ///
+    /// ## Connection Budget
+    ///
+    /// The server handles only two simultaneous connections, and an open
+    /// stream holds one of them for as long as the client stays connected.
+    /// Hence, a single stream is served at a time, while further stream
+    /// requests are answered with `503 Service Unavailable`, leaving the
+    /// other connection to the remaining routes.
+    ///
+    /// A stream is also closed when the [`Server::handler_timeout()`]
+    /// expires, so the handler timeout should not be set when streaming.
+    #[must_use]
+    pub const fn events_stream(mut self) -> Self {
+        self.handler.events_stream = true;
+        self
+    }
+
+    /// Allows the given origin to access the device from a browser through
+    /// Cross-Origin Resource Sharing (CORS).
+    ///
+    /// CORS headers are added to every response, and `OPTIONS` preflight
+    /// requests are answered with `204 No Content`.
+    /// The allowed methods are the ones of the device routes.
+    ///
+    /// The `*` wildcard origin allows any origin.
+    #[must_use]
+    pub fn cors(mut self, origin: &'static str) -> Self {
+        self.handler.cors = Some(Cors::new(
+            origin,
+            self.handler
+                .device
+                .route_configs
+                .iter()
+                .map(|route| route.rest_kind),
+        ));
+        self
+    }
+
+    /// Requires HTTP basic authentication with the given credentials.
+    ///
+    /// Every request, except the one for the `/` device description,
+    /// must carry a matching `Authorization` header, otherwise
+    /// a `401 Unauthorized` response is sent.
+    ///
+    /// Credentials travel in clear text, so basic authentication should be
+    /// combined with `HTTPS`.
+    #[must_use]
+    pub fn basic_auth(mut self, user: &'static str, password: &'static str) -> Self {
+        self.handler.basic_auth = Some(BasicAuth::new(user, password));
+        self
+    }
+
+    /// Limits the number of requests served per second on each connection.
+    ///
+    /// Requests over the limit receive a `429 Too Many Requests` response
+    /// with a `Retry-After` header and the connection is closed, so that
+    /// a misbehaving client cannot monopolize the few sockets of the device.
+    /// A new connection starts with no counted requests.
+    #[must_use]
+    pub const fn rate_limit(mut self, max_per_second: u32) -> Self {
+        self.rate_limit = Some(max_per_second);
+        self
+    }
+
+    /// Serves the `Wi-Fi` provisioning route.
+    ///
+    /// A `GET /provisioning` request returns a page for entering the
+    /// `Wi-Fi` credentials, which are sent back as JSON through
+    /// a `POST /provisioning` request with `ssid` and `password` fields.
+    ///
+    /// The received credentials are returned by
+    /// [`wait_for_credentials`](crate::provisioning::wait_for_credentials),
+    /// so that the firmware can switch the device from
+    /// [`Wifi::start_ap`](crate::wifi::Wifi::start_ap) to station mode.
+    #[must_use]
+    pub const fn provisioning(mut self) -> Self {
+        self.handler.provisioning = true;
+        self
+    }
+
     /// Sets the scheme to `HTTPS`.
     #[must_use]
     pub const fn https(mut self) -> Self {
@@ -271,6 +530,18 @@ where
         self
     }
 
+    /// Serves the device on its `IPV6` address too, when available.
+    ///
+    /// When the network stack has an `IPV6` address, the server binds the
+    /// unspecified `IPV6` address, so it accepts connections on both its
+    /// `IPV4` and `IPV6` addresses, and the [`Mdns`] responder advertises
+    /// both of them. Otherwise, the server falls back to `IPV4` only.
+    #[must_use]
+    pub const fn ipv6(mut self) -> Self {
+        self.ipv6 = true;
+        self
+    }
+
     /// Runs the server and the [`Mdns`] task.
     ///
     /// # Errors
@@ -279,6 +550,29 @@ where
     /// - Failed to spawn the [`Mdns`] task
     /// - Failed to run the server
     pub async fn run(self, stack: Stack<'static>, spawner: Spawner) -> Result<(), Error> {
+        // This signal is never raised, so the server runs forever.
+        let never = Signal::new();
+        self.run_until(stack, spawner, &never).await
+    }
+
+    /// Runs the server and the [`Mdns`] task until the `shutdown` signal
+    /// is raised.
+    ///
+    /// When signaled, the server stops accepting connections, closes its
+    /// listening socket, and returns `Ok(())`, so that the firmware can,
+    /// for example, restart it with a new configuration.
+    ///
+    /// # Errors
+    ///
+    /// - Failed to bind `TCP` protocol buffers to the underlying socket
+    /// - Failed to spawn the [`Mdns`] task
+    /// - Failed to run the server
+    pub async fn run_until(
+        self,
+        stack: Stack<'static>,
+        spawner: Spawner,
+        shutdown: &Signal<CriticalSectionRawMutex, ()>,
+    ) -> Result<(), Error> {
         let Server {
             port,
             handler,
@@ -286,29 +580,36 @@ where
             keepalive_timeout_ms,
             io_timeout_ms,
             handler_timeout_ms,
+            connection_byte_budget,
+            rate_limit,
             is_https,
+            ipv6,
         } = self;
 
         let buffers = TcpBuffers::<NUMBER_OF_CLIENTS, TX_SIZE, RX_SIZE>::new();
         let tcp = Tcp::new(stack, &buffers);
 
         let address = get_ip(stack).await;
-        let socket = SocketAddr::new(address.into(), port);
+        let address_v6 = if ipv6 { get_ipv6(stack) } else { None };
+        let socket = select_bind_address(address, address_v6, ipv6, port);
 
-        let acceptor = tcp.bind(socket).await?;
+        let acceptor = ByteBudget::new(tcp.bind(socket).await?, connection_byte_budget, rate_limit);
 
-        let mdns = if is_https {
-            mdns.properties(&[("scheme", "https")])
-        } else {
-            mdns
-        };
+        // Advertise the device kind and main route, so that controllers
+        // can filter devices without requesting their description.
+        let kind: &'static str = handler.device.kind.clone().leak();
+        let properties = server_properties(kind, handler.device.main_route, is_https);
+        let mdns = mdns.append_properties(&properties);
 
         // Run mdns.
         //
         // NOTE: Use the same server port for the mDNS-SD service
-        mdns.run(stack, address, port, spawner)?;
+        mdns.run(stack, address, address_v6, port, spawner)?;
 
-        info!("Starting server on address `{address}` and port `{port}`");
+        info!(
+            "Starting server on address `{}` and port `{port}`",
+            socket.ip()
+        );
 
         match (io_timeout_ms, handler_timeout_ms) {
             (Some(ta), Some(th)) => {
@@ -316,16 +617,31 @@ where
                     keepalive_timeout_ms,
                     with_timeout(ta, acceptor),
                     with_timeout(th, handler),
+                    shutdown,
                 )
                 .await
             }
             (Some(ta), None) => {
-                Self::run_server(keepalive_timeout_ms, with_timeout(ta, acceptor), handler).await
+                Self::run_server(
+                    keepalive_timeout_ms,
+                    with_timeout(ta, acceptor),
+                    handler,
+                    shutdown,
+                )
+                .await
             }
             (None, Some(th)) => {
-                Self::run_server(keepalive_timeout_ms, acceptor, with_timeout(th, handler)).await
+                Self::run_server(
+                    keepalive_timeout_ms,
+                    acceptor,
+                    with_timeout(th, handler),
+                    shutdown,
+                )
+                .await
+            }
+            (None, None) => {
+                Self::run_server(keepalive_timeout_ms, acceptor, handler, shutdown).await
             }
-            (None, None) => Self::run_server(keepalive_timeout_ms, acceptor, handler).await,
         }
     }
 
@@ -333,6 +649,7 @@ where
         keepalive_timeout_ms: Option<u32>,
         acceptor: A,
         handler: H,
+        shutdown: &Signal<CriticalSectionRawMutex, ()>,
     ) -> Result<(), Error>
     where
         A: edge_nal::TcpAccept,
@@ -341,20 +658,36 @@ where
     {
         let mut server = EdgeServer::<NUMBER_OF_CLIENTS, RX_SIZE, MAXIMUM_HEADERS_COUNT>::new();
 
-        // Run server.
-        server
-            .run(keepalive_timeout_ms, acceptor, handler)
-            .await
-            .map_err(core::convert::Into::into)
+        // Run server until the shutdown signal is raised.
+        //
+        // Dropping the server future drops the acceptor too, which closes
+        // the listening socket.
+        until_shutdown(
+            server.run(keepalive_timeout_ms, acceptor, handler),
+            shutdown,
+        )
+        .await
+        .map_err(core::convert::Into::into)
+    }
+}
+
+// Returns the REST kind of a request method, if any.
+const fn rest_kind(method: Method) -> Option<RestKind> {
+    match method {
+        Method::Get => Some(RestKind::Get),
+        Method::Put => Some(RestKind::Put),
+        Method::Post => Some(RestKind::Post),
+        Method::Delete => Some(RestKind::Delete),
+        _ => None,
     }
 }
 
-const fn method_map(ascot_method: RestKind) -> Method {
-    match ascot_method {
-        RestKind::Get => Method::Get,
-        RestKind::Put => Method::Put,
-        RestKind::Post => Method::Post,
-        RestKind::Delete => Method::Delete,
+fn parameters_error_response(error: &ParametersError) -> Response {
+    match error {
+        ParametersError::Deserialization(e) => {
+            error_response_with_error("Failed to convert bytes into a sequence of parameters", e)
+        }
+        ParametersError::InvalidData(description) => invalid_data_response(description),
     }
 }
 
@@ -400,6 +733,12 @@ where
     S: ValueFromRef + Send + Sync + 'static,
 {
     device: InternalDevice<S>,
+    events_stream: bool,
+    cors: Option<Cors>,
+    basic_auth: Option<BasicAuth>,
+    max_body_size: usize,
+    strict_parameters: bool,
+    provisioning: bool,
 }
 
 impl<S> ServerHandler<S>
@@ -408,116 +747,126 @@ where
 {
     #[inline]
     fn new(device: InternalDevice<S>) -> Self {
-        Self { device }
+        Self {
+            device,
+            events_stream: false,
+            cors: None,
+            basic_auth: None,
+            max_body_size: DEFAULT_MAXIMUM_BODY_SIZE,
+            strict_parameters: false,
+            provisioning: false,
+        }
     }
 
-    async fn analyze_route<const N: usize, T: Read>(
-        &self,
-        method: Method,
-        path: &str,
-        headers: &Headers<'_, N>,
-        body: &mut Body<'_, T>,
-    ) -> Result<RouteInfo, Response> {
+    fn is_events_stream(&self, method: Method, path: &str) -> bool {
         // If the last character of a path ends with '/', remove it.
         let path = path.strip_suffix('/').unwrap_or(path);
 
-        info!("Complete path: {path}");
-
-        // Create an iterator to parse the route in an iterative way. This
-        // function removes the trailing '/' which might be present in route
-        // definition.
-        let mut route_iter = path.split_terminator('/');
+        self.events_stream
+            && method == Method::Get
+            && path
+                .strip_prefix(self.device.main_route)
+                .is_some_and(|route| route == EVENTS_STREAM_ROUTE)
+    }
 
-        // Every time a `nth(0)` function is being called, the iterator
-        // consumes the current element.
-        //
-        // The first 0-element of a route is always an empty path
-        // because **each** path begins with a '/'.
-        //
-        // In case of error, return a not found route.
-        let empty_path = route_iter.nth(0).ok_or_else(Response::not_found)?;
+    async fn stream_events<T, const N: usize>(
+        conn: &mut Connection<'_, T, N>,
+        cors: Option<&Cors>,
+    ) -> Result<(), edge_http::io::Error<T::Error>>
+    where
+        T: Read + Write,
+    {
+        // The lock will be released when the stream is closed.
+        let Ok(_stream) = EVENTS_STREAM.try_lock() else {
+            error!("An events stream is already open");
+            return Response::service_unavailable("An events stream is already open")
+                .write(conn, cors)
+                .await;
+        };
 
-        // If the empty path is equal to the route path,
-        // the route is not correct. This might happen when a route
-        // path makes use of a wrong separator, both for route subpaths, but
-        // also for its parameters.
-        //
-        // In case of error, return a not found route.
-        if empty_path == path {
-            return Err(Response::not_found());
+        let mut headers: Vec<(&str, &str)> = Vec::from([
+            ("Content-Type", "text/event-stream"),
+            ("Cache-Control", "no-cache"),
+        ]);
+        if let Some(cors) = cors {
+            cors.add_headers(&mut headers);
         }
 
-        // Retrieve the main route.
-        let main_route_path = route_iter.nth(0).ok_or_else(Response::not_found)?;
-
-        // If the subpath is not equal to the main route,
-        // the route is not correct. Starts from the 1-index
-        // in order to skip the "/" placed before the main route.
-        if main_route_path != &self.device.main_route[1..] {
-            return Err(Response::not_found());
-        }
+        conn.initiate_response(200, Some("Ok"), &headers).await?;
 
-        let mut route_index = self.device.route_configs.len();
-        for (index, route) in self.device.route_configs.iter().enumerate() {
-            // If the request REST method is different from the route
-            // method, skip to the next route.
-            if method != method_map(route.rest_kind) {
-                continue;
-            }
+        info!("Events stream opened");
 
-            let route_path = &route.data.path[1..];
+        // The stream ends when writing fails, which happens when the client
+        // closes the connection. Keep-alive comments on an idle stream
+        // detect it, releasing the stream and the connection slot.
+        EVENTS_HUB
+            .stream(conn, Duration::from_secs(EVENTS_STREAM_KEEP_ALIVE))
+            .await
+    }
 
-            // Create iterators for both the route and request paths
-            // to compare each segment.
-            let mut route_path_iter = route_path.split_terminator('/');
-            let mut path_iter = path.split_terminator('/');
+    async fn analyze_route<const N: usize, T: Read>(
+        &self,
+        method: Method,
+        path: &str,
+        headers: &Headers<'_, N>,
+        body: &mut Body<'_, T>,
+    ) -> Result<RouteInfo, Response> {
+        // If the last character of a path ends with '/', remove it.
+        let path = path.strip_suffix('/').unwrap_or(path);
 
-            // Skip the empty segment "" and the main route.
-            let _ = path_iter.next();
-            let _ = path_iter.next();
+        info!("Complete path: {path}");
 
-            // Compare route segments with the corresponding path segments.
-            // If all segments match in order, this is the correct route.
-            if !route_path_iter.all(|seg| path_iter.next() == Some(seg)) {
-                continue;
+        let index = match match_route(
+            self.device.main_route,
+            self.device
+                .route_configs
+                .iter()
+                .map(|route| (route.data.path.as_ref(), route.rest_kind)),
+            rest_kind(method),
+            path,
+        ) {
+            RouteMatch::Found(index) => index,
+            RouteMatch::NotAllowed(allowed_methods) => {
+                return Err(Response::not_allowed_with_methods(allow_header(
+                    &allowed_methods,
+                )));
             }
+            RouteMatch::NotFound => return Err(Response::not_found()),
+        };
 
-            info!("Route path: {route_path}");
-
-            for _ in 0..route_path.split_terminator('/').count() {
-                let _ = route_iter.nth(0).ok_or_else(Response::not_found)?;
-            }
+        // Retrieve the route configuration.
+        let Some(route_config) = self.device.route_configs.get_index(index) else {
+            return Err(Response::not_found());
+        };
 
-            // If the route has no parameters, return its index.
-            //
-            // Otherwise, save the index and break the loop,
-            // as there are parameters to analyze.
-            if route.data.parameters.is_empty() {
-                return Ok(RouteInfo::new(index, ToscaParametersPayloads::new()));
-            }
+        let route_path = &route_config.data.path[1..];
+        info!("Route path: {route_path}");
 
-            route_index = index;
-            break;
+        // If the route has no parameters, return its index.
+        if route_config.data.parameters.is_empty() {
+            return Ok(RouteInfo::new(index, ToscaParametersPayloads::new()));
         }
 
-        // Retrieve the route configuration.
-        let route_config = self
-            .device
-            .route_configs
-            .get_index(route_index)
-            .ok_or_else(Response::not_found)?;
+        // Skip the empty segment "", the main route, and the route segments,
+        // so that only the parameters segments are left.
+        let mut route_iter = path.split_terminator('/');
+        let _ = route_iter.nth(route_path.split_terminator('/').count() + 1);
 
         match method {
-            Method::Get => Self::parse_get_parameters(route_config, route_iter),
-            // NOTE: We include the disallowed methods here as well, since
-            // the check has already been performed earlier.
-            _ => Self::parse_headers_parameters(route_config, headers, body).await,
+            Method::Get => self.parse_get_parameters(route_config, route_iter),
+            // NOTE: Disallowed methods never match a route, so only
+            // the `PUT`, `POST`, and `DELETE` methods reach this branch.
+            _ => {
+                self.parse_headers_parameters(route_config, headers, body)
+                    .await
+            }
         }
-        .map(|parameters_payloads| RouteInfo::new(route_index, parameters_payloads))
+        .map(|parameters_payloads| RouteInfo::new(index, parameters_payloads))
     }
 
     #[inline]
     fn parse_get_parameters(
+        &self,
         route_config: &RouteConfig,
         mut route_iter: SplitTerminator<'_, char>,
     ) -> Result<ToscaParametersPayloads<'static>, Response> {
@@ -540,6 +889,13 @@ where
 
             info!("Parameter value as string: {parameter_value}");
             let parameter_value = Self::parse_parameter_value(parameter_value, parameter.1)?;
+            let parameter_value = bound_parameter_value(
+                parameter.0,
+                parameter_value,
+                parameter.1,
+                self.strict_parameters,
+            )
+            .map_err(|e| parameters_error_response(&e))?;
 
             parameters_payloads.add(
                 parameter.0.clone().into(),
@@ -552,12 +908,12 @@ where
         Ok(parameters_payloads)
     }
 
-    #[inline]
-    async fn parse_headers_parameters<const N: usize, T: Read>(
-        route_config: &RouteConfig,
+    // Reads a JSON request body, bounded by the maximum body size.
+    async fn read_json_body<const N: usize, T: Read>(
+        &self,
         headers: &Headers<'_, N>,
         body: &mut Body<'_, T>,
-    ) -> Result<ToscaParametersPayloads<'static>, Response> {
+    ) -> Result<Vec<u8>, Response> {
         info!("Headers: {headers:?}");
 
         let content_length = headers
@@ -571,12 +927,6 @@ where
             )
         })?;
 
-        if content_length > MAXIMUM_REQUEST_SIZE {
-            return Err(error_response(&format!(
-                "The request exceeds the maximum allowed size of {MAXIMUM_REQUEST_SIZE} and cannot be processed"
-            )));
-        }
-
         let content_type = headers
             .content_type()
             .ok_or_else(|| invalid_data_response("No `Content-Type` found"))?;
@@ -587,48 +937,33 @@ where
             ));
         }
 
-        let mut bytes = [0; MAXIMUM_REQUEST_SIZE];
-        let _ = body.read(&mut bytes).await.map_err(|e| {
-            error_response_with_error("Error reading the request bytes", &format!("{e:?}"))
-        })?;
-
-        let route_parameters = serde_json::from_slice::<ParametersValues<'_>>(
-            &bytes[0..content_length],
-        )
-        .map_err(|e| {
-            error_response_with_error(
-                "Failed to convert bytes into a sequence of parameters",
-                &format!("{e}"),
-            )
-        })?;
-
-        info!("Route parameters: {route_parameters:?}");
-
-        let mut parameters_payloads = ToscaParametersPayloads::new();
-        for (parameter_name, parameter_value) in route_parameters {
-            let parameter_kind = route_config
-                .data
-                .parameters
-                .get(&parameter_name)
-                .ok_or_else(|| {
-                    invalid_data_response(&format!("Parameter `{parameter_name}` not found"))
-                })?;
-
-            if !parameter_value.match_kind(parameter_kind) {
-                return Err(invalid_data_response(&format!(
-                    "Found type `{}` for `{parameter_name}`, expected type `{}`",
-                    parameter_value.as_type(),
-                    parameter_kind.as_type(),
-                )));
-            }
+        read_body(body, content_length, self.max_body_size)
+            .await
+            .map_err(|e| match e {
+                BodyError::TooLarge => error_response(&format!(
+                    "The request exceeds the maximum allowed size of {} and cannot be processed",
+                    self.max_body_size
+                )),
+                BodyError::Truncated(received) => invalid_data_response(&format!(
+                    "The request body ended after {received} bytes, expected {content_length} bytes"
+                )),
+                BodyError::Read(e) => {
+                    error_response_with_error("Error reading the request bytes", &format!("{e:?}"))
+                }
+            })
+    }
 
-            parameters_payloads.add(
-                parameter_name,
-                ParameterPayload::new(parameter_kind.clone(), parameter_value),
-            );
-        }
+    #[inline]
+    async fn parse_headers_parameters<const N: usize, T: Read>(
+        &self,
+        route_config: &RouteConfig,
+        headers: &Headers<'_, N>,
+        body: &mut Body<'_, T>,
+    ) -> Result<ToscaParametersPayloads<'static>, Response> {
+        let bytes = self.read_json_body(headers, body).await?;
 
-        Ok(parameters_payloads)
+        parse_body_parameters(route_config, &bytes, self.strict_parameters)
+            .map_err(|e| parameters_error_response(&e))
     }
 
     fn parse_parameter_value(
@@ -651,6 +986,12 @@ where
             ParameterKind::U64 { .. } | ParameterKind::RangeU64 { .. } => {
                 Self::into_value::<u64, _>(parameter_value, "u64", ParameterValue::U64)
             }
+            ParameterKind::RangeI32 { .. } => {
+                Self::into_value::<i32, _>(parameter_value, "i32", ParameterValue::I32)
+            }
+            ParameterKind::RangeI64 { .. } => {
+                Self::into_value::<i64, _>(parameter_value, "i64", ParameterValue::I64)
+            }
             ParameterKind::F32 { .. } => {
                 Self::into_value::<f32, _>(parameter_value, "f32", ParameterValue::F32)
             }
@@ -660,6 +1001,16 @@ where
             ParameterKind::CharsSequence { .. } => Ok(ParameterValue::CharsSequence(Cow::Owned(
                 parameter_value.to_string(),
             ))),
+            ParameterKind::Enum { allowed, .. } => {
+                if !allowed.iter().any(|value| value == parameter_value) {
+                    return Err(invalid_data_response(&format!(
+                        "`{parameter_value}` is not one of the allowed values {allowed:?}"
+                    )));
+                }
+                Ok(ParameterValue::CharsSequence(Cow::Owned(
+                    parameter_value.to_string(),
+                )))
+            }
         }
     }
 
@@ -690,42 +1041,68 @@ where
         &self,
         index: usize,
         parameters_payloads: ParametersPayloads,
-    ) -> Response {
+    ) -> RouteResponse {
+        // Run the route validator, if any, before calling the handler.
+        if let Some(validator) = self.device.validators.get(index)
+            && let Err(error) = validator(&self.device.state.0, &parameters_payloads)
+        {
+            return error.0.into();
+        }
+
         let func_index = self.device.index_array[index];
 
         match func_index.func_type {
             FuncType::OkStateless => {
                 let func = &self.device.routes_functions.0[func_index.index];
-                func(parameters_payloads).await.into()
+                Response::from(func(parameters_payloads).await).into()
             }
             FuncType::OkStateful => {
                 let func = &self.device.routes_functions.1[func_index.index];
-                func(
-                    State(S::value_from_ref(&self.device.state.0)),
-                    parameters_payloads,
+                Response::from(
+                    func(
+                        State(S::value_from_ref(&self.device.state.0)),
+                        parameters_payloads,
+                    )
+                    .await,
                 )
-                .await
                 .into()
             }
             FuncType::SerialStateless => {
                 let func = &self.device.routes_functions.2[func_index.index];
-                func(parameters_payloads).await.into()
+                Response::from(func(parameters_payloads).await).into()
             }
             FuncType::SerialStateful => {
                 let func = &self.device.routes_functions.3[func_index.index];
-                func(
-                    State(S::value_from_ref(&self.device.state.0)),
-                    parameters_payloads,
+                Response::from(
+                    func(
+                        State(S::value_from_ref(&self.device.state.0)),
+                        parameters_payloads,
+                    )
+                    .await,
                 )
-                .await
                 .into()
             }
             FuncType::InfoStateless => {
                 let func = &self.device.routes_functions.4[func_index.index];
-                func(parameters_payloads).await.into()
+                Response::from(func(parameters_payloads).await).into()
             }
             FuncType::InfoStateful => {
                 let func = &self.device.routes_functions.5[func_index.index];
+                Response::from(
+                    func(
+                        State(S::value_from_ref(&self.device.state.0)),
+                        parameters_payloads,
+                    )
+                    .await,
+                )
+                .into()
+            }
+            FuncType::StreamStateless => {
+                let func = &self.device.routes_functions.6[func_index.index];
+                func(parameters_payloads).await.into()
+            }
+            FuncType::StreamStateful => {
+                let func = &self.device.routes_functions.7[func_index.index];
                 func(
                     State(S::value_from_ref(&self.device.state.0)),
                     parameters_payloads,
@@ -736,11 +1113,42 @@ where
         }
     }
 
-    const fn is_method_allowed(method: Method) -> bool {
-        !matches!(
-            method,
-            Method::Get | Method::Post | Method::Put | Method::Delete
-        )
+    async fn provisioning_response<const N: usize, T: Read>(
+        &self,
+        method: Method,
+        headers: &Headers<'_, N>,
+        body: &mut Body<'_, T>,
+    ) -> Response {
+        match method {
+            Method::Get => Response::html(PROVISIONING_PAGE),
+            Method::Post => match self.receive_credentials(headers, body).await {
+                Ok(()) => OkResponse::new().0,
+                Err(response) => response,
+            },
+            _ => Response::not_allowed_with_methods(allow_header(&[RestKind::Get, RestKind::Post])),
+        }
+    }
+
+    async fn receive_credentials<const N: usize, T: Read>(
+        &self,
+        headers: &Headers<'_, N>,
+        body: &mut Body<'_, T>,
+    ) -> Result<(), Response> {
+        let bytes = self.read_json_body(headers, body).await?;
+
+        let credentials = serde_json::from_slice::<CredentialsPayload>(&bytes)
+            .map_err(|e| {
+                error_response_with_error(
+                    "Failed to convert bytes into credentials",
+                    &format!("{e}"),
+                )
+            })?
+            .into_credentials()
+            .map_err(|e| invalid_data_response(&format!("{e}")))?;
+
+        store_credentials(credentials)
+            .await
+            .map_err(|e| invalid_data_response(&format!("{e}")))
     }
 }
 
@@ -759,13 +1167,38 @@ impl<S: ValueFromRef + Send + Sync + 'static> Handler for ServerHandler<S> {
         T: Read + Write,
     {
         let (headers, body) = conn.split();
+        let cors = self.cors.as_ref();
+
+        // Answer CORS preflight requests.
+        if cors.is_some() && headers.method == Method::Options {
+            return Response::no_content().write(conn, cors).await;
+        }
 
         if headers.path == "/" {
-            return self.device.main_route_response.write_from_ref(conn).await;
+            return self
+                .device
+                .main_route_response
+                .write_from_ref(conn, cors)
+                .await;
+        }
+
+        if self.basic_auth.as_ref().is_some_and(|basic_auth| {
+            !basic_auth.is_authorized(headers.headers.get("Authorization"))
+        }) {
+            return Response::unauthorized().write(conn, cors).await;
         }
 
-        if Self::is_method_allowed(headers.method) {
-            return Response::not_allowed().write(conn).await;
+        if self.is_events_stream(headers.method, headers.path) {
+            return Self::stream_events(conn, cors).await;
+        }
+
+        if self.provisioning
+            && headers.path.strip_suffix('/').unwrap_or(headers.path) == PROVISIONING_ROUTE
+        {
+            let response = self
+                .provisioning_response(headers.method, &headers.headers, body)
+                .await;
+            return response.write(conn, cors).await;
         }
 
         let route_info = match self
@@ -773,7 +1206,7 @@ impl<S: ValueFromRef + Send + Sync + 'static> Handler for ServerHandler<S> {
             .await
         {
             Ok(index) => index,
-            Err(response) => return response.write(conn).await,
+            Err(response) => return response.write(conn, cors).await,
         };
 
         let RouteInfo {
@@ -782,6 +1215,6 @@ impl<S: ValueFromRef + Send + Sync + 'static> Handler for ServerHandler<S> {
         } = route_info;
 
         let response = self.run_function(index, parameters_payloads).await;
-        response.write(conn).await
+        response.write(conn, cors).await
     }
 }
@@ -4,17 +4,26 @@ use esp_hal::peripherals::WIFI;
 
 use esp_radio::Controller;
 use esp_radio::wifi::{
-    ClientConfig, Config, Interfaces, ModeConfig, WifiController, WifiEvent, WifiStaState,
-    sta_state,
+    AccessPointConfig, AuthMethod, ClientConfig, Config, Interfaces, ModeConfig, WifiController,
+    WifiDevice, WifiEvent, WifiStaState, sta_state,
 };
 
-use log::{error, info};
+use log::{info, warn};
+
+use tosca_embedded::backoff::ReconnectBackoff;
 
 use crate::error::{Error, ErrorKind, Result};
 use crate::mk_static;
+use crate::provisioning::Credentials;
 
 pub(crate) const WIFI_RECONNECT_DELAY: u64 = 2;
 
+// Minimum length of a `WPA2` access point password.
+const MINIMUM_AP_PASSWORD_LENGTH: usize = 8;
+
+// Maximum delay, in seconds, between two consecutive connection attempts.
+const MAXIMUM_WIFI_RECONNECT_DELAY: u64 = 60;
+
 /// The `Wi-Fi` controller.
 ///
 /// Configures and establishes a connection to a `Wi-Fi` access point.
@@ -47,6 +56,10 @@ impl Wifi {
 
     /// Connects a device to a `Wi-Fi` access point.
     ///
+    /// A background task keeps the device connected: when the link drops,
+    /// the connection is attempted again with an exponential backoff,
+    /// and the network stack recovers its IP configuration afterward.
+    ///
     /// # Errors
     ///
     /// - Missing `Wi-Fi` SSID
@@ -54,42 +67,150 @@ impl Wifi {
     /// - Failed to configure the `Wi-Fi` settings
     /// - Failed to spawn the task for connecting the device to the access
     ///   point via `Wi-Fi`.
-    pub async fn connect(mut self, ssid: &str, password: &str) -> Result<Interfaces<'static>> {
+    pub async fn connect(self, ssid: &str, password: &str) -> Result<Interfaces<'static>> {
+        connect_station(self.controller, self.spawner, ssid, password).await?;
+        Ok(self.interfaces)
+    }
+
+    /// Starts a `Wi-Fi` access point, used to provision a device
+    /// without credentials.
+    ///
+    /// Returns the [`AccessPoint`], which switches the device to station
+    /// mode once credentials are available, and the access point network
+    /// interface. Since no `DHCP` server runs on the device, the network
+    /// stack of the access point interface needs a static IP configuration.
+    ///
+    /// # Errors
+    ///
+    /// - Missing `Wi-Fi` SSID
+    /// - `Wi-Fi` password shorter than 8 characters
+    /// - Failed to configure or start the access point
+    pub async fn start_ap(
+        mut self,
+        ssid: &str,
+        password: &str,
+    ) -> Result<(AccessPoint, WifiDevice<'static>)> {
         if ssid.is_empty() {
             return Err(Error::new(ErrorKind::WiFi, "Missing Wi-Fi SSID"));
         }
 
-        if password.is_empty() {
-            return Err(Error::new(ErrorKind::WiFi, "Missing Wi-Fi password"));
+        if password.len() < MINIMUM_AP_PASSWORD_LENGTH {
+            return Err(Error::new(
+                ErrorKind::WiFi,
+                "The Wi-Fi password must contain at least 8 characters",
+            ));
         }
 
-        let client_config = ModeConfig::Client(
-            ClientConfig::default()
+        let access_point_config = ModeConfig::AccessPoint(
+            AccessPointConfig::default()
                 .with_ssid(ssid.into())
-                .with_password(password.into()),
+                .with_password(password.into())
+                .with_auth_method(AuthMethod::Wpa2Personal),
         );
 
-        self.controller.set_config(&client_config)?;
+        self.controller.set_config(&access_point_config)?;
+        self.controller.start_async().await?;
 
-        self.spawner.spawn(connect(self.controller))?;
+        info!("Wi-Fi access point `{ssid}` started");
 
-        // Wait until Wi-Fi is connected.
-        while sta_state() != WifiStaState::Connected {
-            embassy_time::Timer::after_millis(100).await;
-        }
+        let Interfaces { sta, ap, .. } = self.interfaces;
 
-        Ok(self.interfaces)
+        Ok((
+            AccessPoint {
+                _esp_radio_controller: self._esp_radio_controller,
+                controller: self.controller,
+                sta,
+                spawner: self.spawner,
+            },
+            ap,
+        ))
+    }
+}
+
+/// A running `Wi-Fi` access point.
+///
+/// Created by [`Wifi::start_ap`].
+pub struct AccessPoint {
+    _esp_radio_controller: &'static Controller<'static>,
+    controller: WifiController<'static>,
+    sta: WifiDevice<'static>,
+    spawner: Spawner,
+}
+
+impl AccessPoint {
+    /// Stops the access point and connects the device to a `Wi-Fi` access
+    /// point with the given [`Credentials`], usually obtained through
+    /// [`wait_for_credentials`](crate::provisioning::wait_for_credentials).
+    ///
+    /// Returns the station network interface.
+    ///
+    /// # Errors
+    ///
+    /// - Failed to stop the access point
+    /// - Failed to configure the `Wi-Fi` settings
+    /// - Failed to spawn the task for connecting the device to the access
+    ///   point via `Wi-Fi`.
+    pub async fn connect(mut self, credentials: &Credentials) -> Result<WifiDevice<'static>> {
+        self.controller.stop_async().await?;
+
+        info!("Wi-Fi access point stopped");
+
+        connect_station(
+            self.controller,
+            self.spawner,
+            credentials.ssid(),
+            credentials.password(),
+        )
+        .await?;
+
+        Ok(self.sta)
     }
 }
 
+// Configures the station mode and spawns the task which keeps the device
+// connected, waiting for the first connection.
+async fn connect_station(
+    mut controller: WifiController<'static>,
+    spawner: Spawner,
+    ssid: &str,
+    password: &str,
+) -> Result<()> {
+    if ssid.is_empty() {
+        return Err(Error::new(ErrorKind::WiFi, "Missing Wi-Fi SSID"));
+    }
+
+    if password.is_empty() {
+        return Err(Error::new(ErrorKind::WiFi, "Missing Wi-Fi password"));
+    }
+
+    let client_config = ModeConfig::Client(
+        ClientConfig::default()
+            .with_ssid(ssid.into())
+            .with_password(password.into()),
+    );
+
+    controller.set_config(&client_config)?;
+
+    spawner.spawn(connect(controller))?;
+
+    // Wait until Wi-Fi is connected.
+    while sta_state() != WifiStaState::Connected {
+        embassy_time::Timer::after_millis(100).await;
+    }
+
+    Ok(())
+}
+
 #[embassy_executor::task]
 async fn connect(mut wifi_controller: WifiController<'static>) {
     info!("Wi-Fi connection task started");
+    let mut backoff = ReconnectBackoff::new(WIFI_RECONNECT_DELAY, MAXIMUM_WIFI_RECONNECT_DELAY);
     loop {
         if sta_state() == WifiStaState::Connected {
             wifi_controller
                 .wait_for_event(WifiEvent::StaDisconnected)
                 .await;
+            warn!("Wi-Fi disconnected, reconnecting...");
             embassy_time::Timer::after_secs(WIFI_RECONNECT_DELAY).await;
         }
 
@@ -105,9 +226,14 @@ async fn connect(mut wifi_controller: WifiController<'static>) {
 
         info!("Attempting to connect...");
         if let Err(e) = wifi_controller.connect_async().await {
-            error!("Wi-Fi connect failed: {e:?}");
-            embassy_time::Timer::after_secs(WIFI_RECONNECT_DELAY).await;
+            let delay = backoff.failed();
+            warn!(
+                "Wi-Fi connection attempt {} failed: {e:?}, retrying in {delay}s",
+                backoff.failed_attempts()
+            );
+            embassy_time::Timer::after_secs(delay).await;
         } else {
+            backoff.reset();
             info!("Wi-Fi connected!");
         }
     }
@@ -1,13 +1,7 @@
-#[cfg(target_os = "linux")]
-mod os_mac {
-    use std::fs;
-    use std::path::Path;
-
-    use tracing::warn;
-
-    const IFACE_TYPE_ETHERNET: u16 = 1;
-    const IFACE_TYPE_WIFI: u16 = 801;
-
+// Heuristics identifying virtual interfaces, shared by the platforms which
+// enumerate the network interfaces on their own.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod virtual_interface {
     // Known MAC OUIs for common virtual machine vendors.
     // Source: https://standards-oui.ieee.org/oui.txt and common known VM vendors.
     const VM_MAC_PREFIXES: &[[u8; 3]] = &[
@@ -30,13 +24,10 @@ mod os_mac {
         VM_MAC_PREFIXES.iter().any(|prefix| prefix == &mac[0..3])
     }
 
-    fn is_virtual_interface(iface_path: &Path, mac: [u8; 6]) -> bool {
-        // If the interface does not have a "device" entry,
-        // it is considered virtual.
-        if !iface_path.join("device").exists() {
-            return true;
-        }
-
+    // Checks whether the MAC address or the name of an interface identify
+    // it as virtual, given the name prefixes of the virtual interfaces
+    // of a platform.
+    pub(super) fn is_virtual(name: &str, mac: [u8; 6], name_prefixes: &[&str]) -> bool {
         // Checks if the MAC address is locally administered
         // (bit 1 of the first byte is set).
         // A locally administered address is one assigned by software rather
@@ -54,9 +45,101 @@ mod os_mac {
             return true;
         }
 
+        name_prefixes.iter().any(|prefix| name.starts_with(prefix))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{
+            VM_MAC_PREFIXES, is_locally_administered_mac, is_virtual, is_virtual_mac_vendor,
+        };
+
+        #[test]
+        fn test_is_locally_administered_mac() {
+            assert!(is_locally_administered_mac([0x02, 0, 0, 0, 0, 0]));
+            assert!(is_locally_administered_mac([0xFE, 0, 0, 0, 0, 0]));
+        }
+
+        #[test]
+        fn test_is_not_locally_administered_mac() {
+            assert!(!is_locally_administered_mac([0x00, 0, 0, 0, 0, 0]));
+            assert!(!is_locally_administered_mac([0xFC, 0, 0, 0, 0, 0]));
+        }
+
+        #[test]
+        fn test_is_virtual_mac_vendor() {
+            for prefix in VM_MAC_PREFIXES {
+                let mac = [prefix[0], prefix[1], prefix[2], 0, 0, 0];
+
+                // Ensures all known VM prefixes are detected.
+                //
+                // Failure means a known prefix was not matched
+                assert!(
+                    is_virtual_mac_vendor(mac),
+                    "Failed for prefix {prefix:02X?}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_is_not_virtual_mac_vendor() {
+            assert!(!is_virtual_mac_vendor([0x00, 0x1A, 0x2B, 0, 0, 0]));
+            assert!(!is_virtual_mac_vendor([0xFF, 0xFF, 0xFF, 0, 0, 0]));
+        }
+
+        #[test]
+        fn test_is_virtual() {
+            const PREFIXES: &[&str] = &["docker", "veth"];
+            const PHYSICAL_MAC: [u8; 6] = [0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E];
+
+            assert!(!is_virtual("eth0", PHYSICAL_MAC, PREFIXES));
+            assert!(is_virtual("docker0", PHYSICAL_MAC, PREFIXES));
+            assert!(is_virtual("veth1a2b", PHYSICAL_MAC, PREFIXES));
+            assert!(is_virtual("eth0", [0x02, 0, 0, 0, 0, 0], PREFIXES));
+            assert!(is_virtual("eth0", [0x00, 0x50, 0x56, 0, 0, 0], PREFIXES));
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod os_mac {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use tracing::warn;
+
+    use super::{InterfaceKind, virtual_interface};
+
+    const IFACE_TYPE_ETHERNET: u16 = 1;
+    const IFACE_TYPE_WIFI: u16 = 801;
+
+    // Name prefixes of common container and virtual network interfaces.
+    const VIRTUAL_PREFIXES: &[&str] = &["docker", "br-", "veth", "tun", "tap", "vmnet"];
+
+    // Network interface metadata read from its sysfs directory.
+    struct InterfaceInfo {
+        name: String,
+        mac: [u8; 6],
+        iface_type: u16,
+        // Whether the interface has a "wireless" subdirectory.
+        wireless: bool,
+        // Whether the interface has a "device" entry.
+        has_device: bool,
+        canonical_path: Option<PathBuf>,
+    }
+
+    fn is_virtual_interface(iface: &InterfaceInfo) -> bool {
+        // If the interface does not have a "device" entry,
+        // it is considered virtual.
+        if !iface.has_device {
+            return true;
+        }
+
         // Canonical path checks for virtual devices or hypervisor interfaces.
-        if let Ok(canon) = fs::canonicalize(iface_path)
-            && let Some(s) = canon.to_str()
+        if let Some(s) = iface
+            .canonical_path
+            .as_deref()
+            .and_then(|canon| canon.to_str())
         {
             // Virtual devices path.
             if s.contains("/sys/devices/virtual/") {
@@ -77,20 +160,26 @@ mod os_mac {
             // those interfaces are correctly recognized as virtual.
         }
 
-        if let Some(name) = iface_path.file_name().and_then(|n| n.to_str()) {
-            // Exclude loopback interface "lo".
-            if name == "lo" {
-                return true;
-            }
+        // Exclude loopback interface "lo".
+        if iface.name == "lo" {
+            return true;
+        }
 
-            // Exclude common container and virtual network interface prefixes.
-            let prefixes = ["docker", "br-", "veth", "tun", "tap", "vmnet"];
-            if prefixes.iter().any(|p| name.starts_with(p)) {
-                return true;
-            }
+        virtual_interface::is_virtual(&iface.name, iface.mac, VIRTUAL_PREFIXES)
+    }
+
+    fn classify_interface(iface: &InterfaceInfo) -> Option<InterfaceKind> {
+        // Skip interface if it is virtual.
+        if is_virtual_interface(iface) {
+            return None;
         }
 
-        false
+        // Classify based on wireless flag and interface type.
+        match (iface.wireless, iface.iface_type) {
+            (true, IFACE_TYPE_WIFI) => Some(InterfaceKind::Wifi),
+            (false, IFACE_TYPE_ETHERNET) => Some(InterfaceKind::Ethernet),
+            _ => None,
+        }
     }
 
     fn read_mac(iface_path: &Path) -> Option<[u8; 6]> {
@@ -121,88 +210,303 @@ mod os_mac {
             .and_then(|s| s.trim().parse::<u16>().ok())
     }
 
-    fn is_wireless(iface_path: &Path) -> bool {
-        // A wireless interface has a "wireless" subdirectory.
-        iface_path.join("wireless").exists()
-    }
+    fn read_interface(iface_path: &Path) -> Option<InterfaceInfo> {
+        let name = iface_path.file_name()?.to_str()?.to_string();
+
+        // Skip interface if MAC is invalid or unreadable.
+        let mac = read_mac(iface_path)?;
+
+        // Skip if interface type is unknown or unparsable.
+        let iface_type = get_interface_type(iface_path)?;
 
-    pub(crate) fn get_mac_addresses() -> (Option<[u8; 6]>, Option<[u8; 6]>) {
-        let mut wifi_mac = None;
-        let mut ethernet_mac = None;
+        Some(InterfaceInfo {
+            name,
+            mac,
+            iface_type,
+            wireless: iface_path.join("wireless").exists(),
+            has_device: iface_path.join("device").exists(),
+            canonical_path: fs::canonicalize(iface_path).ok(),
+        })
+    }
 
+    pub(crate) fn get_all_mac_addresses() -> Vec<(InterfaceKind, [u8; 6], String)> {
         // Root directory for network interfaces metadata on Linux.
         let net_dir = Path::new("/sys/class/net");
 
         let Ok(entries) = fs::read_dir(net_dir) else {
             warn!("Unable to read {}.", net_dir.display());
-            return (None, None);
+            return Vec::new();
         };
 
         // Iterate over each directory entry representing a network interface.
-        for entry in entries.flatten() {
-            let iface_path = entry.path();
+        let mut interfaces: Vec<_> = entries
+            .flatten()
+            .filter_map(|entry| read_interface(&entry.path()))
+            .filter_map(|iface| {
+                classify_interface(&iface).map(|kind| (kind, iface.mac, iface.name))
+            })
+            .collect();
+
+        // Directory entries are not ordered, so sort the interfaces by name
+        // to always return them in the same order.
+        interfaces.sort_by(|a, b| a.2.cmp(&b.2));
+
+        interfaces
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::path::PathBuf;
+
+        use super::{
+            IFACE_TYPE_ETHERNET, IFACE_TYPE_WIFI, InterfaceInfo, InterfaceKind, classify_interface,
+        };
+
+        const PHYSICAL_MAC: [u8; 6] = [0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E];
+
+        fn interface(name: &str, iface_type: u16, wireless: bool, path: &str) -> InterfaceInfo {
+            InterfaceInfo {
+                name: name.into(),
+                mac: PHYSICAL_MAC,
+                iface_type,
+                wireless,
+                has_device: true,
+                canonical_path: Some(PathBuf::from(path)),
+            }
+        }
+
+        #[test]
+        fn test_classify_physical_interfaces() {
+            let ethernet = interface(
+                "enp3s0",
+                IFACE_TYPE_ETHERNET,
+                false,
+                "/sys/devices/pci0000:00/0000:00:1c.0/net/enp3s0",
+            );
+            let wifi = interface(
+                "wlp2s0",
+                IFACE_TYPE_WIFI,
+                true,
+                "/sys/devices/pci0000:00/0000:00:1d.0/net/wlp2s0",
+            );
+
+            assert_eq!(classify_interface(&ethernet), Some(InterfaceKind::Ethernet));
+            assert_eq!(classify_interface(&wifi), Some(InterfaceKind::Wifi));
+        }
+
+        #[test]
+        fn test_filter_virtual_interfaces() {
+            let mut no_device = interface("eth1", IFACE_TYPE_ETHERNET, false, "/sys/devices/pci");
+            no_device.has_device = false;
+
+            let mut locally_administered =
+                interface("eth2", IFACE_TYPE_ETHERNET, false, "/sys/devices/pci");
+            locally_administered.mac = [0x02, 0x42, 0xAC, 0x11, 0x00, 0x02];
+
+            let interfaces = [
+                no_device,
+                locally_administered,
+                interface(
+                    "lo",
+                    IFACE_TYPE_ETHERNET,
+                    false,
+                    "/sys/devices/virtual/net/lo",
+                ),
+                interface("docker0", IFACE_TYPE_ETHERNET, false, "/sys/devices/pci"),
+                interface(
+                    "eth3",
+                    IFACE_TYPE_ETHERNET,
+                    false,
+                    "/sys/devices/LNXSYSTM:00/VMBUS:00/net/eth3",
+                ),
+            ];
+
+            for iface in &interfaces {
+                assert_eq!(classify_interface(iface), None, "Failed for {}", iface.name);
+            }
+        }
+
+        #[test]
+        fn test_filter_mismatched_types() {
+            // A wireless flag with an Ethernet type, and vice versa.
+            let interfaces = [
+                interface("wlan0", IFACE_TYPE_ETHERNET, true, "/sys/devices/pci"),
+                interface("eth0", IFACE_TYPE_WIFI, false, "/sys/devices/pci"),
+            ];
+
+            for iface in &interfaces {
+                assert_eq!(classify_interface(iface), None, "Failed for {}", iface.name);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[allow(unsafe_code)]
+mod os_mac {
+    use std::ffi::CStr;
+    use std::{ptr, slice};
+
+    use libc::{AF_LINK, IFF_LOOPBACK, freeifaddrs, getifaddrs, ifaddrs, sockaddr_dl};
+
+    use tracing::warn;
+
+    use super::{InterfaceKind, virtual_interface};
+
+    // Link-layer type of Ethernet interfaces, also reported by Wi-Fi ones.
+    const IFT_ETHER: u8 = 0x06;
+
+    // Name of the built-in Wi-Fi interface.
+    const WIFI_INTERFACE: &str = "en0";
+
+    // Name prefixes of loopback, tunnel, bridge, and other virtual
+    // interfaces created by macOS or by virtualization software.
+    const VIRTUAL_PREFIXES: &[&str] = &[
+        "lo", "utun", "awdl", "llw", "bridge", "gif", "stf", "anpi", "ap", "ipsec", "ppp",
+        "vmenet", "vmnet", "feth",
+    ];
+
+    // Classifies a physical interface from its name and link-layer type.
+    //
+    // Wi-Fi interfaces report the same link-layer type of Ethernet ones,
+    // hence the built-in Wi-Fi interface is recognized by its name.
+    fn classify_interface(name: &str, link_type: u8) -> Option<InterfaceKind> {
+        if link_type != IFT_ETHER || !name.starts_with("en") {
+            return None;
+        }
+
+        if name == WIFI_INTERFACE {
+            Some(InterfaceKind::Wifi)
+        } else {
+            Some(InterfaceKind::Ethernet)
+        }
+    }
+
+    // Reads the MAC address stored in a link-layer address.
+    //
+    // SAFETY: `addr` must come from `getifaddrs`, so that its data extends
+    // over the interface name and the link-layer address.
+    unsafe fn read_mac(addr: &sockaddr_dl) -> Option<[u8; 6]> {
+        if addr.sdl_alen != 6 {
+            return None;
+        }
+
+        // The link-layer address follows the interface name, and it might
+        // exceed the declared size of `sdl_data`.
+        //
+        // SAFETY: `getifaddrs` allocates `sdl_nlen + sdl_alen` bytes of data.
+        let bytes = unsafe {
+            slice::from_raw_parts(
+                addr.sdl_data
+                    .as_ptr()
+                    .cast::<u8>()
+                    .add(usize::from(addr.sdl_nlen)),
+                6,
+            )
+        };
+
+        let mut mac = [0u8; 6];
+        mac.copy_from_slice(bytes);
+        Some(mac)
+    }
 
-            // Skip interface if MAC is invalid or unreadable.
-            let Some(mac) = read_mac(&iface_path) else {
+    pub(crate) fn get_all_mac_addresses() -> Vec<(InterfaceKind, [u8; 6], String)> {
+        let mut mac_addresses = Vec::new();
+
+        let mut interfaces: *mut ifaddrs = ptr::null_mut();
+
+        // SAFETY: `getifaddrs` fills `interfaces` with a linked list,
+        // which is released by `freeifaddrs` below.
+        if unsafe { getifaddrs(&mut interfaces) } != 0 {
+            warn!("Unable to retrieve the network interfaces.");
+            return Vec::new();
+        }
+
+        let mut current = interfaces;
+        while !current.is_null() {
+            // SAFETY: `current` is a valid node of the list returned by
+            // `getifaddrs`, which is terminated with a null pointer.
+            let iface = unsafe { &*current };
+
+            // Move to next interface in the linked list.
+            current = iface.ifa_next;
+
+            // Only link-layer addresses contain a MAC address.
+            //
+            // SAFETY: A non-null `ifa_addr` points to a valid address.
+            if iface.ifa_addr.is_null()
+                || i32::from(unsafe { (*iface.ifa_addr).sa_family }) != AF_LINK
+            {
                 continue;
-            };
+            }
 
-            // Skip interface if it is virtual.
-            if is_virtual_interface(&iface_path, mac) {
+            // Skip loopback interfaces.
+            if iface.ifa_flags & IFF_LOOPBACK.cast_unsigned() != 0 {
                 continue;
             }
 
-            // Skip if interface type is unknown or unparsable.
-            let Some(iface_type) = get_interface_type(&iface_path) else {
+            // SAFETY: `ifa_name` is a valid null-terminated string.
+            let Ok(name) = unsafe { CStr::from_ptr(iface.ifa_name) }.to_str() else {
+                continue;
+            };
+
+            // SAFETY: An `AF_LINK` address is a `sockaddr_dl` structure.
+            let addr = unsafe { &*iface.ifa_addr.cast::<sockaddr_dl>() };
+
+            // Skip interface if MAC is missing.
+            //
+            // SAFETY: `addr` comes from `getifaddrs`.
+            let Some(mac) = (unsafe { read_mac(addr) }) else {
                 continue;
             };
 
-            // Classify based on wireless flag and interface type.
-            match (is_wireless(&iface_path), iface_type) {
-                (true, t) if t == IFACE_TYPE_WIFI => wifi_mac = Some(mac),
-                (false, t) if t == IFACE_TYPE_ETHERNET => ethernet_mac = Some(mac),
-                _ => {}
+            // Skip interface if it is virtual.
+            if virtual_interface::is_virtual(name, mac, VIRTUAL_PREFIXES) {
+                continue;
+            }
+
+            if let Some(kind) = classify_interface(name, addr.sdl_type) {
+                mac_addresses.push((kind, mac, name.to_string()));
             }
         }
 
-        (wifi_mac, ethernet_mac)
+        // SAFETY: `interfaces` has been allocated by `getifaddrs`.
+        unsafe { freeifaddrs(interfaces) };
+
+        mac_addresses
     }
 
     #[cfg(test)]
     mod tests {
-        use super::{VM_MAC_PREFIXES, is_locally_administered_mac, is_virtual_mac_vendor};
+        use super::{IFT_ETHER, InterfaceKind, classify_interface};
 
-        #[test]
-        fn test_is_locally_administered_mac() {
-            assert!(is_locally_administered_mac([0x02, 0, 0, 0, 0, 0]));
-            assert!(is_locally_administered_mac([0xFE, 0, 0, 0, 0, 0]));
-        }
+        // Link-layer type of bridge interfaces.
+        const IFT_BRIDGE: u8 = 0xD1;
 
         #[test]
-        fn test_is_not_locally_administered_mac() {
-            assert!(!is_locally_administered_mac([0x00, 0, 0, 0, 0, 0]));
-            assert!(!is_locally_administered_mac([0xFC, 0, 0, 0, 0, 0]));
+        fn test_classify_wifi_interface() {
+            assert_eq!(
+                classify_interface("en0", IFT_ETHER),
+                Some(InterfaceKind::Wifi)
+            );
         }
 
         #[test]
-        fn test_is_virtual_mac_vendor() {
-            for prefix in VM_MAC_PREFIXES {
-                let mac = [prefix[0], prefix[1], prefix[2], 0, 0, 0];
-
-                // Ensures all known VM prefixes are detected.
-                //
-                // Failure means a known prefix was not matched
-                assert!(
-                    is_virtual_mac_vendor(mac),
-                    "Failed for prefix {prefix:02X?}"
-                );
-            }
+        fn test_classify_ethernet_interface() {
+            assert_eq!(
+                classify_interface("en1", IFT_ETHER),
+                Some(InterfaceKind::Ethernet)
+            );
+            assert_eq!(
+                classify_interface("en5", IFT_ETHER),
+                Some(InterfaceKind::Ethernet)
+            );
         }
 
         #[test]
-        fn test_is_not_virtual_mac_vendor() {
-            assert!(!is_virtual_mac_vendor([0x00, 0x1A, 0x2B, 0, 0, 0]));
-            assert!(!is_virtual_mac_vendor([0xFF, 0xFF, 0xFF, 0, 0, 0]));
+        fn test_classify_other_interfaces() {
+            assert_eq!(classify_interface("en2", IFT_BRIDGE), None);
+            assert_eq!(classify_interface("utun0", IFT_ETHER), None);
         }
     }
 }
@@ -210,10 +514,13 @@ mod os_mac {
 #[cfg(target_os = "windows")]
 #[allow(unsafe_code)]
 mod os_mac {
+    use std::ffi::CStr;
     use std::{mem, ptr};
 
     use tracing::warn;
 
+    use super::InterfaceKind;
+
     use windows_sys::Win32::Foundation::{ERROR_BUFFER_OVERFLOW, ERROR_SUCCESS};
     use windows_sys::Win32::NetworkManagement::IpHelper::{
         GetAdaptersAddresses, GetIfEntry2, IF_TYPE_ETHERNET_CSMACD, IF_TYPE_IEEE80211,
@@ -239,13 +546,12 @@ mod os_mac {
         }
     }
 
-    // Traverses the adapter linked list and extracts the first matching Wi-Fi
-    // and Ethernet MAC addresses.
+    // Traverses the adapter linked list and extracts the Wi-Fi and Ethernet
+    // MAC addresses.
     fn process_adapter(
         adapter: *mut IP_ADAPTER_ADDRESSES_LH,
-    ) -> (Option<[u8; 6]>, Option<[u8; 6]>) {
-        let mut wifi = None;
-        let mut ethernet = None;
+    ) -> Vec<(InterfaceKind, [u8; 6], String)> {
+        let mut mac_addresses = Vec::new();
 
         let mut current = adapter;
         while !current.is_null() {
@@ -266,15 +572,23 @@ mod os_mac {
             if unsafe { GetIfEntry2(&mut row) } == 0 {
                 if let Some(mac) = extract_mac_from_row(&row) {
                     // Verify interface type and physical medium.
-                    match (row.Type, row.PhysicalMediumType) {
-                        // We intentionally store only the last valid Wi-Fi and
-                        // Ethernet MAC address found, as we're only interested
-                        // in retrieving at least one valid MAC address.
-                        (IF_TYPE_IEEE80211, NDIS_PHYSICAL_MEDIUM_NATIVE802_11) => wifi = Some(mac),
+                    let kind = match (row.Type, row.PhysicalMediumType) {
+                        (IF_TYPE_IEEE80211, NDIS_PHYSICAL_MEDIUM_NATIVE802_11) => {
+                            Some(InterfaceKind::Wifi)
+                        }
                         (IF_TYPE_ETHERNET_CSMACD, NDIS_PHYSICAL_MEDIUM802_3) => {
-                            ethernet = Some(mac)
+                            Some(InterfaceKind::Ethernet)
                         }
-                        _ => {}
+                        _ => None,
+                    };
+
+                    if let Some(kind) = kind {
+                        // SAFETY: `AdapterName` is a valid null-terminated
+                        // string owned by the adapter list.
+                        let name = unsafe { CStr::from_ptr(addr.AdapterName.cast()) }
+                            .to_string_lossy()
+                            .into_owned();
+                        mac_addresses.push((kind, mac, name));
                     }
                 }
             }
@@ -283,10 +597,10 @@ mod os_mac {
             current = addr.Next;
         }
 
-        (wifi, ethernet)
+        mac_addresses
     }
 
-    pub(crate) fn get_mac_addresses() -> (Option<[u8; 6]>, Option<[u8; 6]>) {
+    pub(crate) fn get_all_mac_addresses() -> Vec<(InterfaceKind, [u8; 6], String)> {
         let mut size = 0;
 
         // SAFETY: First call only fills `size` to determine required
@@ -305,12 +619,12 @@ mod os_mac {
         // because it’s used only to determine the required buffer size.
         if error_code != ERROR_BUFFER_OVERFLOW {
             warn!("`GetAdaptersAddresses` returned an error with code: {error_code}.");
-            return (None, None);
+            return Vec::new();
         }
 
         if size == 0 {
             warn!("`GetAdaptersAddresses` returned zero size.");
-            return (None, None);
+            return Vec::new();
         }
 
         let mut buffer = vec![0u8; size as usize];
@@ -324,26 +638,80 @@ mod os_mac {
             process_adapter(adapter)
         } else {
             warn!("Unable to retrieve adapters addresses.");
-            (None, None)
+            Vec::new()
         }
     }
 }
 
-pub(crate) fn get_mac_addresses() -> (Option<[u8; 6]>, Option<[u8; 6]>) {
-    #[cfg(any(target_os = "linux", target_os = "windows"))]
+// Kind of a physical network interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InterfaceKind {
+    Wifi,
+    Ethernet,
+}
+
+// Returns the kind, the MAC address, and the name of every physical
+// network interface.
+pub(crate) fn get_all_mac_addresses() -> Vec<(InterfaceKind, [u8; 6], String)> {
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
     {
-        os_mac::get_mac_addresses()
+        os_mac::get_all_mac_addresses()
     }
 
-    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
     {
-        (None, None)
+        Vec::new()
     }
 }
 
+// Returns the MAC addresses of the first Wi-Fi and the first Ethernet
+// interfaces.
+pub(crate) fn get_mac_addresses() -> (Option<[u8; 6]>, Option<[u8; 6]>) {
+    first_mac_addresses(&get_all_mac_addresses())
+}
+
+fn first_mac_addresses(
+    interfaces: &[(InterfaceKind, [u8; 6], String)],
+) -> (Option<[u8; 6]>, Option<[u8; 6]>) {
+    let first = |kind| {
+        interfaces
+            .iter()
+            .find(|(iface_kind, _, _)| *iface_kind == kind)
+            .map(|(_, mac, _)| *mac)
+    };
+
+    (first(InterfaceKind::Wifi), first(InterfaceKind::Ethernet))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::get_mac_addresses;
+    use super::{InterfaceKind, first_mac_addresses, get_mac_addresses};
+
+    #[test]
+    fn test_first_mac_addresses() {
+        let interfaces = [
+            (InterfaceKind::Ethernet, [0, 0, 0, 0, 0, 1], "eth0".into()),
+            (InterfaceKind::Wifi, [0, 0, 0, 0, 0, 2], "wlan0".into()),
+            (InterfaceKind::Ethernet, [0, 0, 0, 0, 0, 3], "eth1".into()),
+            (InterfaceKind::Wifi, [0, 0, 0, 0, 0, 4], "wlan1".into()),
+        ];
+
+        assert_eq!(
+            first_mac_addresses(&interfaces),
+            (Some([0, 0, 0, 0, 0, 2]), Some([0, 0, 0, 0, 0, 1]))
+        );
+    }
+
+    #[test]
+    fn test_first_mac_addresses_single_kind() {
+        let interfaces = [(InterfaceKind::Ethernet, [0, 0, 0, 0, 0, 1], "eth0".into())];
+
+        assert_eq!(
+            first_mac_addresses(&interfaces),
+            (None, Some([0, 0, 0, 0, 0, 1]))
+        );
+        assert_eq!(first_mac_addresses(&[]), (None, None));
+    }
 
     // This test only runs on systems that have physical MAC addresses.
     // Systems with virtual MAC interfaces, such as CI environments,
@@ -1,6 +1,6 @@
 use serde::Serialize;
 
-use crate::economy::Economy;
+use crate::economy::{Currency, Economy};
 use crate::energy::Energy;
 use crate::events::EventsDescription;
 use crate::route::RouteConfigs;
@@ -42,6 +42,10 @@ pub enum DeviceKind {
     Unknown,
     /// Light.
     Light,
+    /// Sensor.
+    Sensor,
+    /// Thermostat.
+    Thermostat,
 }
 
 impl DeviceKindTrait for DeviceKind {
@@ -49,6 +53,8 @@ impl DeviceKindTrait for DeviceKind {
         match self {
             Self::Unknown => "Unknown",
             Self::Light => "Light",
+            Self::Sensor => "Sensor",
+            Self::Thermostat => "Thermostat",
         }
     }
 }
@@ -118,6 +124,62 @@ pub enum DeviceEnvironment {
     Os,
 }
 
+/// Device clock.
+///
+/// Device timestamps, such as the ones of events, are expressed in
+/// milliseconds elapsed since the device boot. A device reports its clock
+/// so that a controller can align those timestamps to the wall-clock time,
+/// and order events coming from different devices.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct DeviceClock {
+    /// Milliseconds elapsed since the device boot when the clock was read.
+    pub uptime_ms: u64,
+    /// Milliseconds elapsed since the Unix epoch when the clock was read.
+    ///
+    /// It is `None` when the device clock is not synchronized with
+    /// a time source.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub unix_time_ms: Option<u64>,
+}
+
+impl DeviceClock {
+    /// Creates an unsynchronized [`DeviceClock`] from the milliseconds
+    /// elapsed since the device boot.
+    #[must_use]
+    pub const fn uptime(uptime_ms: u64) -> Self {
+        Self {
+            uptime_ms,
+            unix_time_ms: None,
+        }
+    }
+
+    /// Sets the milliseconds elapsed since the Unix epoch, marking the
+    /// clock as synchronized.
+    #[must_use]
+    pub const fn unix_time(mut self, unix_time_ms: u64) -> Self {
+        self.unix_time_ms = Some(unix_time_ms);
+        self
+    }
+
+    /// Checks whether the device clock is synchronized with a time source.
+    #[must_use]
+    pub const fn is_synced(&self) -> bool {
+        self.unix_time_ms.is_some()
+    }
+
+    /// Returns the device boot time, in milliseconds elapsed since the
+    /// Unix epoch, if the clock is synchronized.
+    #[must_use]
+    pub const fn boot_time_ms(&self) -> Option<u64> {
+        match self.unix_time_ms {
+            Some(unix_time_ms) => Some(unix_time_ms.saturating_sub(self.uptime_ms)),
+            None => None,
+        }
+    }
+}
+
 /// Device metrics.
 #[derive(Debug, PartialEq, Clone, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -130,6 +192,10 @@ pub struct DeviceMetrics {
     #[serde(skip_serializing_if = "Economy::is_empty")]
     #[serde(default = "Economy::empty")]
     pub economy: Economy,
+    /// Device clock.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub clock: Option<DeviceClock>,
 }
 
 impl DeviceMetrics {
@@ -140,6 +206,7 @@ impl DeviceMetrics {
         Self {
             energy,
             economy: Economy::empty(),
+            clock: None,
         }
     }
 
@@ -150,6 +217,7 @@ impl DeviceMetrics {
         Self {
             energy: Energy::empty(),
             economy,
+            clock: None,
         }
     }
 
@@ -168,6 +236,48 @@ impl DeviceMetrics {
         self.economy = economy;
         self
     }
+
+    /// Adds [`DeviceClock`] data.
+    #[inline]
+    #[must_use]
+    pub fn add_clock(mut self, clock: DeviceClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Estimates the annual cost of running the device for the given hours
+    /// per day, at the given price per kilowatt-hour expressed in the given
+    /// [`Currency`].
+    ///
+    /// The energy cost computed from the [`PowerRating`] data is added to
+    /// the annualized [`Economy`] costs expressed in the same [`Currency`].
+    /// Costs expressed in other currencies are skipped, since no exchange
+    /// rate is known.
+    ///
+    /// Returns [`None`] when the [`PowerRating`] data is missing.
+    ///
+    /// [`PowerRating`]: crate::energy::PowerRating
+    #[must_use]
+    pub fn annual_cost_estimate(
+        &self,
+        price_per_kwh: f64,
+        currency: Currency,
+        hours_per_day: f64,
+    ) -> Option<f64> {
+        let energy_cost = self
+            .energy
+            .estimated_cost(hours_per_day * 365., price_per_kwh)?;
+
+        let costs = self.economy.costs.as_ref().map_or(0., |costs| {
+            costs
+                .iter()
+                .filter(|cost| cost.currency == currency)
+                .map(|cost| f64::from(cost.amount) * cost.timespan.per_year())
+                .sum()
+        });
+
+        Some(energy_cost + costs)
+    }
 }
 
 /// Device data.
@@ -201,6 +311,54 @@ impl DeviceData {
     }
 }
 
+/// An invalid device main route found by [`validate_main_route`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MainRouteError {
+    /// The main route has no name.
+    Empty,
+    /// The main route does not start with a `/`.
+    MissingLeadingSlash,
+    /// The main route ends with a `/`.
+    TrailingSlash,
+}
+
+impl core::fmt::Display for MainRouteError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Empty => "The main route has no name",
+            Self::MissingLeadingSlash => "The main route does not start with a `/`",
+            Self::TrailingSlash => "The main route ends with a `/`",
+        }
+        .fmt(f)
+    }
+}
+
+impl core::error::Error for MainRouteError {}
+
+/// Validates a device main route.
+///
+/// A main route must start with a `/`, followed by a non-empty name
+/// without a trailing `/`, such as `/light`.
+///
+/// # Errors
+///
+/// Returns a [`MainRouteError`] describing why the main route is invalid.
+pub fn validate_main_route(main_route: &str) -> Result<(), MainRouteError> {
+    if main_route.is_empty() || main_route == "/" {
+        return Err(MainRouteError::Empty);
+    }
+
+    if !main_route.starts_with('/') {
+        return Err(MainRouteError::MissingLeadingSlash);
+    }
+
+    if main_route.ends_with('/') {
+        return Err(MainRouteError::TrailingSlash);
+    }
+
+    Ok(())
+}
+
 /// Device description.
 #[derive(Debug, PartialEq, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -238,6 +396,23 @@ impl DeviceDescription {
         }
     }
 
+    /// Sets the device main route, after checking it through
+    /// [`validate_main_route`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MainRouteError`] when the main route is invalid,
+    /// leaving the [`DeviceDescription`] unchanged.
+    #[inline]
+    pub fn with_validated_main_route(
+        &mut self,
+        main_route: &'static str,
+    ) -> Result<&mut Self, MainRouteError> {
+        validate_main_route(main_route)?;
+        self.main_route = alloc::borrow::Cow::Borrowed(main_route);
+        Ok(self)
+    }
+
     /// Sets the device environment.
     #[must_use]
     pub const fn environment(mut self, environment: DeviceEnvironment) -> Self {
@@ -284,14 +459,17 @@ impl DeviceDescription {
 mod tests {
     use crate::route::{Route, RouteConfigs};
 
-    use crate::economy::{Cost, CostTimespan, Costs, Economy, Roi, Rois};
+    use crate::economy::{Cost, CostTimespan, Costs, Currency, Economy, Roi, Rois};
     use crate::energy::{
         CarbonFootprint, CarbonFootprints, Energy, EnergyClass, EnergyEfficiencies,
-        EnergyEfficiency, WaterUseEfficiency,
+        EnergyEfficiency, PowerRating, WaterUseEfficiency,
     };
     use crate::{deserialize, serialize};
 
-    use super::{DeviceDescription, DeviceEnvironment, DeviceKind, DeviceKindId, DeviceMetrics};
+    use super::{
+        DeviceClock, DeviceDescription, DeviceEnvironment, DeviceKind, DeviceKindId, DeviceMetrics,
+        MainRouteError, validate_main_route,
+    };
 
     fn energy() -> Energy {
         let energy_efficiencies =
@@ -324,9 +502,55 @@ mod tests {
             .insert(Route::put("Off", "/off").serialize_data())
     }
 
+    #[test]
+    fn test_valid_main_routes() {
+        assert_eq!(validate_main_route("/light"), Ok(()));
+        assert_eq!(validate_main_route("/living-room/light"), Ok(()));
+
+        let mut description = DeviceDescription::new(
+            DeviceKindId::from(&DeviceKind::Light),
+            "/light",
+            routes(),
+            2,
+        );
+        assert!(description.with_validated_main_route("/lamp").is_ok());
+        assert_eq!(description.main_route, "/lamp");
+    }
+
+    #[test]
+    fn test_invalid_main_routes() {
+        assert_eq!(validate_main_route(""), Err(MainRouteError::Empty));
+        assert_eq!(validate_main_route("/"), Err(MainRouteError::Empty));
+        assert_eq!(
+            validate_main_route("light"),
+            Err(MainRouteError::MissingLeadingSlash)
+        );
+        assert_eq!(
+            validate_main_route("/light/"),
+            Err(MainRouteError::TrailingSlash)
+        );
+
+        let mut description = DeviceDescription::new(
+            DeviceKindId::from(&DeviceKind::Light),
+            "/light",
+            routes(),
+            2,
+        );
+        assert_eq!(
+            description.with_validated_main_route("lamp/").unwrap_err(),
+            MainRouteError::MissingLeadingSlash
+        );
+        assert_eq!(description.main_route, "/light");
+    }
+
     #[test]
     fn test_device_kind() {
-        for device_kind in &[DeviceKind::Unknown, DeviceKind::Light] {
+        for device_kind in &[
+            DeviceKind::Unknown,
+            DeviceKind::Light,
+            DeviceKind::Sensor,
+            DeviceKind::Thermostat,
+        ] {
             assert_eq!(
                 deserialize::<DeviceKind>(serialize(device_kind)),
                 *device_kind
@@ -361,6 +585,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_annual_cost_estimate() {
+        // 100 W for 10 hours a day: 365 kWh in a year.
+        let device_metrics =
+            DeviceMetrics::with_energy(Energy::init_with_power_rating(PowerRating::new(100.)));
+        let estimate = device_metrics
+            .annual_cost_estimate(0.5, Currency::Usd, 10.)
+            .unwrap();
+        assert!((estimate - 182.5).abs() < 1e-6);
+
+        // Costs are annualized: 100 a week and 1000 a month.
+        let device_metrics = device_metrics.add_economy(economy());
+        let estimate = device_metrics
+            .annual_cost_estimate(0.5, Currency::Usd, 10.)
+            .unwrap();
+        assert!((estimate - (182.5 + 5200. + 12_000.)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_annual_cost_estimate_mixed_currencies() {
+        let costs = Costs::init(Cost::new_with_currency(
+            100,
+            Currency::Eur,
+            CostTimespan::Year,
+        ))
+        .insert(Cost::new_with_currency(
+            10_000,
+            Currency::Jpy,
+            CostTimespan::Year,
+        ));
+        let device_metrics =
+            DeviceMetrics::with_energy(Energy::init_with_power_rating(PowerRating::new(100.)))
+                .add_economy(Economy::init_with_costs(costs));
+
+        // Only the costs in the price currency are added.
+        let estimate = device_metrics
+            .annual_cost_estimate(0.5, Currency::Eur, 10.)
+            .unwrap();
+        assert!((estimate - (182.5 + 100.)).abs() < 1e-6);
+
+        let estimate = device_metrics
+            .annual_cost_estimate(0.5, Currency::Jpy, 10.)
+            .unwrap();
+        assert!((estimate - (182.5 + 10_000.)).abs() < 1e-6);
+
+        let estimate = device_metrics
+            .annual_cost_estimate(0.5, Currency::Usd, 10.)
+            .unwrap();
+        assert!((estimate - 182.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_annual_cost_estimate_missing_data() {
+        // No power rating.
+        assert_eq!(
+            DeviceMetrics::with_energy(energy())
+                .add_economy(economy())
+                .annual_cost_estimate(0.5, Currency::Usd, 10.),
+            None
+        );
+        assert_eq!(
+            DeviceMetrics::with_economy(economy()).annual_cost_estimate(0.5, Currency::Usd, 10.),
+            None
+        );
+    }
+
+    #[test]
+    fn test_device_clock() {
+        let clock = DeviceClock::uptime(5_000);
+        assert!(!clock.is_synced());
+        assert_eq!(clock.boot_time_ms(), None);
+        assert_eq!(
+            serialize(clock),
+            serde_json::json!({
+                "uptime_ms": 5_000,
+            })
+        );
+        assert_eq!(deserialize::<DeviceClock>(serialize(clock)), clock);
+
+        let clock = clock.unix_time(1_700_000_005_000);
+        assert!(clock.is_synced());
+        assert_eq!(clock.boot_time_ms(), Some(1_700_000_000_000));
+        assert_eq!(deserialize::<DeviceClock>(serialize(clock)), clock);
+
+        // Metrics without a clock are still accepted.
+        let device_metrics = DeviceMetrics::with_energy(energy());
+        assert_eq!(
+            deserialize::<DeviceMetrics>(serialize(&device_metrics)),
+            device_metrics
+        );
+
+        let device_metrics = device_metrics.add_clock(clock);
+        assert_eq!(
+            deserialize::<DeviceMetrics>(serialize(&device_metrics)),
+            device_metrics
+        );
+    }
+
     #[test]
     fn test_device_description() {
         let device_description = DeviceDescription::new(
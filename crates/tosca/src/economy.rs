@@ -27,6 +27,15 @@ impl CostTimespan {
             Self::Year => "year",
         }
     }
+
+    // Number of timespans in a year.
+    pub(crate) const fn per_year(self) -> f64 {
+        match self {
+            Self::Week => 52.,
+            Self::Month => 12.,
+            Self::Year => 1.,
+        }
+    }
 }
 
 impl core::fmt::Display for CostTimespan {
@@ -35,16 +44,56 @@ impl core::fmt::Display for CostTimespan {
     }
 }
 
+/// Currency of a device [`Cost`].
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub enum Currency {
+    /// United States dollar.
+    #[default]
+    Usd,
+    /// Euro.
+    Eur,
+    /// Pound sterling.
+    Gbp,
+    /// Japanese yen.
+    Jpy,
+}
+
+impl Currency {
+    /// Returns the ISO 4217 code of the [`Currency`].
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::Usd => "USD",
+            Self::Eur => "EUR",
+            Self::Gbp => "GBP",
+            Self::Jpy => "JPY",
+        }
+    }
+}
+
+impl core::fmt::Display for Currency {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.code().fmt(f)
+    }
+}
+
 /// Device cost.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 pub struct Cost {
-    /// Amount of money in USD currency.
+    /// Amount of money in the cost [`Currency`].
     /// A negative value indicates savings during the considered
     /// timespan, while a positive value indicates expenditures in the
     /// considered timespan.
-    #[serde(rename = "usd")]
-    pub usd_currency: i32,
+    #[serde(alias = "usd")]
+    pub amount: i32,
+    /// Currency of the amount.
+    ///
+    /// It defaults to [`Currency::Usd`] when missing, so that costs which
+    /// only contain an amount in dollars are still accepted.
+    #[serde(default)]
+    pub currency: Currency,
     /// Considered timespan to estimate the costs.
     pub timespan: CostTimespan,
 }
@@ -53,24 +102,32 @@ impl core::fmt::Display for Cost {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
-            "The device {} {} USD in a {} timespan",
-            if self.usd_currency < 0 {
-                "saves"
-            } else {
-                "spends"
-            },
-            self.usd_currency.abs(),
+            "The device {} {} {} in a {} timespan",
+            if self.amount < 0 { "saves" } else { "spends" },
+            self.amount.unsigned_abs(),
+            self.currency,
             self.timespan
         )
     }
 }
 
 impl Cost {
-    /// Creates a [`Cost`].
+    /// Creates a [`Cost`] in [`Currency::Usd`].
+    #[must_use]
+    pub const fn new(amount: i32, timespan: CostTimespan) -> Self {
+        Self::new_with_currency(amount, Currency::Usd, timespan)
+    }
+
+    /// Creates a [`Cost`] in the given [`Currency`].
     #[must_use]
-    pub const fn new(usd_currency: i32, timespan: CostTimespan) -> Self {
+    pub const fn new_with_currency(
+        amount: i32,
+        currency: Currency,
+        timespan: CostTimespan,
+    ) -> Self {
         Self {
-            usd_currency,
+            amount,
+            currency,
             timespan,
         }
     }
@@ -206,7 +263,9 @@ mod tests {
     use crate::energy::EnergyClass;
     use crate::{deserialize, serialize};
 
-    use super::{Cost, CostTimespan, Costs, Roi, Rois};
+    use alloc::string::ToString;
+
+    use super::{Cost, CostTimespan, Costs, Currency, Roi, Rois};
 
     #[test]
     fn test_cost_timespan() {
@@ -225,6 +284,40 @@ mod tests {
         assert_eq!(deserialize::<Cost>(serialize(cost)), cost);
     }
 
+    #[test]
+    fn test_cost_currency() {
+        let cost = Cost::new_with_currency(-25, Currency::Eur, CostTimespan::Month);
+        assert_eq!(
+            serialize(cost),
+            serde_json::json!({
+                "amount": -25,
+                "currency": "Eur",
+                "timespan": "Month",
+            })
+        );
+        assert_eq!(deserialize::<Cost>(serialize(cost)), cost);
+        assert_eq!(
+            cost.to_string(),
+            "The device saves 25 EUR in a month timespan"
+        );
+
+        let cost = Cost::new_with_currency(300, Currency::Jpy, CostTimespan::Year);
+        assert_eq!(deserialize::<Cost>(serialize(cost)), cost);
+        assert_eq!(
+            cost.to_string(),
+            "The device spends 300 JPY in a year timespan"
+        );
+
+        // `new` creates costs in dollars.
+        assert_eq!(Cost::new(10, CostTimespan::Week).currency, Currency::Usd);
+
+        // Costs with only an amount in dollars are still accepted.
+        assert_eq!(
+            deserialize::<Cost>(serde_json::json!({"usd": 10, "timespan": "Week"})),
+            Cost::new(10, CostTimespan::Week)
+        );
+    }
+
     #[test]
     fn test_roi_serde() {
         let roi = Roi::new(10, EnergyClass::A);
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use hashbrown::DefaultHashBuilder;
 
 use indexmap::set::{IndexSet, IntoIter, Iter};
@@ -7,6 +9,9 @@ use serde::Serialize;
 use crate::macros::set;
 
 /// Energy efficiency class.
+///
+/// Classes are ordered by efficiency, so [`EnergyClass::APlusPlusPlus`] is
+/// the greatest class and [`EnergyClass::G`] the smallest one.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 pub enum EnergyClass {
@@ -50,6 +55,44 @@ impl EnergyClass {
             Self::G => "G",
         }
     }
+
+    /// Returns the rank of an [`EnergyClass`], from `0` for
+    /// [`EnergyClass::G`] up to `9` for [`EnergyClass::APlusPlusPlus`].
+    ///
+    /// A higher rank means a more efficient class.
+    #[must_use]
+    pub const fn rank(&self) -> u8 {
+        match self {
+            Self::APlusPlusPlus => 9,
+            Self::APlusPlus => 8,
+            Self::APlus => 7,
+            Self::A => 6,
+            Self::B => 5,
+            Self::C => 4,
+            Self::D => 3,
+            Self::E => 2,
+            Self::F => 1,
+            Self::G => 0,
+        }
+    }
+
+    /// Checks whether an [`EnergyClass`] is more efficient than another one.
+    #[must_use]
+    pub const fn better_than(&self, other: &Self) -> bool {
+        self.rank() > other.rank()
+    }
+}
+
+impl PartialOrd for EnergyClass {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EnergyClass {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
 }
 
 impl core::fmt::Display for EnergyClass {
@@ -123,6 +166,36 @@ set! {
   pub struct EnergyEfficiencies(IndexSet<EnergyEfficiency, DefaultHashBuilder>);
 }
 
+impl EnergyEfficiencies {
+    /// Returns the average of the [`EnergyEfficiency`] percentages.
+    ///
+    /// The average is kept within the `-100..=100` range, and it is
+    /// [`None`] when [`EnergyEfficiencies`] is empty.
+    #[must_use]
+    pub fn average_percentage(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        let sum = self
+            .iter()
+            .map(|energy_efficiency| f64::from(energy_efficiency.percentage))
+            .sum::<f64>();
+        // A set never holds more than `u32::MAX` efficiencies in practice.
+        let len = f64::from(u32::try_from(self.len()).unwrap_or(u32::MAX));
+        Some((sum / len).clamp(-100., 100.))
+    }
+
+    /// Returns the [`EnergyEfficiency`]s of the given [`EnergyClass`],
+    /// following the insertion order.
+    #[must_use]
+    pub fn by_class(&self, class: EnergyClass) -> Vec<EnergyEfficiency> {
+        self.iter()
+            .filter(|energy_efficiency| energy_efficiency.energy_class == class)
+            .copied()
+            .collect()
+    }
+}
+
 /// Carbon footprint.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -264,6 +337,28 @@ impl WaterUseEfficiency {
     }
 }
 
+/// Power rating of a device.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct PowerRating {
+    /// Power drawn by the device while running, in watts.
+    pub watts: f32,
+}
+
+impl PowerRating {
+    /// Creates a [`PowerRating`].
+    #[must_use]
+    pub const fn new(watts: f32) -> Self {
+        Self { watts }
+    }
+
+    /// Returns the energy consumed over the given hours, in kilowatt-hours.
+    #[must_use]
+    pub fn kilowatt_hours(&self, hours: f64) -> f64 {
+        f64::from(self.watts) / 1000. * hours
+    }
+}
+
 /// Energy information of a device.
 #[derive(Debug, PartialEq, Clone, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -280,6 +375,10 @@ pub struct Energy {
     #[serde(rename = "water-use-efficiency")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub water_use_efficiency: Option<WaterUseEfficiency>,
+    /// Power rating.
+    #[serde(rename = "power-rating")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power_rating: Option<PowerRating>,
 }
 
 impl Energy {
@@ -290,6 +389,7 @@ impl Energy {
             energy_efficiencies: None,
             carbon_footprints: None,
             water_use_efficiency: None,
+            power_rating: None,
         }
     }
 
@@ -300,6 +400,7 @@ impl Energy {
             energy_efficiencies: Some(energy_efficiencies),
             carbon_footprints: None,
             water_use_efficiency: None,
+            power_rating: None,
         }
     }
 
@@ -310,6 +411,7 @@ impl Energy {
             energy_efficiencies: None,
             carbon_footprints: Some(carbon_footprints),
             water_use_efficiency: None,
+            power_rating: None,
         }
     }
 
@@ -320,6 +422,18 @@ impl Energy {
             energy_efficiencies: None,
             carbon_footprints: None,
             water_use_efficiency: Some(water_use_efficiency),
+            power_rating: None,
+        }
+    }
+
+    /// Creates a [`Energy`] initialized with the [`PowerRating`] data.
+    #[must_use]
+    pub const fn init_with_power_rating(power_rating: PowerRating) -> Self {
+        Self {
+            energy_efficiencies: None,
+            carbon_footprints: None,
+            water_use_efficiency: None,
+            power_rating: Some(power_rating),
         }
     }
 
@@ -346,12 +460,30 @@ impl Energy {
         self
     }
 
+    /// Adds the [`PowerRating`] data.
+    #[must_use]
+    pub const fn power_rating(mut self, power_rating: PowerRating) -> Self {
+        self.power_rating = Some(power_rating);
+        self
+    }
+
+    /// Estimates the cost of running the device for the given hours, at the
+    /// given price per kilowatt-hour.
+    ///
+    /// Returns [`None`] when the [`PowerRating`] data is missing.
+    #[must_use]
+    pub fn estimated_cost(&self, hours: f64, price_per_kwh: f64) -> Option<f64> {
+        self.power_rating
+            .map(|power_rating| power_rating.kilowatt_hours(hours) * price_per_kwh)
+    }
+
     /// Checks if [`Energy`] is **entirely** empty.
     #[must_use]
     pub const fn is_empty(&self) -> bool {
         self.energy_efficiencies.is_none()
             && self.carbon_footprints.is_none()
             && self.water_use_efficiency.is_none()
+            && self.power_rating.is_none()
     }
 }
 
@@ -364,7 +496,7 @@ mod tests {
 
     use super::{
         CarbonFootprint, CarbonFootprints, EnergyClass, EnergyEfficiencies, EnergyEfficiency,
-        WaterUseEfficiency,
+        PowerRating, WaterUseEfficiency,
     };
 
     fn assert_float_eq(a: f64, b: f64) {
@@ -392,6 +524,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_energy_class_ordering() {
+        assert!(EnergyClass::APlusPlusPlus > EnergyClass::A);
+        assert!(EnergyClass::G < EnergyClass::F);
+        assert!(EnergyClass::APlus.better_than(&EnergyClass::B));
+        assert!(!EnergyClass::B.better_than(&EnergyClass::B));
+        assert_eq!(EnergyClass::APlusPlusPlus.rank(), 9);
+        assert_eq!(EnergyClass::G.rank(), 0);
+
+        let mut energy_classes = [
+            EnergyClass::C,
+            EnergyClass::APlusPlusPlus,
+            EnergyClass::G,
+            EnergyClass::A,
+            EnergyClass::E,
+            EnergyClass::APlus,
+            EnergyClass::F,
+            EnergyClass::B,
+            EnergyClass::APlusPlus,
+            EnergyClass::D,
+        ];
+        energy_classes.sort();
+
+        assert_eq!(
+            energy_classes,
+            [
+                EnergyClass::G,
+                EnergyClass::F,
+                EnergyClass::E,
+                EnergyClass::D,
+                EnergyClass::C,
+                EnergyClass::B,
+                EnergyClass::A,
+                EnergyClass::APlus,
+                EnergyClass::APlusPlus,
+                EnergyClass::APlusPlusPlus,
+            ]
+        );
+    }
+
     #[test]
     fn test_energy_efficiency_serde() {
         let energy_efficiency = EnergyEfficiency::new(100, EnergyClass::A);
@@ -421,6 +593,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_energy_efficiencies_aggregation() {
+        let energy_efficiencies =
+            EnergyEfficiencies::init(EnergyEfficiency::new(-50, EnergyClass::A))
+                .insert(EnergyEfficiency::new(20, EnergyClass::B))
+                .insert(EnergyEfficiency::new(-10, EnergyClass::A));
+
+        assert_float_eq(energy_efficiencies.average_percentage().unwrap(), -40. / 3.);
+        assert_eq!(
+            energy_efficiencies.by_class(EnergyClass::A),
+            [
+                EnergyEfficiency::new(-50, EnergyClass::A),
+                EnergyEfficiency::new(-10, EnergyClass::A),
+            ]
+        );
+        assert_eq!(
+            energy_efficiencies.by_class(EnergyClass::B),
+            [EnergyEfficiency::new(20, EnergyClass::B)]
+        );
+        assert!(energy_efficiencies.by_class(EnergyClass::G).is_empty());
+
+        assert_eq!(EnergyEfficiencies::new().average_percentage(), None);
+    }
+
     #[test]
     fn test_carbon_footprint_serde() {
         let carbon_footprint = CarbonFootprint::new(100, EnergyClass::A);
@@ -486,4 +682,28 @@ mod tests {
 
         assert_eq!(deserialize::<Energy>(serialize(&energy)), energy);
     }
+
+    #[test]
+    fn test_power_rating() {
+        let energy = Energy::init_with_power_rating(PowerRating::new(1500.));
+
+        assert!(!energy.is_empty());
+        assert_eq!(
+            serialize(&energy),
+            serde_json::json!({ "power-rating": { "watts": 1500.0 } })
+        );
+        assert_eq!(deserialize::<Energy>(serialize(&energy)), energy);
+
+        // 1.5 kW for 4 hours at 0.25 per kWh.
+        assert_float_eq(energy.estimated_cost(4., 0.25).unwrap(), 1.5);
+        assert_float_eq(energy.estimated_cost(0., 0.25).unwrap(), 0.);
+
+        assert_eq!(Energy::empty().estimated_cost(4., 0.25), None);
+        assert!(
+            Energy::empty()
+                .power_rating(PowerRating::new(60.))
+                .estimated_cost(10., 0.3)
+                .is_some()
+        );
+    }
 }
@@ -7,21 +7,56 @@ use core::time::Duration;
 
 use serde::Serialize;
 
+/// Event broker address.
+///
+/// It is serialized as a plain string, both for an IP address and a
+/// hostname.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+#[serde(untagged)]
+pub enum BrokerAddress {
+    /// An IPv4 or IPv6 address.
+    Ip(IpAddr),
+    /// A hostname to be resolved through DNS.
+    Host(String),
+}
+
+impl fmt::Display for BrokerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::Ip(address) => address.fmt(f),
+            Self::Host(host) => host.fmt(f),
+        }
+    }
+}
+
 /// Event broker data.
 #[derive(Debug, PartialEq, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 pub struct BrokerData {
     /// Broker address.
-    pub address: IpAddr,
+    pub address: BrokerAddress,
     /// Broker port number.
     pub port: u16,
 }
 
 impl BrokerData {
-    /// Creates a [`BrokerData`] .
+    /// Creates a [`BrokerData`] from an IP address.
     #[must_use]
     pub const fn new(address: IpAddr, port: u16) -> Self {
-        Self { address, port }
+        Self {
+            address: BrokerAddress::Ip(address),
+            port,
+        }
+    }
+
+    /// Creates a [`BrokerData`] from a hostname.
+    #[must_use]
+    pub const fn host(name: String, port: u16) -> Self {
+        Self {
+            address: BrokerAddress::Host(name),
+            port,
+        }
     }
 }
 
@@ -31,13 +66,39 @@ mod private {
     pub trait TypeName {
         const TYPE: &'static str;
     }
+
+    // A fake trait to identify the numeric events.
+    #[doc(hidden)]
+    pub trait Numeric: TypeName + Copy + PartialOrd {}
+}
+
+/// An error occurred while updating the value of an event contained
+/// in [`Events`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventsError {
+    /// The index of the event to be updated.
+    pub index: usize,
+    /// The number of events of the requested kind.
+    pub len: usize,
 }
 
+impl fmt::Display for EventsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "Event index {} is out of range, {} events of this kind are present",
+            self.index, self.len
+        )
+    }
+}
+
+impl core::error::Error for EventsError {}
+
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[cfg_attr(not(feature = "deserialize"), derive(Copy))]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 /// An event of a specific type.
-pub struct Event<T: Clone + Copy + private::TypeName> {
+pub struct Event<T: Clone + private::TypeName> {
     /// Event name.
     #[cfg(not(feature = "deserialize"))]
     pub name: &'static str,
@@ -54,18 +115,38 @@ pub struct Event<T: Clone + Copy + private::TypeName> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<alloc::borrow::Cow<'static, str>>,
 
+    /// Unit of measurement of the event value.
+    #[cfg(not(feature = "deserialize"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<&'static str>,
+    /// Unit of measurement of the event value.
+    #[cfg(feature = "deserialize")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<alloc::borrow::Cow<'static, str>>,
+
+    /// Minimum allowed value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<T>,
+    /// Maximum allowed value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<T>,
+
     /// Event value.
     pub value: T,
 }
 
-impl<T: Clone + Copy + fmt::Display + private::TypeName> fmt::Display for Event<T> {
+impl<T: Clone + fmt::Display + private::TypeName> fmt::Display for Event<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         writeln!(f, "Name: \"{}\"", self.name)?;
         if let Some(description) = &self.description {
             writeln!(f, "Description: \"{description}\"")?;
         }
         writeln!(f, "Type: {}", T::TYPE)?;
-        writeln!(f, "Value: {}", self.value)
+        write!(f, "Value: {}", self.value)?;
+        if let Some(unit) = &self.unit {
+            write!(f, " {unit}")?;
+        }
+        writeln!(f)
     }
 }
 
@@ -79,6 +160,9 @@ impl Event<bool> {
             #[cfg(feature = "deserialize")]
             name: alloc::borrow::Cow::Borrowed(name),
             description: None,
+            unit: None,
+            min: None,
+            max: None,
             value: false,
         }
     }
@@ -98,6 +182,9 @@ impl Event<u8> {
             #[cfg(feature = "deserialize")]
             name: alloc::borrow::Cow::Borrowed(name),
             description: None,
+            unit: None,
+            min: None,
+            max: None,
             value: 0,
         }
     }
@@ -107,6 +194,8 @@ impl private::TypeName for u8 {
     const TYPE: &'static str = "u8";
 }
 
+impl private::Numeric for u8 {}
+
 impl Event<i32> {
     /// Creates an [`Event<i32>`].
     #[must_use]
@@ -117,6 +206,9 @@ impl Event<i32> {
             #[cfg(feature = "deserialize")]
             name: alloc::borrow::Cow::Borrowed(name),
             description: None,
+            unit: None,
+            min: None,
+            max: None,
             value: 0,
         }
     }
@@ -126,6 +218,8 @@ impl private::TypeName for i32 {
     const TYPE: &'static str = "i32";
 }
 
+impl private::Numeric for i32 {}
+
 impl Event<f32> {
     /// Creates an [`Event<f32>`].
     #[must_use]
@@ -136,6 +230,9 @@ impl Event<f32> {
             #[cfg(feature = "deserialize")]
             name: alloc::borrow::Cow::Borrowed(name),
             description: None,
+            unit: None,
+            min: None,
+            max: None,
             value: 0.,
         }
     }
@@ -145,6 +242,8 @@ impl private::TypeName for f32 {
     const TYPE: &'static str = "f32";
 }
 
+impl private::Numeric for f32 {}
+
 impl Event<f64> {
     /// Creates an [`Event<f64>`].
     #[must_use]
@@ -155,6 +254,9 @@ impl Event<f64> {
             #[cfg(feature = "deserialize")]
             name: alloc::borrow::Cow::Borrowed(name),
             description: None,
+            unit: None,
+            min: None,
+            max: None,
             value: 0.,
         }
     }
@@ -164,7 +266,53 @@ impl private::TypeName for f64 {
     const TYPE: &'static str = "f64";
 }
 
-impl<T: Clone + Copy + private::TypeName> Event<T> {
+impl private::Numeric for f64 {}
+
+/// The value of a string [`Event`].
+#[cfg(not(feature = "deserialize"))]
+pub type StrValue = &'static str;
+/// The value of a string [`Event`].
+#[cfg(feature = "deserialize")]
+pub type StrValue = alloc::borrow::Cow<'static, str>;
+
+impl Event<StrValue> {
+    /// Creates an [`Event<StrValue>`] with an empty string as value.
+    #[must_use]
+    pub const fn str(name: &'static str) -> Self {
+        Self {
+            #[cfg(not(feature = "deserialize"))]
+            name,
+            #[cfg(feature = "deserialize")]
+            name: alloc::borrow::Cow::Borrowed(name),
+            description: None,
+            unit: None,
+            min: None,
+            max: None,
+            #[cfg(not(feature = "deserialize"))]
+            value: "",
+            #[cfg(feature = "deserialize")]
+            value: alloc::borrow::Cow::Borrowed(""),
+        }
+    }
+
+    // Updates the event value.
+    #[cfg(not(feature = "deserialize"))]
+    const fn update_str_value(&mut self, value: &'static str) {
+        self.value = value;
+    }
+
+    // Updates the event value.
+    #[cfg(feature = "deserialize")]
+    fn update_str_value(&mut self, value: &'static str) {
+        self.value = alloc::borrow::Cow::Borrowed(value);
+    }
+}
+
+impl private::TypeName for StrValue {
+    const TYPE: &'static str = "str";
+}
+
+impl<T: Clone + private::TypeName> Event<T> {
     /// Sets the event description.
     #[must_use]
     #[cfg(not(feature = "deserialize"))]
@@ -182,6 +330,27 @@ impl<T: Clone + Copy + private::TypeName> Event<T> {
         self
     }
 
+    /// Sets the unit of measurement of the event value.
+    ///
+    /// An example might be `°C` for a temperature.
+    #[must_use]
+    #[cfg(not(feature = "deserialize"))]
+    pub const fn unit(mut self, unit: &'static str) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Sets the unit of measurement of the event value.
+    ///
+    /// An example might be `°C` for a temperature.
+    #[must_use]
+    #[inline]
+    #[cfg(feature = "deserialize")]
+    pub fn unit(mut self, unit: &'static str) -> Self {
+        self.unit = Some(alloc::borrow::Cow::Borrowed(unit));
+        self
+    }
+
     /// Removes the event description.
     ///
     /// This method might be useful to reduce the payload sent over the network.
@@ -199,13 +368,53 @@ impl<T: Clone + Copy + private::TypeName> Event<T> {
     pub fn remove_description(&mut self) {
         self.description = None;
     }
+}
 
+impl<T: Clone + private::TypeName> Event<T> {
+    // Returns the event name, regardless of how it is stored.
+    #[cfg(not(feature = "deserialize"))]
+    pub(crate) const fn name(&self) -> &str {
+        self.name
+    }
+
+    // Returns the event name, regardless of how it is stored.
+    #[cfg(feature = "deserialize")]
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<T: Clone + Copy + private::TypeName> Event<T> {
     // Updates the event value.
     pub(crate) const fn update_value(&mut self, value: T) {
         self.value = value;
     }
 }
 
+impl<T: Clone + private::Numeric> Event<T> {
+    /// Sets the minimum and maximum allowed values.
+    ///
+    /// The bounds are inclusive and they are not enforced when the value
+    /// is updated, so that a receiver can detect out-of-range readings
+    /// through [`Self::is_in_bounds`].
+    #[must_use]
+    #[inline]
+    pub fn bounds(mut self, min: T, max: T) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+
+    /// Checks whether the event value lies within its bounds.
+    ///
+    /// An event without bounds is always within them.
+    #[must_use]
+    #[inline]
+    pub fn is_in_bounds(&self) -> bool {
+        self.min.is_none_or(|min| self.value >= min) && self.max.is_none_or(|max| self.value <= max)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[cfg_attr(not(feature = "deserialize"), derive(Copy))]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -272,6 +481,34 @@ impl PeriodicEvent<f64> {
     }
 }
 
+/// A rule violated by an invalid [`Topic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopicError {
+    /// The topic is empty.
+    Empty,
+    /// The topic starts with a `/`.
+    LeadingSlash,
+    /// The topic ends with a `/`.
+    TrailingSlash,
+    /// The topic contains a `+` or `#` wildcard character.
+    Wildcard(char),
+}
+
+impl fmt::Display for TopicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::Empty => "The topic is empty".fmt(f),
+            Self::LeadingSlash => "The topic starts with a slash".fmt(f),
+            Self::TrailingSlash => "The topic ends with a slash".fmt(f),
+            Self::Wildcard(wildcard) => {
+                write!(f, "The topic contains the `{wildcard}` wildcard character")
+            }
+        }
+    }
+}
+
+impl core::error::Error for TopicError {}
+
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 /// The topic for event publication over the network.
@@ -288,11 +525,42 @@ impl Topic {
     }
 
     /// Creates a [`Topic`].
+    ///
+    /// The value is not validated, hence this method should be used only
+    /// with trusted values. Use [`Self::try_new`] otherwise.
     #[must_use]
     pub const fn new(value: String) -> Self {
         Self(value)
     }
 
+    /// Creates a [`Topic`] which can be safely published and subscribed to
+    /// over MQTT.
+    ///
+    /// # Errors
+    ///
+    /// A [`TopicError`] describing the violated rule is returned when
+    /// the value:
+    ///
+    /// - Is empty
+    /// - Starts with a `/`
+    /// - Ends with a `/`
+    /// - Contains the `+` or `#` wildcard characters
+    pub fn try_new(value: String) -> Result<Self, TopicError> {
+        if value.is_empty() {
+            return Err(TopicError::Empty);
+        }
+        if value.starts_with('/') {
+            return Err(TopicError::LeadingSlash);
+        }
+        if value.ends_with('/') {
+            return Err(TopicError::TrailingSlash);
+        }
+        if let Some(wildcard) = value.chars().find(|c| matches!(c, '+' | '#')) {
+            return Err(TopicError::Wildcard(wildcard));
+        }
+        Ok(Self(value))
+    }
+
     /// Returns the [`Topic`] as a [`&str`].
     #[inline]
     #[must_use]
@@ -319,6 +587,8 @@ pub struct Events {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     f64_events: Vec<Event<f64>>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    str_events: Vec<Event<StrValue>>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     periodic_bool_events: Vec<PeriodicEvent<bool>>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     periodic_u8_events: Vec<PeriodicEvent<u8>>,
@@ -362,6 +632,12 @@ impl fmt::Display for Events {
             }
         }
 
+        if !self.str_events.is_empty() {
+            for str_event in &self.str_events {
+                str_event.fmt(f)?;
+            }
+        }
+
         if !self.periodic_bool_events.is_empty() {
             for periodic_bool_event in &self.periodic_bool_events {
                 periodic_bool_event.fmt(f)?;
@@ -406,6 +682,7 @@ impl Events {
             i32_events: Vec::new(),
             f32_events: Vec::new(),
             f64_events: Vec::new(),
+            str_events: Vec::new(),
             periodic_bool_events: Vec::new(),
             periodic_u8_events: Vec::new(),
             periodic_i32_events: Vec::new(),
@@ -425,6 +702,7 @@ impl Events {
             i32_events: Vec::with_capacity(size),
             f32_events: Vec::with_capacity(size),
             f64_events: Vec::with_capacity(size),
+            str_events: Vec::with_capacity(size),
             periodic_bool_events: Vec::with_capacity(size),
             periodic_u8_events: Vec::with_capacity(size),
             periodic_i32_events: Vec::with_capacity(size),
@@ -473,6 +751,14 @@ impl Events {
         self
     }
 
+    /// Adds a sequence of [`Event<StrValue>`].
+    #[inline]
+    #[must_use]
+    pub fn str_events(mut self, str_events: Vec<Event<StrValue>>) -> Self {
+        self.str_events = str_events;
+        self
+    }
+
     /// Adds a sequence of [`PeriodicEvent<bool>`].
     #[inline]
     #[must_use]
@@ -514,123 +800,365 @@ impl Events {
     }
 
     /// Adds a single [`Event<bool>`].
+    ///
+    /// An existing [`Event<bool>`] with the same name is replaced in place.
     #[inline]
     pub fn add_bool_event(&mut self, bool_event: Event<bool>) {
-        self.bool_events.push(bool_event);
+        add_or_replace(&mut self.bool_events, bool_event, |e| e.name());
     }
 
     /// Adds a single [`Event<u8>`].
+    ///
+    /// An existing [`Event<u8>`] with the same name is replaced in place.
     #[inline]
     pub fn add_u8_event(&mut self, u8_event: Event<u8>) {
-        self.u8_events.push(u8_event);
+        add_or_replace(&mut self.u8_events, u8_event, |e| e.name());
     }
 
     /// Adds a single [`Event<i32>`].
+    ///
+    /// An existing [`Event<i32>`] with the same name is replaced in place.
     #[inline]
     pub fn add_i32_event(&mut self, i32_event: Event<i32>) {
-        self.i32_events.push(i32_event);
+        add_or_replace(&mut self.i32_events, i32_event, |e| e.name());
     }
 
     /// Adds a single [`Event<f32>`].
+    ///
+    /// An existing [`Event<f32>`] with the same name is replaced in place.
     #[inline]
     pub fn add_f32_event(&mut self, f32_event: Event<f32>) {
-        self.f32_events.push(f32_event);
+        add_or_replace(&mut self.f32_events, f32_event, |e| e.name());
     }
 
     /// Adds a single [`Event<f64>`].
+    ///
+    /// An existing [`Event<f64>`] with the same name is replaced in place.
     #[inline]
     pub fn add_f64_event(&mut self, f64_event: Event<f64>) {
-        self.f64_events.push(f64_event);
+        add_or_replace(&mut self.f64_events, f64_event, |e| e.name());
+    }
+
+    /// Adds a single [`Event<StrValue>`].
+    ///
+    /// An existing [`Event<StrValue>`] with the same name is replaced in place.
+    #[inline]
+    pub fn add_str_event(&mut self, str_event: Event<StrValue>) {
+        add_or_replace(&mut self.str_events, str_event, |e| e.name());
     }
 
     /// Adds a single [`PeriodicEvent<bool>`].
+    ///
+    /// An existing [`PeriodicEvent<bool>`] with the same name is replaced in place.
     #[inline]
     pub fn add_periodic_bool_event(&mut self, periodic_bool_event: PeriodicEvent<bool>) {
-        self.periodic_bool_events.push(periodic_bool_event);
+        add_or_replace(&mut self.periodic_bool_events, periodic_bool_event, |e| {
+            e.event.name()
+        });
     }
 
     /// Adds a single [`PeriodicEvent<u8>`].
+    ///
+    /// An existing [`PeriodicEvent<u8>`] with the same name is replaced in place.
     #[inline]
     pub fn add_periodic_u8_event(&mut self, periodic_u8_event: PeriodicEvent<u8>) {
-        self.periodic_u8_events.push(periodic_u8_event);
+        add_or_replace(&mut self.periodic_u8_events, periodic_u8_event, |e| {
+            e.event.name()
+        });
     }
 
     /// Adds a single [`PeriodicEvent<i32>`].
+    ///
+    /// An existing [`PeriodicEvent<i32>`] with the same name is replaced in place.
     #[inline]
     pub fn add_periodic_i32_event(&mut self, periodic_i32_event: PeriodicEvent<i32>) {
-        self.periodic_i32_events.push(periodic_i32_event);
+        add_or_replace(&mut self.periodic_i32_events, periodic_i32_event, |e| {
+            e.event.name()
+        });
     }
 
     /// Adds a single [`PeriodicEvent<f32>`].
+    ///
+    /// An existing [`PeriodicEvent<f32>`] with the same name is replaced in place.
     #[inline]
     pub fn add_periodic_f32_event(&mut self, periodic_f32_event: PeriodicEvent<f32>) {
-        self.periodic_f32_events.push(periodic_f32_event);
+        add_or_replace(&mut self.periodic_f32_events, periodic_f32_event, |e| {
+            e.event.name()
+        });
     }
 
     /// Adds a single [`PeriodicEvent<f64>`].
+    ///
+    /// An existing [`PeriodicEvent<f64>`] with the same name is replaced in place.
     #[inline]
     pub fn add_periodic_f64_event(&mut self, periodic_f64_event: PeriodicEvent<f64>) {
-        self.periodic_f64_events.push(periodic_f64_event);
+        add_or_replace(&mut self.periodic_f64_events, periodic_f64_event, |e| {
+            e.event.name()
+        });
     }
 
     /// Updates the [`Event<bool>`] value located at the given index.
+    ///
+    /// # Errors
+    ///
+    /// An [`EventsError`] is returned when the index is out of range.
     #[inline]
-    pub fn update_bool_value(&mut self, index: usize, value: bool) {
-        self.bool_events[index].update_value(value);
+    pub fn update_bool_value(&mut self, index: usize, value: bool) -> Result<(), EventsError> {
+        event_at(&mut self.bool_events, index)?.update_value(value);
+        Ok(())
     }
 
     /// Updates the [`Event<u8>`] value located at the given index.
+    ///
+    /// # Errors
+    ///
+    /// An [`EventsError`] is returned when the index is out of range.
     #[inline]
-    pub fn update_u8_value(&mut self, index: usize, value: u8) {
-        self.u8_events[index].update_value(value);
+    pub fn update_u8_value(&mut self, index: usize, value: u8) -> Result<(), EventsError> {
+        event_at(&mut self.u8_events, index)?.update_value(value);
+        Ok(())
     }
 
     /// Updates the [`Event<i32>`] value located at the given index.
+    ///
+    /// # Errors
+    ///
+    /// An [`EventsError`] is returned when the index is out of range.
     #[inline]
-    pub fn update_i32_value(&mut self, index: usize, value: i32) {
-        self.i32_events[index].update_value(value);
+    pub fn update_i32_value(&mut self, index: usize, value: i32) -> Result<(), EventsError> {
+        event_at(&mut self.i32_events, index)?.update_value(value);
+        Ok(())
     }
 
     /// Updates the [`Event<f32>`] value located at the given index.
+    ///
+    /// # Errors
+    ///
+    /// An [`EventsError`] is returned when the index is out of range.
     #[inline]
-    pub fn update_f32_value(&mut self, index: usize, value: f32) {
-        self.f32_events[index].update_value(value);
+    pub fn update_f32_value(&mut self, index: usize, value: f32) -> Result<(), EventsError> {
+        event_at(&mut self.f32_events, index)?.update_value(value);
+        Ok(())
     }
 
     /// Updates the [`Event<f64>`] value located at the given index.
+    ///
+    /// # Errors
+    ///
+    /// An [`EventsError`] is returned when the index is out of range.
     #[inline]
-    pub fn update_f64_value(&mut self, index: usize, value: f64) {
-        self.f64_events[index].update_value(value);
+    pub fn update_f64_value(&mut self, index: usize, value: f64) -> Result<(), EventsError> {
+        event_at(&mut self.f64_events, index)?.update_value(value);
+        Ok(())
+    }
+
+    /// Updates the [`Event<StrValue>`] value located at the given index.
+    ///
+    /// # Errors
+    ///
+    /// An [`EventsError`] is returned when the index is out of range.
+    #[inline]
+    pub fn update_str_value(
+        &mut self,
+        index: usize,
+        value: &'static str,
+    ) -> Result<(), EventsError> {
+        event_at(&mut self.str_events, index)?.update_str_value(value);
+        Ok(())
     }
 
     /// Updates the [`PeriodicEvent<bool>`] value located at the given index.
+    ///
+    /// # Errors
+    ///
+    /// An [`EventsError`] is returned when the index is out of range.
     #[inline]
-    pub fn update_periodic_bool_value(&mut self, index: usize, value: bool) {
-        self.periodic_bool_events[index].event.update_value(value);
+    pub fn update_periodic_bool_value(
+        &mut self,
+        index: usize,
+        value: bool,
+    ) -> Result<(), EventsError> {
+        event_at(&mut self.periodic_bool_events, index)?
+            .event
+            .update_value(value);
+        Ok(())
     }
 
     /// Updates the [`PeriodicEvent<u8>`] value located at the given index.
+    ///
+    /// # Errors
+    ///
+    /// An [`EventsError`] is returned when the index is out of range.
     #[inline]
-    pub fn update_periodic_u8_value(&mut self, index: usize, value: u8) {
-        self.periodic_u8_events[index].event.update_value(value);
+    pub fn update_periodic_u8_value(&mut self, index: usize, value: u8) -> Result<(), EventsError> {
+        event_at(&mut self.periodic_u8_events, index)?
+            .event
+            .update_value(value);
+        Ok(())
     }
 
     /// Updates the [`PeriodicEvent<i32>`] value located at the given index.
+    ///
+    /// # Errors
+    ///
+    /// An [`EventsError`] is returned when the index is out of range.
     #[inline]
-    pub fn update_periodic_i32_value(&mut self, index: usize, value: i32) {
-        self.periodic_i32_events[index].event.update_value(value);
+    pub fn update_periodic_i32_value(
+        &mut self,
+        index: usize,
+        value: i32,
+    ) -> Result<(), EventsError> {
+        event_at(&mut self.periodic_i32_events, index)?
+            .event
+            .update_value(value);
+        Ok(())
     }
 
     /// Updates the [`PeriodicEvent<f32>`] value located at the given index.
+    ///
+    /// # Errors
+    ///
+    /// An [`EventsError`] is returned when the index is out of range.
     #[inline]
-    pub fn update_periodic_f32_value(&mut self, index: usize, value: f32) {
-        self.periodic_f32_events[index].event.update_value(value);
+    pub fn update_periodic_f32_value(
+        &mut self,
+        index: usize,
+        value: f32,
+    ) -> Result<(), EventsError> {
+        event_at(&mut self.periodic_f32_events, index)?
+            .event
+            .update_value(value);
+        Ok(())
     }
 
     /// Updates the [`PeriodicEvent<f64>`] value located at the given index.
+    ///
+    /// # Errors
+    ///
+    /// An [`EventsError`] is returned when the index is out of range.
     #[inline]
-    pub fn update_periodic_f64_value(&mut self, index: usize, value: f64) {
-        self.periodic_f64_events[index].event.update_value(value);
+    pub fn update_periodic_f64_value(
+        &mut self,
+        index: usize,
+        value: f64,
+    ) -> Result<(), EventsError> {
+        event_at(&mut self.periodic_f64_events, index)?
+            .event
+            .update_value(value);
+        Ok(())
+    }
+
+    /// Updates the value of the [`Event<bool>`] or [`PeriodicEvent<bool>`]
+    /// with the given name.
+    ///
+    /// Returns `false` when no event with that name exists.
+    #[inline]
+    pub fn update_bool_value_by_name(&mut self, name: &str, value: bool) -> bool {
+        update_by_name(
+            &mut self.bool_events,
+            &mut self.periodic_bool_events,
+            name,
+            value,
+        )
+    }
+
+    /// Updates the value of the [`Event<u8>`] or [`PeriodicEvent<u8>`]
+    /// with the given name.
+    ///
+    /// Returns `false` when no event with that name exists.
+    #[inline]
+    pub fn update_u8_value_by_name(&mut self, name: &str, value: u8) -> bool {
+        update_by_name(
+            &mut self.u8_events,
+            &mut self.periodic_u8_events,
+            name,
+            value,
+        )
+    }
+
+    /// Updates the value of the [`Event<i32>`] or [`PeriodicEvent<i32>`]
+    /// with the given name.
+    ///
+    /// Returns `false` when no event with that name exists.
+    #[inline]
+    pub fn update_i32_value_by_name(&mut self, name: &str, value: i32) -> bool {
+        update_by_name(
+            &mut self.i32_events,
+            &mut self.periodic_i32_events,
+            name,
+            value,
+        )
+    }
+
+    /// Updates the value of the [`Event<f32>`] or [`PeriodicEvent<f32>`]
+    /// with the given name.
+    ///
+    /// Returns `false` when no event with that name exists.
+    #[inline]
+    pub fn update_f32_value_by_name(&mut self, name: &str, value: f32) -> bool {
+        update_by_name(
+            &mut self.f32_events,
+            &mut self.periodic_f32_events,
+            name,
+            value,
+        )
+    }
+
+    /// Updates the value of the [`Event<f64>`] or [`PeriodicEvent<f64>`]
+    /// with the given name.
+    ///
+    /// Returns `false` when no event with that name exists.
+    #[inline]
+    pub fn update_f64_value_by_name(&mut self, name: &str, value: f64) -> bool {
+        update_by_name(
+            &mut self.f64_events,
+            &mut self.periodic_f64_events,
+            name,
+            value,
+        )
+    }
+
+    /// Merges the given [`Events`] into the current ones.
+    ///
+    /// For each event type, an event sharing its name with an existing one
+    /// replaces it, updating its value, while the unseen events are appended.
+    #[inline]
+    pub fn merge(&mut self, other: Events) {
+        let Events {
+            bool_events,
+            u8_events,
+            i32_events,
+            f32_events,
+            f64_events,
+            str_events,
+            periodic_bool_events,
+            periodic_u8_events,
+            periodic_i32_events,
+            periodic_f32_events,
+            periodic_f64_events,
+        } = other;
+
+        merge_by_name(&mut self.bool_events, bool_events, |e| e.name());
+        merge_by_name(&mut self.u8_events, u8_events, |e| e.name());
+        merge_by_name(&mut self.i32_events, i32_events, |e| e.name());
+        merge_by_name(&mut self.f32_events, f32_events, |e| e.name());
+        merge_by_name(&mut self.f64_events, f64_events, |e| e.name());
+        merge_by_name(&mut self.str_events, str_events, |e| e.name());
+        merge_by_name(&mut self.periodic_bool_events, periodic_bool_events, |e| {
+            e.event.name()
+        });
+        merge_by_name(&mut self.periodic_u8_events, periodic_u8_events, |e| {
+            e.event.name()
+        });
+        merge_by_name(&mut self.periodic_i32_events, periodic_i32_events, |e| {
+            e.event.name()
+        });
+        merge_by_name(&mut self.periodic_f32_events, periodic_f32_events, |e| {
+            e.event.name()
+        });
+        merge_by_name(&mut self.periodic_f64_events, periodic_f64_events, |e| {
+            e.event.name()
+        });
     }
 
     /// Returns an immutable slice of the [`Event<bool>`] sequence.
@@ -668,6 +1196,13 @@ impl Events {
         self.f64_events.as_slice()
     }
 
+    /// Returns an immutable slice of the [`Event<StrValue>`] sequence.
+    #[inline]
+    #[must_use]
+    pub fn str_events_as_slice(&self) -> &[Event<StrValue>] {
+        self.str_events.as_slice()
+    }
+
     /// Returns an immutable slice of the [`PeriodicEvent<bool>`] sequence.
     #[inline]
     #[must_use]
@@ -711,6 +1246,7 @@ impl Events {
             && self.i32_events.is_empty()
             && self.f32_events.is_empty()
             && self.f64_events.is_empty()
+            && self.str_events.is_empty()
             && self.periodic_bool_events.is_empty()
             && self.periodic_u8_events.is_empty()
             && self.periodic_i32_events.is_empty()
@@ -719,6 +1255,74 @@ impl Events {
     }
 }
 
+fn event_at<E>(events: &mut [E], index: usize) -> Result<&mut E, EventsError> {
+    let len = events.len();
+    events.get_mut(index).ok_or(EventsError { index, len })
+}
+
+fn update_by_name<T: Clone + Copy + private::TypeName>(
+    events: &mut [Event<T>],
+    periodic_events: &mut [PeriodicEvent<T>],
+    name: &str,
+    value: T,
+) -> bool {
+    if let Some(event) = events.iter_mut().find(|event| event.name == name) {
+        event.update_value(value);
+        return true;
+    }
+
+    if let Some(periodic_event) = periodic_events
+        .iter_mut()
+        .find(|periodic_event| periodic_event.event.name == name)
+    {
+        periodic_event.event.update_value(value);
+        return true;
+    }
+
+    false
+}
+
+// Replaces the event sharing its name with the given event, keeping its
+// position, or appends the event when its name is unseen.
+fn add_or_replace<E>(events: &mut Vec<E>, event: E, name: fn(&E) -> &str) {
+    if let Some(existing) = events
+        .iter_mut()
+        .find(|existing| name(existing) == name(&event))
+    {
+        *existing = event;
+    } else {
+        events.push(event);
+    }
+}
+
+// Replaces the events sharing their name with an incoming event,
+// and appends the incoming events with an unseen name.
+fn merge_by_name<E>(events: &mut Vec<E>, incoming: Vec<E>, name: fn(&E) -> &str) {
+    for event in incoming {
+        add_or_replace(events, event, name);
+    }
+}
+
+/// The route, relative to a device main route, on which a device streams
+/// its [`Events`] as Server-Sent Events.
+pub const EVENTS_STREAM_ROUTE: &str = "/events/stream";
+
+/// Encodes the given data as a Server-Sent Events frame.
+///
+/// Each line of data is prefixed with `data: ` and the frame is terminated
+/// by an empty line, as mandated by the `text/event-stream` format.
+#[must_use]
+pub fn sse_frame(data: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(data.len() + 8);
+    for line in data.split(|byte| *byte == b'\n') {
+        frame.extend_from_slice(b"data: ");
+        frame.extend_from_slice(line);
+        frame.push(b'\n');
+    }
+    frame.push(b'\n');
+    frame
+}
+
 #[derive(Debug, PartialEq, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 /// All events to be published over the network, including their associated
@@ -742,17 +1346,108 @@ impl EventsDescription {
             events,
         }
     }
+
+    /// Creates an [`EventsDescriptionBuilder`] to assemble an
+    /// [`EventsDescription`] incrementally.
+    #[must_use]
+    pub const fn builder() -> EventsDescriptionBuilder {
+        EventsDescriptionBuilder {
+            broker_data: None,
+            topic: None,
+            events: Events::empty(),
+        }
+    }
+}
+
+/// A missing part of an [`EventsDescription`] built through an
+/// [`EventsDescriptionBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventsDescriptionError {
+    /// The [`BrokerData`] has not been set.
+    MissingBroker,
+    /// The [`Topic`] has not been set.
+    MissingTopic,
+}
+
+impl fmt::Display for EventsDescriptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::MissingBroker => "The events description has no broker data".fmt(f),
+            Self::MissingTopic => "The events description has no topic".fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for EventsDescriptionError {}
+
+/// An [`EventsDescription`] builder.
+///
+/// It allows to register the [`Events`] before knowing the [`BrokerData`],
+/// for example when the broker address is discovered at runtime.
+#[derive(Debug, PartialEq)]
+pub struct EventsDescriptionBuilder {
+    broker_data: Option<BrokerData>,
+    topic: Option<Topic>,
+    events: Events,
+}
+
+impl EventsDescriptionBuilder {
+    /// Sets the [`BrokerData`].
+    #[must_use]
+    #[inline]
+    pub fn broker(mut self, broker_data: BrokerData) -> Self {
+        self.broker_data = Some(broker_data);
+        self
+    }
+
+    /// Sets the [`Topic`].
+    #[must_use]
+    #[inline]
+    pub fn topic(mut self, topic: Topic) -> Self {
+        self.topic = Some(topic);
+        self
+    }
+
+    /// Sets the [`Events`].
+    ///
+    /// When they are not set, the [`EventsDescription`] contains empty
+    /// [`Events`].
+    #[must_use]
+    #[inline]
+    pub fn events(mut self, events: Events) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Builds the [`EventsDescription`].
+    ///
+    /// # Errors
+    ///
+    /// - The [`BrokerData`] has not been set
+    /// - The [`Topic`] has not been set
+    pub fn build(self) -> Result<EventsDescription, EventsDescriptionError> {
+        let broker_data = self
+            .broker_data
+            .ok_or(EventsDescriptionError::MissingBroker)?;
+        let topic = self.topic.ok_or(EventsDescriptionError::MissingTopic)?;
+        Ok(EventsDescription::new(broker_data, topic, self.events))
+    }
 }
 
 #[cfg(test)]
 #[cfg(feature = "deserialize")]
 mod tests {
-    use core::net::Ipv4Addr;
+    use core::net::{Ipv4Addr, Ipv6Addr};
     use core::time::Duration;
 
     use crate::{deserialize, serialize};
 
-    use super::{BrokerData, Event, Events, EventsDescription, PeriodicEvent, Topic};
+    use alloc::string::{String, ToString};
+
+    use super::{
+        BrokerAddress, BrokerData, Event, Events, EventsDescription, EventsDescriptionError,
+        EventsError, PeriodicEvent, StrValue, Topic, TopicError, sse_frame,
+    };
 
     const DEFAULT_DURATION: Duration = Duration::from_secs(1);
 
@@ -847,6 +1542,246 @@ mod tests {
         assert_eq!(deserialize::<Events>(serialize(&events)), events);
     }
 
+    #[test]
+    fn test_str_events() {
+        let str_event = Event::str("status").description("A status event");
+        assert_eq!(
+            deserialize::<Event<StrValue>>(serialize(&str_event)),
+            str_event
+        );
+
+        let mut events = Events::empty();
+        events.add_str_event(str_event);
+        events.add_bool_event(Event::bool("bool_event"));
+        assert_eq!(deserialize::<Events>(serialize(&events)), events);
+
+        events.update_str_value(0, "heating").unwrap();
+        assert_eq!(events.str_events_as_slice()[0].value, "heating");
+        assert_eq!(
+            serialize(&events)["str_events"],
+            serde_json::json!([{
+                "name": "status",
+                "description": "A status event",
+                "value": "heating",
+            }])
+        );
+        assert_eq!(deserialize::<Events>(serialize(&events)), events);
+
+        assert_eq!(
+            events.str_events_as_slice()[0].to_string(),
+            "Name: \"status\"\nDescription: \"A status event\"\nType: str\nValue: heating\n"
+        );
+    }
+
+    #[test]
+    fn test_event_unit() {
+        let temperature = Event::f32("temp").unit("°C");
+        assert_eq!(
+            deserialize::<Event<f32>>(serialize(&temperature)),
+            temperature
+        );
+        assert_eq!(
+            serialize(&temperature),
+            serde_json::json!({ "name": "temp", "unit": "°C", "value": 0.0 })
+        );
+        assert_eq!(
+            temperature.to_string(),
+            "Name: \"temp\"\nType: f32\nValue: 0 °C\n"
+        );
+
+        // Without a unit, neither the serialized event nor its display
+        // output mention it.
+        let humidity = Event::f32("humidity");
+        assert_eq!(
+            serialize(&humidity),
+            serde_json::json!({ "name": "humidity", "value": 0.0 })
+        );
+        assert_eq!(
+            humidity.to_string(),
+            "Name: \"humidity\"\nType: f32\nValue: 0\n"
+        );
+    }
+
+    #[test]
+    fn test_event_bounds() {
+        let mut percentage = Event::u8("percentage").bounds(0, 100);
+        assert_eq!(
+            serialize(&percentage),
+            serde_json::json!({ "name": "percentage", "min": 0, "max": 100, "value": 0 })
+        );
+        assert_eq!(deserialize::<Event<u8>>(serialize(&percentage)), percentage);
+        assert!(percentage.is_in_bounds());
+
+        percentage.update_value(100);
+        assert!(percentage.is_in_bounds());
+
+        percentage.update_value(101);
+        assert!(!percentage.is_in_bounds());
+
+        let mut temperature = Event::f32("temperature").bounds(-40., 85.);
+        temperature.update_value(-40.5);
+        assert!(!temperature.is_in_bounds());
+
+        // An event without bounds accepts any value.
+        let mut counter = Event::i32("counter");
+        counter.update_value(i32::MIN);
+        assert!(counter.is_in_bounds());
+        assert_eq!(
+            serialize(&counter),
+            serde_json::json!({ "name": "counter", "value": i32::MIN })
+        );
+    }
+
+    #[test]
+    fn test_update_value_out_of_range() {
+        let mut events = Events::empty();
+        events.add_bool_event(Event::bool("bool_event"));
+        events.add_periodic_f32_event(PeriodicEvent::f32(
+            Event::f32("f32_event"),
+            DEFAULT_DURATION,
+        ));
+
+        assert_eq!(events.update_bool_value(0, true), Ok(()));
+        assert!(events.bool_events_as_slice()[0].value);
+
+        assert_eq!(
+            events.update_bool_value(1, false),
+            Err(EventsError { index: 1, len: 1 })
+        );
+        assert!(events.bool_events_as_slice()[0].value);
+
+        assert_eq!(
+            events.update_u8_value(0, 42),
+            Err(EventsError { index: 0, len: 0 })
+        );
+        assert_eq!(
+            events.update_periodic_f32_value(usize::MAX, 1.0),
+            Err(EventsError {
+                index: usize::MAX,
+                len: 1
+            })
+        );
+        assert_eq!(events.update_periodic_f32_value(0, 1.0), Ok(()));
+        assert_eq!(
+            events.update_str_value(0, "idle"),
+            Err(EventsError { index: 0, len: 0 })
+        );
+    }
+
+    #[test]
+    fn test_update_value_by_name() {
+        let mut events = Events::empty();
+        events.add_bool_event(Event::bool("bool_event"));
+        events.add_periodic_u8_event(PeriodicEvent::u8(Event::u8("u8_event"), DEFAULT_DURATION));
+
+        assert!(events.update_bool_value_by_name("bool_event", true));
+        assert!(events.bool_events_as_slice()[0].value);
+
+        assert!(events.update_u8_value_by_name("u8_event", 42));
+        assert_eq!(events.periodic_u8_events_as_slice()[0].event.value, 42);
+
+        assert!(!events.update_bool_value_by_name("u8_event", true));
+        assert!(!events.update_f64_value_by_name("missing", 1.0));
+    }
+
+    #[test]
+    fn test_sse_frame() {
+        let mut events = Events::empty();
+        events.add_bool_event(Event::bool("bool_event"));
+
+        let frame = sse_frame(&serde_json::to_vec(&events).unwrap());
+        assert_eq!(
+            frame,
+            br#"data: {"bool_events":[{"name":"bool_event","value":false}]}
+
+"#
+        );
+
+        // A value change produces a frame containing the new value.
+        assert!(events.update_bool_value_by_name("bool_event", true));
+        let frame = sse_frame(&serde_json::to_vec(&events).unwrap());
+        assert_eq!(
+            frame,
+            br#"data: {"bool_events":[{"name":"bool_event","value":true}]}
+
+"#
+        );
+
+        // Every line of multi-line data is prefixed.
+        assert_eq!(
+            sse_frame(b"first\nsecond"),
+            b"data: first\ndata: second\n\n"
+        );
+    }
+
+    #[test]
+    fn test_topic_validation() {
+        assert_eq!(
+            Topic::try_new("tosca/light/events".into()),
+            Ok(Topic::new("tosca/light/events".into()))
+        );
+
+        assert_eq!(Topic::try_new(String::new()), Err(TopicError::Empty));
+        assert_eq!(
+            Topic::try_new("/tosca/light".into()),
+            Err(TopicError::LeadingSlash)
+        );
+        assert_eq!(
+            Topic::try_new("tosca/light/".into()),
+            Err(TopicError::TrailingSlash)
+        );
+        assert_eq!(
+            Topic::try_new("tosca/+/events".into()),
+            Err(TopicError::Wildcard('+'))
+        );
+        assert_eq!(
+            Topic::try_new("tosca/light/#".into()),
+            Err(TopicError::Wildcard('#'))
+        );
+        assert_eq!(
+            TopicError::Wildcard('#').to_string(),
+            "The topic contains the `#` wildcard character"
+        );
+    }
+
+    #[test]
+    fn test_broker_data() {
+        let broker_data = BrokerData::new(Ipv4Addr::LOCALHOST.into(), 1883);
+        assert_eq!(
+            serialize(&broker_data),
+            serde_json::json!({ "address": "127.0.0.1", "port": 1883 })
+        );
+        assert_eq!(
+            deserialize::<BrokerData>(serialize(&broker_data)),
+            broker_data
+        );
+
+        let broker_data = BrokerData::new(Ipv6Addr::LOCALHOST.into(), 1883);
+        assert_eq!(
+            serialize(&broker_data),
+            serde_json::json!({ "address": "::1", "port": 1883 })
+        );
+        assert_eq!(
+            deserialize::<BrokerData>(serialize(&broker_data)),
+            broker_data
+        );
+
+        let broker_data = BrokerData::host("broker.local".into(), 8883);
+        assert_eq!(
+            serialize(&broker_data),
+            serde_json::json!({ "address": "broker.local", "port": 8883 })
+        );
+        assert_eq!(
+            deserialize::<BrokerData>(serialize(&broker_data)),
+            broker_data
+        );
+        assert_eq!(
+            broker_data.address,
+            BrokerAddress::Host("broker.local".into())
+        );
+        assert_eq!(broker_data.address.to_string(), "broker.local");
+    }
+
     #[test]
     fn test_events_description() {
         let broker_data = BrokerData::new(Ipv4Addr::LOCALHOST.into(), 80);
@@ -868,4 +1803,78 @@ mod tests {
             events_description
         );
     }
+
+    #[test]
+    fn test_events_description_builder() {
+        let events = || {
+            let mut events = Events::empty();
+            events.add_bool_event(Event::bool("bool_event"));
+            events
+        };
+        let broker_data = || BrokerData::new(Ipv4Addr::LOCALHOST.into(), 1883);
+        let topic = || Topic::new("test".into());
+
+        assert_eq!(
+            EventsDescription::builder()
+                .events(events())
+                .topic(topic())
+                .broker(broker_data())
+                .build(),
+            Ok(EventsDescription::new(broker_data(), topic(), events()))
+        );
+
+        assert_eq!(
+            EventsDescription::builder()
+                .events(events())
+                .topic(topic())
+                .build(),
+            Err(EventsDescriptionError::MissingBroker)
+        );
+        assert_eq!(
+            EventsDescription::builder().broker(broker_data()).build(),
+            Err(EventsDescriptionError::MissingTopic)
+        );
+    }
+
+    #[test]
+    fn test_merge_events() {
+        let mut events = Events::empty();
+        events.add_bool_event(Event::bool("motion"));
+        events.add_i32_event(Event::i32("counter"));
+
+        let mut motion = Event::bool("motion");
+        motion.update_value(true);
+        let mut temperature = Event::f32("temperature").unit("°C");
+        temperature.update_value(21.5);
+
+        let mut other = Events::empty();
+        other.add_bool_event(motion.clone());
+        other.add_f32_event(temperature.clone());
+
+        events.merge(other);
+
+        assert_eq!(events.bool_events_as_slice(), &[motion]);
+        assert_eq!(events.i32_events_as_slice(), &[Event::i32("counter")]);
+        assert_eq!(events.f32_events_as_slice(), &[temperature]);
+    }
+
+    #[test]
+    fn test_add_event_with_same_name() {
+        let mut events = Events::empty();
+        events.add_bool_event(Event::bool("led"));
+        events.add_bool_event(Event::bool("button"));
+
+        let mut led = Event::bool("led");
+        led.update_value(true);
+        events.add_bool_event(led.clone());
+
+        assert_eq!(events.bool_events_as_slice(), &[led, Event::bool("button")]);
+
+        let mut periodic_led = PeriodicEvent::bool(Event::bool("led"), DEFAULT_DURATION);
+        events.add_periodic_bool_event(periodic_led.clone());
+        periodic_led.event.update_value(true);
+        events.add_periodic_bool_event(periodic_led.clone());
+
+        assert_eq!(events.periodic_bool_events_as_slice(), &[periodic_led]);
+    }
 }
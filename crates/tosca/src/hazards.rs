@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use hashbrown::DefaultHashBuilder;
 
 use indexmap::set::{IndexSet, IntoIter, Iter};
@@ -34,6 +36,30 @@ pub const ALL_HAZARDS: &[Hazard] = &[
     Hazard::WaterFlooding,
 ];
 
+// Compares two names in a `const` context.
+const fn names_eq(first: &[u8], second: &[u8], ignore_case: bool) -> bool {
+    if first.len() != second.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < first.len() {
+        let (a, b) = if ignore_case {
+            (
+                first[i].to_ascii_lowercase(),
+                second[i].to_ascii_lowercase(),
+            )
+        } else {
+            (first[i], second[i])
+        };
+        if a != b {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
 /// All possible hazards for a device route.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -212,6 +238,39 @@ impl Hazard {
         }
     }
 
+    /// Returns the [`Severity`] of the [`Hazard`].
+    ///
+    /// The severity is a coarse ranking, meant to sort and highlight the
+    /// risks of a device, which does not replace the hazard description.
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        match self {
+            Self::AirPoisoning | Self::Asphyxia | Self::Explosion | Self::FireHazard => {
+                Severity::Critical
+            }
+            Self::AudioVideoRecordAndStore
+            | Self::PaySubscriptionFee
+            | Self::PowerSurge
+            | Self::SpendMoney
+            | Self::TakePictures
+            | Self::UnauthorisedPhysicalAccess
+            | Self::VideoRecordAndStore
+            | Self::WaterFlooding => Severity::High,
+            Self::AudioVideoDisplay
+            | Self::PowerOutage
+            | Self::RecordIssuedCommands
+            | Self::RecordUserPreferences
+            | Self::SpoiledFood
+            | Self::TakeDeviceScreenshots
+            | Self::VideoDisplay => Severity::Medium,
+            Self::ElectricEnergyConsumption
+            | Self::GasConsumption
+            | Self::LogEnergyConsumption
+            | Self::LogUsageTime
+            | Self::WaterConsumption => Severity::Low,
+        }
+    }
+
     /// Returns the [`Category`] associated with the [`Hazard`].
     ///
     /// A hazard is **always** associated with **exactly one** one category.
@@ -311,6 +370,59 @@ impl Hazard {
         }
     }
 
+    /// Returns the [`Hazard`] associated with the given name.
+    ///
+    /// The name must match exactly the one returned by [`Hazard::name`].
+    /// The return value is [`None`] when the name does not exist.
+    #[must_use]
+    pub const fn from_name(name: &str) -> Option<Self> {
+        Self::find_by_name(name, false)
+    }
+
+    /// Returns the [`Hazard`] associated with the given name, ignoring
+    /// the ASCII case.
+    ///
+    /// The return value is [`None`] when the name does not exist.
+    #[must_use]
+    pub const fn from_name_ignore_ascii_case(name: &str) -> Option<Self> {
+        Self::find_by_name(name, true)
+    }
+
+    const fn find_by_name(name: &str, ignore_case: bool) -> Option<Self> {
+        let mut i = 0;
+        while i < ALL_HAZARDS.len() {
+            let hazard = ALL_HAZARDS[i];
+            if names_eq(hazard.name().as_bytes(), name.as_bytes(), ignore_case) {
+                return Some(hazard);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Returns the [`Hazard`]s implied by the given [`Hazard`].
+    ///
+    /// A hazard implies another one when the latter is **always** part of
+    /// the former, hence a policy blocking the broader hazard should also
+    /// block the narrower ones:
+    ///
+    /// - [`Hazard::AudioVideoDisplay`] implies [`Hazard::VideoDisplay`]
+    /// - [`Hazard::AudioVideoRecordAndStore`] implies
+    ///   [`Hazard::VideoRecordAndStore`]
+    /// - [`Hazard::PaySubscriptionFee`] implies [`Hazard::SpendMoney`]
+    ///
+    /// The table is intentionally conservative: hazards which are only
+    /// **likely** to occur together are not related.
+    #[must_use]
+    pub const fn implied(&self) -> &'static [Hazard] {
+        match self {
+            Self::AudioVideoDisplay => &[Self::VideoDisplay],
+            Self::AudioVideoRecordAndStore => &[Self::VideoRecordAndStore],
+            Self::PaySubscriptionFee => &[Self::SpendMoney],
+            _ => &[],
+        }
+    }
+
     /// Returns the [`HazardData`] constructed from the given [`Hazard`].
     #[must_use]
     pub const fn data(&self) -> HazardData {
@@ -342,6 +454,100 @@ impl Hazards {
         }
         elements
     }
+
+    /// Inserts all the [`Hazard`]s of a [`Category`] into [`Hazards`].
+    #[must_use]
+    #[inline]
+    pub fn insert_category(mut self, category: Category) -> Self {
+        for hazard in category.hazards() {
+            self.add(*hazard);
+        }
+        self
+    }
+
+    /// Checks if [`Hazards`] contains **all** the [`Hazard`]s of
+    /// a [`Category`].
+    #[must_use]
+    #[inline]
+    pub fn contains_category(&self, category: Category) -> bool {
+        category
+            .hazards()
+            .iter()
+            .all(|hazard| self.contains(hazard))
+    }
+
+    /// Returns the [`Hazard`]s contained in both [`Hazards`], following
+    /// the insertion order of `self`.
+    #[must_use]
+    pub fn intersection(&self, other: &Hazards) -> Self {
+        let mut hazards = Self::new();
+        for hazard in self {
+            if other.contains(hazard) {
+                hazards.add(*hazard);
+            }
+        }
+        hazards
+    }
+
+    /// Returns the [`Hazard`]s of `self` which are not contained in
+    /// `other`, following the insertion order of `self`.
+    #[must_use]
+    pub fn difference(&self, other: &Hazards) -> Self {
+        let mut hazards = Self::new();
+        for hazard in self {
+            if !other.contains(hazard) {
+                hazards.add(*hazard);
+            }
+        }
+        hazards
+    }
+
+    /// Returns the highest [`Severity`] among the [`Hazard`]s.
+    ///
+    /// The return value is [`None`] when [`Hazards`] is empty.
+    #[must_use]
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.iter().map(Hazard::severity).max()
+    }
+
+    /// Constructs [`Hazards`] from a slice of hazard identifiers.
+    ///
+    /// Every valid identifier is converted through [`Hazard::from_id`] and
+    /// inserted following the slice order. The identifiers which do not
+    /// match any [`Hazard`] are returned too, so that they can be reported.
+    #[must_use]
+    pub fn from_ids(ids: &[u16]) -> (Self, Vec<u16>) {
+        let mut hazards = Self::new();
+        let mut unknown_ids = Vec::new();
+        for id in ids {
+            match Hazard::from_id(*id) {
+                Some(hazard) => hazards.add(hazard),
+                None => unknown_ids.push(*id),
+            }
+        }
+        (hazards, unknown_ids)
+    }
+
+    /// Returns a copy of [`Hazards`] expanded with all the hazards implied
+    /// by its elements, as defined by [`Hazard::implied`].
+    ///
+    /// Implications are followed transitively, and the original hazards
+    /// keep their order, followed by the implied ones.
+    #[must_use]
+    pub fn with_implied(&self) -> Self {
+        let mut hazards = self.clone();
+
+        // Newly added hazards are appended, hence they are expanded too.
+        let mut index = 0;
+        while let Some(hazard) = hazards.get_index(index).copied() {
+            for implied in hazard.implied() {
+                hazards.add(*implied);
+            }
+            index += 1;
+        }
+
+        hazards
+    }
 }
 
 /// All [`Hazard`] data.
@@ -359,6 +565,21 @@ pub struct HazardData {
     pub category_description: &'static str,
 }
 
+/// Hazard severities, ordered from the lowest to the highest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub enum Severity {
+    /// The hazard has a limited impact.
+    Low,
+    /// The hazard might cause damages or disclose personal data.
+    Medium,
+    /// The hazard might cause serious damages, money losses, or disclose
+    /// sensitive data.
+    High,
+    /// The hazard might endanger people's lives.
+    Critical,
+}
+
 /// All [`Category`]s.
 pub const ALL_CATEGORIES: &[Category] = &[Category::Safety, Category::Privacy, Category::Financial];
 
@@ -447,9 +668,11 @@ impl Category {
 #[cfg(test)]
 #[cfg(feature = "deserialize")]
 mod tests {
+    use alloc::vec::Vec;
+
     use crate::{deserialize, serialize};
 
-    use super::{ALL_CATEGORIES, ALL_HAZARDS, Category, Hazard};
+    use super::{ALL_CATEGORIES, ALL_HAZARDS, Category, Hazard, Hazards, Severity};
 
     #[test]
     fn test_hazard() {
@@ -476,6 +699,157 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Hazard::from_name("Unknown Hazard"), None);
+        assert_eq!(Hazard::from_name("fire hazard"), None);
+        assert_eq!(
+            Hazard::from_name_ignore_ascii_case("fire HAZARD"),
+            Some(Hazard::FireHazard)
+        );
+        assert_eq!(Hazard::from_name_ignore_ascii_case("Fire Hazards"), None);
+
+        for hazard in ALL_HAZARDS {
+            assert_eq!(Hazard::from_name(hazard.name()), Some(*hazard));
+            assert_eq!(
+                Hazard::from_name_ignore_ascii_case(&hazard.name().to_ascii_uppercase()),
+                Some(*hazard)
+            );
+        }
+    }
+
+    #[test]
+    fn test_severity() {
+        assert_eq!(Hazard::Explosion.severity(), Severity::Critical);
+        assert_eq!(Hazard::FireHazard.severity(), Severity::Critical);
+        assert_eq!(Hazard::SpendMoney.severity(), Severity::High);
+        assert_eq!(Hazard::SpoiledFood.severity(), Severity::Medium);
+        assert_eq!(Hazard::LogUsageTime.severity(), Severity::Low);
+
+        assert!(Severity::Low < Severity::Medium);
+        assert!(Severity::High < Severity::Critical);
+
+        let hazards = Hazards::new()
+            .insert(Hazard::LogUsageTime)
+            .insert(Hazard::TakePictures)
+            .insert(Hazard::PowerOutage);
+        assert_eq!(hazards.max_severity(), Some(Severity::High));
+        assert_eq!(
+            hazards.insert(Hazard::Asphyxia).max_severity(),
+            Some(Severity::Critical)
+        );
+        assert_eq!(Hazards::new().max_severity(), None);
+
+        assert_eq!(
+            deserialize::<Severity>(serialize(Severity::Medium)),
+            Severity::Medium
+        );
+    }
+
+    #[test]
+    fn test_intersection_difference() {
+        let first = Hazards::new()
+            .insert(Hazard::FireHazard)
+            .insert(Hazard::Explosion);
+        let second = Hazards::new()
+            .insert(Hazard::Explosion)
+            .insert(Hazard::Asphyxia);
+
+        assert_eq!(
+            first.intersection(&second),
+            Hazards::init(Hazard::Explosion)
+        );
+        assert_eq!(first.difference(&second), Hazards::init(Hazard::FireHazard));
+        assert_eq!(second.difference(&first), Hazards::init(Hazard::Asphyxia));
+
+        // The insertion order of `self` is preserved.
+        let all = second.clone().insert(Hazard::FireHazard);
+        assert_eq!(
+            all.intersection(&first).iter().copied().collect::<Vec<_>>(),
+            [Hazard::Explosion, Hazard::FireHazard]
+        );
+
+        assert!(first.intersection(&Hazards::new()).is_empty());
+        assert_eq!(first.difference(&Hazards::new()), first);
+    }
+
+    #[test]
+    fn test_category_hazards() {
+        let hazards = Hazards::new()
+            .insert(Hazard::FireHazard)
+            .insert_category(Category::Privacy);
+
+        assert!(hazards.contains_category(Category::Privacy));
+        assert_eq!(hazards.len(), Category::Privacy.hazards().len() + 1);
+        // The hazards of a category follow the existing ones.
+        assert_eq!(hazards.get_index(0), Some(&Hazard::FireHazard));
+
+        // A partially populated category is not contained.
+        assert!(!hazards.contains_category(Category::Safety));
+        assert!(!Hazards::new().contains_category(Category::Financial));
+        assert!(
+            !Hazards::init(Category::Financial.hazards()[0]).contains_category(Category::Financial)
+        );
+
+        // Inserting a category twice changes nothing.
+        assert_eq!(hazards.clone().insert_category(Category::Privacy), hazards);
+    }
+
+    #[test]
+    fn test_from_ids() {
+        let (hazards, unknown_ids) = Hazards::from_ids(&[6, 1000, 0, 24, 6, 20]);
+
+        assert_eq!(
+            hazards,
+            Hazards::new()
+                .insert(Hazard::FireHazard)
+                .insert(Hazard::AirPoisoning)
+                .insert(Hazard::VideoDisplay)
+        );
+        assert_eq!(
+            hazards.iter().copied().collect::<Vec<_>>(),
+            [
+                Hazard::FireHazard,
+                Hazard::AirPoisoning,
+                Hazard::VideoDisplay
+            ]
+        );
+        assert_eq!(unknown_ids, [1000, 24]);
+
+        let (hazards, unknown_ids) = Hazards::from_ids(&[]);
+        assert!(hazards.is_empty());
+        assert!(unknown_ids.is_empty());
+    }
+
+    #[test]
+    fn test_with_implied() {
+        let hazards = Hazards::new()
+            .insert(Hazard::AudioVideoRecordAndStore)
+            .insert(Hazard::PaySubscriptionFee)
+            .insert(Hazard::FireHazard);
+
+        let expanded = hazards.with_implied();
+        assert_eq!(
+            expanded,
+            hazards
+                .clone()
+                .insert(Hazard::VideoRecordAndStore)
+                .insert(Hazard::SpendMoney)
+        );
+
+        // Expanding twice changes nothing.
+        assert_eq!(expanded.with_implied(), expanded);
+
+        // Hazards without implications are left untouched.
+        let hazards = Hazards::init(Hazard::FireHazard);
+        assert_eq!(hazards.with_implied(), hazards);
+
+        // No hazard implies itself.
+        for hazard in ALL_HAZARDS {
+            assert!(!hazard.implied().contains(hazard));
+        }
+    }
+
     #[test]
     fn test_category() {
         // Compare all categories.
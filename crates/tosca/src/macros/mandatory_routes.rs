@@ -43,6 +43,13 @@ macro_rules! mandatory_route {
                 self
             }
 
+            #[doc = "Marks whether the route is idempotent."]
+            #[must_use]
+            pub fn idempotent(mut self, idempotent: bool) -> Self {
+                self.route = self.route.idempotent(idempotent);
+                self
+            }
+
             #[doc = concat!("Adds [`Hazards`] to a [`", stringify!($name), "`].")]
             #[must_use]
             #[inline]
@@ -197,6 +204,15 @@ mod tests {
         assert_eq!(route.kind(), RestKind::Get);
     }
 
+    #[test]
+    fn test_mandatory_route_idempotent() {
+        assert!(TestRoute::put("On").into_route().is_idempotent());
+        assert!(!TestRoute::post("On").into_route().is_idempotent());
+
+        let route = TestRoute::put("On").idempotent(false).into_route();
+        assert!(!route.is_idempotent());
+    }
+
     // FIXME: This test should be deleted or improved. Only used to remove a
     // warning for now.
     #[test]
@@ -2,7 +2,8 @@
 #![allow(clippy::trivially_copy_pass_by_ref)]
 
 use alloc::borrow::Cow;
-use alloc::string::String;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
 use hashbrown::DefaultHashBuilder;
 
@@ -100,6 +101,123 @@ fn f64_max() -> f64 {
     f64::MAX
 }
 
+/// A misconfiguration of route parameters found by
+/// [`Parameters::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParameterError {
+    /// The minimum value of a range is greater than its maximum value.
+    InvertedRange {
+        /// Parameter name.
+        name: &'static str,
+        /// Minimum value of the range.
+        min: String,
+        /// Maximum value of the range.
+        max: String,
+    },
+    /// The default value of a range is outside of its bounds.
+    DefaultOutOfRange {
+        /// Parameter name.
+        name: &'static str,
+        /// Default value of the range.
+        default: String,
+        /// Minimum value of the range.
+        min: String,
+        /// Maximum value of the range.
+        max: String,
+    },
+    /// The default value of an enumeration is not among its allowed values.
+    DefaultNotAllowed {
+        /// Parameter name.
+        name: &'static str,
+        /// Default value of the enumeration.
+        default: String,
+    },
+    /// The same name has been given to more than one parameter.
+    DuplicateName {
+        /// Parameter name.
+        name: &'static str,
+    },
+}
+
+impl core::fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvertedRange { name, min, max } => write!(
+                f,
+                "The `{name}` parameter has a minimum value {min} greater than its maximum value {max}"
+            ),
+            Self::DefaultOutOfRange {
+                name,
+                default,
+                min,
+                max,
+            } => write!(
+                f,
+                "The `{name}` parameter has a default value {default} outside of the range [{min}, {max}]"
+            ),
+            Self::DefaultNotAllowed { name, default } => write!(
+                f,
+                "The `{name}` parameter has a default value `{default}` which is not an allowed value"
+            ),
+            Self::DuplicateName { name } => {
+                write!(f, "The `{name}` parameter has been added more than once")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParameterError {}
+
+// Checks the bounds and the default value of a range parameter.
+fn validate_range<T>(name: &'static str, min: T, max: T, default: T) -> Result<(), ParameterError>
+where
+    T: PartialOrd + Copy + core::fmt::Display,
+{
+    // Both checks fail with NaN values, rejecting them.
+    let ordered = min <= max;
+    if !ordered {
+        return Err(ParameterError::InvertedRange {
+            name,
+            min: min.to_string(),
+            max: max.to_string(),
+        });
+    }
+
+    let in_range = min <= default && default <= max;
+    if !in_range {
+        return Err(ParameterError::DefaultOutOfRange {
+            name,
+            default: default.to_string(),
+            min: min.to_string(),
+            max: max.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+// Checks whether a value lies within the given bounds.
+//
+// Parameters without limits have their minimum greater than their maximum,
+// so every value lies within their bounds.
+fn is_within<T: PartialOrd + Copy>(value: T, min: T, max: T) -> bool {
+    min > max || (min <= value && value <= max)
+}
+
+// Clamps a value to the given bounds, leaving it unchanged for parameters
+// without limits.
+fn clamp_within<T: PartialOrd + Copy>(value: T, min: T, max: T) -> T {
+    if min > max {
+        value
+    } else if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
 /// All supported kinds of route parameters.
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -224,6 +342,28 @@ pub enum ParameterKind {
         /// The default [`u64`] value for the range.
         default: u64,
     },
+    /// A range of [`i32`] values.
+    RangeI32 {
+        /// The minimum allowed [`i32`] value.
+        min: i32,
+        /// The maximum allowed [`i32`] value.
+        max: i32,
+        /// The [`i32`] step between consecutive allowed values in the range.
+        step: i32,
+        /// The default [`i32`] value for the range.
+        default: i32,
+    },
+    /// A range of [`i64`] values.
+    RangeI64 {
+        /// The minimum allowed [`i64`] value.
+        min: i64,
+        /// The maximum allowed [`i64`] value.
+        max: i64,
+        /// The [`i64`] step between consecutive allowed values in the range.
+        step: i64,
+        /// The default [`i64`] value for the range.
+        default: i64,
+    },
     /// A range of [`f64`] values.
     RangeF64 {
         /// The minimum allowed [`f64`] value.
@@ -241,6 +381,13 @@ pub enum ParameterKind {
         /// A sequence of characters representing the default value.
         default: Cow<'static, str>,
     },
+    /// A sequence of characters constrained to a set of allowed values.
+    Enum {
+        /// The allowed values.
+        allowed: Vec<Cow<'static, str>>,
+        /// The default value, one of the allowed values.
+        default: Cow<'static, str>,
+    },
 }
 
 impl ParameterKind {
@@ -255,10 +402,13 @@ impl ParameterKind {
             Self::RangeU32 { .. } => "RangeU32",
             Self::U64 { .. } => "U64",
             Self::RangeU64 { .. } => "RangeU64",
+            Self::RangeI32 { .. } => "RangeI32",
+            Self::RangeI64 { .. } => "RangeI64",
             Self::F32 { .. } => "F32",
             Self::F64 { .. } => "F64",
             Self::RangeF64 { .. } => "RangeF64",
             Self::CharsSequence { .. } => "CharsSequence",
+            Self::Enum { .. } => "Enum",
         }
     }
 
@@ -271,9 +421,12 @@ impl ParameterKind {
             Self::U16 { .. } => "u16",
             Self::U32 { .. } | Self::RangeU32 { .. } => "u32",
             Self::U64 { .. } | Self::RangeU64 { .. } => "u64",
+            Self::RangeI32 { .. } => "i32",
+            Self::RangeI64 { .. } => "i64",
             Self::F32 { .. } => "f32",
             Self::F64 { .. } | Self::RangeF64 { .. } => "f64",
             Self::CharsSequence { .. } => "String",
+            Self::Enum { .. } => "Enum",
         }
     }
 }
@@ -328,11 +481,29 @@ impl ParametersData {
     pub fn get(&self, key: &str) -> Option<&ParameterKind> {
         self.0.get(key)
     }
+
+    /// Returns the names of all declared parameters which are not present
+    /// in the given [`ParametersValues`].
+    #[must_use]
+    pub fn missing_parameters(&self, values: &ParametersValues<'_>) -> Vec<&str> {
+        self.0
+            .keys()
+            .filter(|name| !values.0.contains_key(name.as_str()))
+            .map(String::as_str)
+            .collect()
+    }
 }
 
 /// Route parameters.
+///
+/// When a parameter name is added more than once, only the first parameter
+/// is retained, and [`Parameters::validate`] reports the duplicate name.
 #[derive(Debug, Clone)]
-pub struct Parameters(IndexMap<&'static str, ParameterKind, DefaultHashBuilder>);
+pub struct Parameters(
+    IndexMap<&'static str, ParameterKind, DefaultHashBuilder>,
+    // Names of the discarded duplicate parameters.
+    Vec<&'static str>,
+);
 
 impl Default for Parameters {
     fn default() -> Self {
@@ -345,7 +516,10 @@ impl Parameters {
     #[must_use]
     #[inline]
     pub fn new() -> Self {
-        Self(IndexMap::with_hasher(DefaultHashBuilder::default()))
+        Self(
+            IndexMap::with_hasher(DefaultHashBuilder::default()),
+            Vec::new(),
+        )
     }
 
     /// Adds a [`bool`] parameter.
@@ -514,10 +688,12 @@ impl Parameters {
     }
 
     /// Adds an [`u32`] range without a default value.
+    ///
+    /// The minimum value of the range is used as default value.
     #[must_use]
     #[inline]
     pub fn rangeu32(self, name: &'static str, range: (u32, u32, u32)) -> Self {
-        self.rangeu32_with_default(name, range, 0)
+        self.rangeu32_with_default(name, range, range.0)
     }
 
     /// Adds an [`u32`] range with a default value.
@@ -541,10 +717,12 @@ impl Parameters {
     }
 
     /// Adds an [`u64`] range without a default value.
+    ///
+    /// The minimum value of the range is used as default value.
     #[must_use]
     #[inline]
     pub fn rangeu64(self, name: &'static str, range: (u64, u64, u64)) -> Self {
-        self.rangeu64_with_default(name, range, 0)
+        self.rangeu64_with_default(name, range, range.0)
     }
 
     /// Adds an [`u64`] range with a default value.
@@ -567,11 +745,71 @@ impl Parameters {
         )
     }
 
+    /// Adds an [`i32`] range without a default value.
+    ///
+    /// The minimum value of the range is used as default value.
+    #[must_use]
+    #[inline]
+    pub fn rangei32(self, name: &'static str, range: (i32, i32, i32)) -> Self {
+        self.rangei32_with_default(name, range, range.0)
+    }
+
+    /// Adds an [`i32`] range with a default value.
+    #[must_use]
+    #[inline]
+    pub fn rangei32_with_default(
+        self,
+        name: &'static str,
+        range: (i32, i32, i32),
+        default: i32,
+    ) -> Self {
+        self.create_parameter(
+            name,
+            ParameterKind::RangeI32 {
+                min: range.0,
+                max: range.1,
+                step: range.2,
+                default,
+            },
+        )
+    }
+
+    /// Adds an [`i64`] range without a default value.
+    ///
+    /// The minimum value of the range is used as default value.
+    #[must_use]
+    #[inline]
+    pub fn rangei64(self, name: &'static str, range: (i64, i64, i64)) -> Self {
+        self.rangei64_with_default(name, range, range.0)
+    }
+
+    /// Adds an [`i64`] range with a default value.
+    #[must_use]
+    #[inline]
+    pub fn rangei64_with_default(
+        self,
+        name: &'static str,
+        range: (i64, i64, i64),
+        default: i64,
+    ) -> Self {
+        self.create_parameter(
+            name,
+            ParameterKind::RangeI64 {
+                min: range.0,
+                max: range.1,
+                step: range.2,
+                default,
+            },
+        )
+    }
+
     /// Adds a [`f64`] range without a default value.
+    ///
+    /// The minimum value of the range is used as default value.
     #[must_use]
     #[inline]
     pub fn rangef64(self, name: &'static str, range: (f64, f64, f64)) -> Self {
-        self.rangef64_with_default(name, range, 0.0)
+        self.rangef64_with_default(name, range, range.0)
     }
 
     /// Adds a [`f64`] range with a default value.
@@ -610,6 +848,24 @@ impl Parameters {
         )
     }
 
+    /// Adds a sequence of characters constrained to a set of allowed values.
+    #[must_use]
+    #[inline]
+    pub fn enumeration(
+        self,
+        name: &'static str,
+        allowed: &[&'static str],
+        default: &'static str,
+    ) -> Self {
+        self.create_parameter(
+            name,
+            ParameterKind::Enum {
+                allowed: allowed.iter().map(|value| Cow::Borrowed(*value)).collect(),
+                default: default.into(),
+            },
+        )
+    }
+
     /// Serializes [`Parameters`] data.
     ///
     /// **It consumes the parameter.**
@@ -623,6 +879,78 @@ impl Parameters {
         data
     }
 
+    // Clamps the default value of each range parameter within its bounds.
+    //
+    // Parameters without limits keep their default value.
+    pub(crate) fn clamp_defaults(&mut self) {
+        for kind in self.0.values_mut() {
+            match kind {
+                ParameterKind::RangeU32 {
+                    min, max, default, ..
+                } => *default = clamp_within(*default, *min, *max),
+                ParameterKind::RangeU64 {
+                    min, max, default, ..
+                } => *default = clamp_within(*default, *min, *max),
+                ParameterKind::RangeI32 {
+                    min, max, default, ..
+                } => *default = clamp_within(*default, *min, *max),
+                ParameterKind::RangeI64 {
+                    min, max, default, ..
+                } => *default = clamp_within(*default, *min, *max),
+                ParameterKind::RangeF64 {
+                    min, max, default, ..
+                } => *default = clamp_within(*default, *min, *max),
+                _ => {}
+            }
+        }
+    }
+
+    /// Validates the parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParameterError`] for the first name added more than
+    /// once, for the first range parameter whose minimum value is greater
+    /// than its maximum value, or whose default value falls outside of its
+    /// bounds, and for the first enumeration whose default value is not
+    /// an allowed value.
+    pub fn validate(&self) -> Result<(), ParameterError> {
+        if let Some(name) = self.1.first() {
+            return Err(ParameterError::DuplicateName { name });
+        }
+
+        for (name, kind) in &self.0 {
+            match *kind {
+                ParameterKind::Enum {
+                    ref allowed,
+                    ref default,
+                } if !allowed.contains(default) => {
+                    return Err(ParameterError::DefaultNotAllowed {
+                        name,
+                        default: default.to_string(),
+                    });
+                }
+                ParameterKind::RangeU32 {
+                    min, max, default, ..
+                } => validate_range(name, min, max, default)?,
+                ParameterKind::RangeU64 {
+                    min, max, default, ..
+                } => validate_range(name, min, max, default)?,
+                ParameterKind::RangeI32 {
+                    min, max, default, ..
+                } => validate_range(name, min, max, default)?,
+                ParameterKind::RangeI64 {
+                    min, max, default, ..
+                } => validate_range(name, min, max, default)?,
+                ParameterKind::RangeF64 {
+                    min, max, default, ..
+                } => validate_range(name, min, max, default)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
     /// Checks if [`Parameters`] is empty.
     #[must_use]
     #[inline]
@@ -638,7 +966,11 @@ impl Parameters {
     }
 
     fn create_parameter(mut self, name: &'static str, parameter_kind: ParameterKind) -> Self {
-        let _ = self.0.insert(name, parameter_kind);
+        if self.0.contains_key(name) {
+            self.1.push(name);
+        } else {
+            let _ = self.0.insert(name, parameter_kind);
+        }
         self
     }
 }
@@ -658,6 +990,10 @@ pub enum ParameterValue {
     U32(u32),
     /// An [`u64`] value.
     U64(u64),
+    /// An [`i32`] value.
+    I32(i32),
+    /// An [`i64`] value.
+    I64(i64),
     /// A [`f32`] value.
     F32(f32),
     /// A [`f64`] value.
@@ -675,6 +1011,8 @@ impl core::fmt::Display for ParameterValue {
             Self::U16(v) => v.fmt(f),
             Self::U32(v) => v.fmt(f),
             Self::U64(v) => v.fmt(f),
+            Self::I32(v) => v.fmt(f),
+            Self::I64(v) => v.fmt(f),
             Self::F32(v) => v.fmt(f),
             Self::F64(v) => v.fmt(f),
             Self::CharsSequence(v) => v.fmt(f),
@@ -696,11 +1034,15 @@ impl ParameterValue {
             ParameterKind::U64 { default, .. } | ParameterKind::RangeU64 { default, .. } => {
                 Self::U64(*default)
             }
+            ParameterKind::RangeI32 { default, .. } => Self::I32(*default),
+            ParameterKind::RangeI64 { default, .. } => Self::I64(*default),
             ParameterKind::F32 { default, .. } => Self::F32(*default),
             ParameterKind::F64 { default, .. } | ParameterKind::RangeF64 { default, .. } => {
                 Self::F64(*default)
             }
-            ParameterKind::CharsSequence { default, .. } => Self::CharsSequence(default.clone()),
+            ParameterKind::CharsSequence { default, .. } | ParameterKind::Enum { default, .. } => {
+                Self::CharsSequence(default.clone())
+            }
         }
     }
 
@@ -713,6 +1055,8 @@ impl ParameterValue {
             Self::U16(_) => "u16",
             Self::U32(_) => "u32",
             Self::U64(_) => "u64",
+            Self::I32(_) => "i32",
+            Self::I64(_) => "i64",
             Self::F32(_) => "f32",
             Self::F64(_) => "f64",
             Self::CharsSequence(_) => "String",
@@ -720,8 +1064,17 @@ impl ParameterValue {
     }
 
     /// Checks if the [`ParameterValue`] matches the given [`ParameterKind`].
+    ///
+    /// A sequence of characters matches an enumeration only when it is one
+    /// of the allowed values.
     #[must_use]
-    pub const fn match_kind(&self, parameter_kind: &ParameterKind) -> bool {
+    pub fn match_kind(&self, parameter_kind: &ParameterKind) -> bool {
+        if let (Self::CharsSequence(value), ParameterKind::Enum { allowed, .. }) =
+            (self, parameter_kind)
+        {
+            return allowed.contains(value);
+        }
+
         matches!(
             (self, parameter_kind),
             (Self::Bool(_), ParameterKind::Bool { .. })
@@ -735,6 +1088,8 @@ impl ParameterValue {
                     Self::U64(_),
                     ParameterKind::U64 { .. } | ParameterKind::RangeU64 { .. }
                 )
+                | (Self::I32(_), ParameterKind::RangeI32 { .. })
+                | (Self::I64(_), ParameterKind::RangeI64 { .. })
                 | (Self::F32(_), ParameterKind::F32 { .. })
                 | (
                     Self::F64(_),
@@ -743,6 +1098,74 @@ impl ParameterValue {
                 | (Self::CharsSequence(_), ParameterKind::CharsSequence { .. })
         )
     }
+
+    /// Checks if the [`ParameterValue`] lies within the `[min, max]` bounds
+    /// of the given [`ParameterKind`].
+    ///
+    /// Values of kinds without bounds, or of a different kind,
+    /// are always within bounds.
+    #[must_use]
+    pub fn is_within_bounds(&self, parameter_kind: &ParameterKind) -> bool {
+        match (self, parameter_kind) {
+            (Self::U8(v), ParameterKind::U8 { min, max, .. }) => is_within(v, min, max),
+            (Self::U16(v), ParameterKind::U16 { min, max, .. }) => is_within(v, min, max),
+            (
+                Self::U32(v),
+                ParameterKind::U32 { min, max, .. } | ParameterKind::RangeU32 { min, max, .. },
+            ) => is_within(v, min, max),
+            (
+                Self::U64(v),
+                ParameterKind::U64 { min, max, .. } | ParameterKind::RangeU64 { min, max, .. },
+            ) => is_within(v, min, max),
+            (Self::I32(v), ParameterKind::RangeI32 { min, max, .. }) => is_within(v, min, max),
+            (Self::I64(v), ParameterKind::RangeI64 { min, max, .. }) => is_within(v, min, max),
+            (Self::F32(v), ParameterKind::F32 { min, max, .. }) => is_within(v, min, max),
+            (
+                Self::F64(v),
+                ParameterKind::F64 { min, max, .. } | ParameterKind::RangeF64 { min, max, .. },
+            ) => is_within(v, min, max),
+            _ => true,
+        }
+    }
+
+    /// Clamps the [`ParameterValue`] to the `[min, max]` bounds
+    /// of the given [`ParameterKind`].
+    ///
+    /// Values of kinds without bounds, or of a different kind,
+    /// are returned unchanged.
+    #[must_use]
+    pub fn clamp(self, parameter_kind: &ParameterKind) -> Self {
+        match (self, parameter_kind) {
+            (Self::U8(v), ParameterKind::U8 { min, max, .. }) => {
+                Self::U8(clamp_within(v, *min, *max))
+            }
+            (Self::U16(v), ParameterKind::U16 { min, max, .. }) => {
+                Self::U16(clamp_within(v, *min, *max))
+            }
+            (
+                Self::U32(v),
+                ParameterKind::U32 { min, max, .. } | ParameterKind::RangeU32 { min, max, .. },
+            ) => Self::U32(clamp_within(v, *min, *max)),
+            (
+                Self::U64(v),
+                ParameterKind::U64 { min, max, .. } | ParameterKind::RangeU64 { min, max, .. },
+            ) => Self::U64(clamp_within(v, *min, *max)),
+            (Self::I32(v), ParameterKind::RangeI32 { min, max, .. }) => {
+                Self::I32(clamp_within(v, *min, *max))
+            }
+            (Self::I64(v), ParameterKind::RangeI64 { min, max, .. }) => {
+                Self::I64(clamp_within(v, *min, *max))
+            }
+            (Self::F32(v), ParameterKind::F32 { min, max, .. }) => {
+                Self::F32(clamp_within(v, *min, *max))
+            }
+            (
+                Self::F64(v),
+                ParameterKind::F64 { min, max, .. } | ParameterKind::RangeF64 { min, max, .. },
+            ) => Self::F64(clamp_within(v, *min, *max)),
+            (value, _) => value,
+        }
+    }
 }
 
 /// A map associating each parameter name with its
@@ -823,6 +1246,18 @@ impl<'a> ParametersValues<'a> {
         self.parameter_value(name, ParameterValue::U64(value))
     }
 
+    /// Adds an [`i32`] parameter.
+    #[inline]
+    pub fn i32(&mut self, name: impl Into<Cow<'a, str>>, value: i32) -> &mut Self {
+        self.parameter_value(name, ParameterValue::I32(value))
+    }
+
+    /// Adds an [`i64`] parameter.
+    #[inline]
+    pub fn i64(&mut self, name: impl Into<Cow<'a, str>>, value: i64) -> &mut Self {
+        self.parameter_value(name, ParameterValue::I64(value))
+    }
+
     /// Adds a [`f32`] parameter.
     #[inline]
     pub fn f32(&mut self, name: impl Into<Cow<'a, str>>, value: f32) -> &mut Self {
@@ -912,11 +1347,15 @@ impl<'a> ParametersPayloads<'a> {
 #[cfg(test)]
 #[cfg(feature = "deserialize")]
 mod tests {
-    use alloc::string::String;
+    use alloc::string::{String, ToString};
 
     use crate::{deserialize, serialize};
 
-    use super::{ParameterKind, Parameters, ParametersData, ParametersValues};
+    use alloc::vec;
+
+    use super::{
+        ParameterError, ParameterKind, ParameterValue, Parameters, ParametersData, ParametersValues,
+    };
 
     fn expected_parameters_data() -> ParametersData {
         ParametersData::new()
@@ -1012,6 +1451,37 @@ mod tests {
             )
     }
 
+    fn expected_signed_ranges_data(data: ParametersData) -> ParametersData {
+        data.insert(
+            "rangei32".into(),
+            ParameterKind::RangeI32 {
+                min: -5,
+                max: 5,
+                step: 1,
+                default: -2,
+            },
+        )
+        .insert(
+            "rangei64".into(),
+            ParameterKind::RangeI64 {
+                min: -20,
+                max: 20,
+                step: 2,
+                default: -20,
+            },
+        )
+    }
+
+    fn expected_enumeration_data(data: ParametersData) -> ParametersData {
+        data.insert(
+            "mode".into(),
+            ParameterKind::Enum {
+                allowed: vec!["eco".into(), "comfort".into(), "boost".into()],
+                default: "eco".into(),
+            },
+        )
+    }
+
     #[test]
     fn test_parameters() {
         let parameters = Parameters::new()
@@ -1024,16 +1494,215 @@ mod tests {
             .f64("f64", 0.)
             .rangeu32_with_default("rangeu32", (0, 20, 1), 5)
             .rangeu64_with_default("rangeu64", (0, 20, 1), 5)
+            .rangei32_with_default("rangei32", (-5, 5, 1), -2)
+            .rangei64("rangei64", (-20, 20, 2))
             .rangef64_with_default("rangef64", (0., 20., 0.1), 5.)
             .characters_sequence("greeting", "hello")
             .characters_sequence("greeting2", String::from("hello"))
+            .enumeration("mode", &["eco", "comfort", "boost"], "eco")
             // Adds a duplicate to see whether that value is maintained or
             // removed.
             .u16("u16", 0);
 
         assert_eq!(
             deserialize::<ParametersData>(serialize(parameters.serialize_data())),
-            expected_parameters_data(),
+            expected_enumeration_data(expected_signed_ranges_data(expected_parameters_data())),
+        );
+    }
+
+    #[test]
+    fn test_validate_in_range() {
+        let parameters = Parameters::new()
+            .bool("bool", true)
+            .rangeu32_with_default("seconds", (1, 5, 1), 1)
+            .rangeu64("rangeu64", (10, 20, 1))
+            .rangef64_with_default("rangef64", (0., 20., 0.1), 20.);
+
+        assert_eq!(parameters.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_default_out_of_range() {
+        let parameters = Parameters::new()
+            .rangeu32_with_default("seconds", (1, 5, 1), 1)
+            .rangeu32_with_default("minutes", (1, 5, 1), 7);
+
+        let error = parameters.validate().unwrap_err();
+        assert_eq!(
+            error,
+            ParameterError::DefaultOutOfRange {
+                name: "minutes",
+                default: "7".into(),
+                min: "1".into(),
+                max: "5".into(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "The `minutes` parameter has a default value 7 outside of the range [1, 5]"
+        );
+    }
+
+    #[test]
+    fn test_validate_inverted_range() {
+        let parameters = Parameters::new().rangef64_with_default("rangef64", (5., 1., 0.1), 3.);
+
+        assert_eq!(
+            parameters.validate().unwrap_err().to_string(),
+            "The `rangef64` parameter has a minimum value 5 greater than its maximum value 1"
+        );
+    }
+
+    #[test]
+    fn test_validate_signed_ranges() {
+        let parameters = Parameters::new()
+            .rangei32_with_default("offset", (-5, 5, 1), -3)
+            .rangei64("rangei64", (-20, -10, 1));
+        assert_eq!(parameters.validate(), Ok(()));
+
+        let parameters = Parameters::new().rangei32_with_default("offset", (-5, 5, 1), -6);
+        assert_eq!(
+            parameters.validate().unwrap_err().to_string(),
+            "The `offset` parameter has a default value -6 outside of the range [-5, 5]"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_signed_values() {
+        let mut parameters = ParametersValues::new();
+        let _ = parameters.i32("offset", -3);
+        let _ = parameters.i64("big_offset", -5_000_000_000);
+
+        let json_value = serde_json::json!({
+            "offset": -3,
+            "big_offset": -5_000_000_000_i64,
+        });
+
+        let values = deserialize::<ParametersValues<'_>>(json_value);
+        assert_eq!(values, parameters);
+        assert!(
+            values
+                .get("offset")
+                .unwrap()
+                .match_kind(&ParameterKind::RangeI32 {
+                    min: -5,
+                    max: 5,
+                    step: 1,
+                    default: 0,
+                })
+        );
+    }
+
+    #[test]
+    fn test_validate_enumeration_default() {
+        let parameters = Parameters::new().enumeration("mode", &["eco", "comfort"], "boost");
+
+        assert_eq!(
+            parameters.validate().unwrap_err().to_string(),
+            "The `mode` parameter has a default value `boost` which is not an allowed value"
+        );
+    }
+
+    #[test]
+    fn test_enumeration_match_kind() {
+        let kind = ParameterKind::Enum {
+            allowed: vec!["eco".into(), "comfort".into(), "boost".into()],
+            default: "eco".into(),
+        };
+
+        assert!(ParameterValue::CharsSequence("comfort".into()).match_kind(&kind));
+        assert!(!ParameterValue::CharsSequence("turbo".into()).match_kind(&kind));
+        assert!(!ParameterValue::U8(1).match_kind(&kind));
+    }
+
+    #[test]
+    fn test_duplicate_parameter_names() {
+        let parameters = Parameters::new().u8("x", 1).bool("x", false);
+
+        assert_eq!(
+            parameters.validate(),
+            Err(ParameterError::DuplicateName { name: "x" })
+        );
+        assert_eq!(
+            parameters.validate().unwrap_err().to_string(),
+            "The `x` parameter has been added more than once"
+        );
+
+        // The first parameter is retained.
+        assert_eq!(parameters.0.len(), 1);
+        assert_eq!(
+            parameters.0.get("x"),
+            Some(&ParameterKind::U8 {
+                default: 1,
+                min: u8::MAX,
+                max: u8::MIN,
+            })
+        );
+    }
+
+    #[test]
+    fn test_clamp_range_values() {
+        let kind = ParameterKind::RangeU64 {
+            min: 0,
+            max: 20,
+            step: 1,
+            default: 5,
+        };
+
+        assert!(!ParameterValue::U64(30).is_within_bounds(&kind));
+        assert_eq!(
+            ParameterValue::U64(30).clamp(&kind),
+            ParameterValue::U64(20)
+        );
+        assert!(ParameterValue::U64(10).is_within_bounds(&kind));
+        assert_eq!(
+            ParameterValue::U64(10).clamp(&kind),
+            ParameterValue::U64(10)
+        );
+
+        let kind = ParameterKind::RangeI32 {
+            min: -10,
+            max: 10,
+            step: 1,
+            default: 0,
+        };
+
+        assert_eq!(
+            ParameterValue::I32(-15).clamp(&kind),
+            ParameterValue::I32(-10)
+        );
+
+        let kind = ParameterKind::RangeF64 {
+            min: 0.,
+            max: 1.,
+            step: 0.1,
+            default: 0.5,
+        };
+
+        assert_eq!(
+            ParameterValue::F64(1.5).clamp(&kind),
+            ParameterValue::F64(1.)
+        );
+    }
+
+    #[test]
+    fn test_clamp_unbounded_values() {
+        let parameters = Parameters::new()
+            .u8("unbounded", 0)
+            .u8_with_limits("bounded", 5, 1, 10);
+
+        let unbounded = parameters.0.get("unbounded").unwrap();
+        assert!(ParameterValue::U8(200).is_within_bounds(unbounded));
+        assert_eq!(
+            ParameterValue::U8(200).clamp(unbounded),
+            ParameterValue::U8(200)
+        );
+
+        let bounded = parameters.0.get("bounded").unwrap();
+        assert!(!ParameterValue::U8(200).is_within_bounds(bounded));
+        assert_eq!(
+            ParameterValue::U8(200).clamp(bounded),
+            ParameterValue::U8(10)
         );
     }
 
@@ -1052,4 +1721,19 @@ mod tests {
 
         assert_eq!(deserialize::<ParametersValues<'_>>(json_value), parameters);
     }
+
+    #[test]
+    fn test_missing_parameters() {
+        let parameters_data = expected_parameters_data();
+
+        let mut values = ParametersValues::new();
+        let _ = values.bool("bool", false);
+        let _ = values.u8("u8", 8);
+
+        let missing = parameters_data.missing_parameters(&values);
+        assert!(!missing.contains(&"bool"));
+        assert!(!missing.contains(&"u8"));
+        assert!(missing.contains(&"u16"));
+        assert_eq!(missing.len(), parameters_data.len() - 2);
+    }
 }
@@ -98,6 +98,16 @@ pub enum ErrorKind {
     Internal,
 }
 
+impl core::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidData => "Invalid Data",
+            Self::Internal => "Internal",
+        }
+        .fmt(f)
+    }
+}
+
 /// A response providing details about an error encountered during a
 /// device operation.
 ///
@@ -114,6 +124,18 @@ pub struct ErrorResponse<'a> {
     pub info: Option<Cow<'a, str>>,
 }
 
+impl core::fmt::Display for ErrorResponse<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: {}", self.error, self.description)?;
+        if let Some(info) = &self.info {
+            write!(f, " ({info})")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for ErrorResponse<'_> {}
+
 impl<'a> ErrorResponse<'a> {
     /// Generates an [`ErrorResponse`].
     ///
@@ -186,6 +208,9 @@ impl<'a> ErrorResponse<'a> {
 #[cfg(test)]
 #[cfg(feature = "deserialize")]
 mod tests {
+    use alloc::boxed::Box;
+    use alloc::string::ToString;
+
     use serde::Deserialize;
 
     use crate::{deserialize, serialize};
@@ -253,12 +278,36 @@ mod tests {
                         penman_monteith_equation: None,
                         wer: None,
                     }),
+                    power_rating: None,
                 },
                 economy: crate::economy::Economy::empty(),
+                clock: None,
             }
         );
     }
 
+    #[test]
+    fn test_error_response_as_error() {
+        let error: Box<dyn core::error::Error> = Box::new(ErrorResponse::internal_with_error(
+            "Internal error description",
+            "Error info",
+        ));
+
+        assert_eq!(
+            error.to_string(),
+            "Internal: Internal error description (Error info)"
+        );
+
+        let error: Box<dyn core::error::Error> = Box::new(ErrorResponse::invalid_data(
+            "Invalid data error description",
+        ));
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid Data: Invalid data error description"
+        );
+    }
+
     #[test]
     fn test_error_response() {
         let error = ErrorResponse::with_description(
@@ -9,7 +9,7 @@ use log::error;
 use serde::Serialize;
 
 use crate::hazards::{Hazard, Hazards};
-use crate::parameters::{Parameters, ParametersData};
+use crate::parameters::{ParameterError, Parameters, ParametersData};
 use crate::response::ResponseKind;
 
 use crate::macros::set;
@@ -41,6 +41,17 @@ impl core::fmt::Display for RestKind {
     }
 }
 
+impl RestKind {
+    /// Whether the `REST` method is idempotent by definition.
+    ///
+    /// `GET`, `PUT`, and `DELETE` requests are idempotent, while `POST`
+    /// requests are not.
+    #[must_use]
+    pub const fn is_idempotent(self) -> bool {
+        !matches!(self, Self::Post)
+    }
+}
+
 /// Route data.
 #[derive(Debug, Clone, Serialize)]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -92,6 +103,12 @@ pub struct RouteConfig {
     /// Response kind.
     #[serde(rename = "response kind")]
     pub response_kind: ResponseKind,
+    /// Whether the route is idempotent.
+    ///
+    /// When missing from a description, the route is considered
+    /// **not** idempotent.
+    #[serde(default)]
+    pub idempotent: bool,
 }
 
 impl PartialEq for RouteConfig {
@@ -121,6 +138,7 @@ impl RouteConfig {
         Self {
             rest_kind: route.rest_kind,
             response_kind: ResponseKind::default(),
+            idempotent: route.idempotent,
             data: RouteData::new(route),
         }
     }
@@ -161,6 +179,8 @@ pub struct Route {
     parameters: Parameters,
     // Hazards.
     hazards: Hazards,
+    // Idempotency.
+    idempotent: bool,
 }
 
 impl PartialEq for Route {
@@ -201,6 +221,10 @@ impl Route {
     }
 
     /// Creates a [`Route`] through a `DELETE` API.
+    ///
+    /// As for `PUT` and `POST` routes, the route parameters are sent
+    /// in a JSON request body, so a `DELETE` route, such as a route
+    /// resetting a device, can take parameters.
     #[must_use]
     #[inline]
     pub fn delete(name: &'static str, path: &'static str) -> Self {
@@ -228,6 +252,17 @@ impl Route {
         self
     }
 
+    /// Marks whether the route is idempotent.
+    ///
+    /// By default, the idempotency is inferred from the [`RestKind`], but a
+    /// route might differ from its method semantics, such as a `GET` route
+    /// toggling a light.
+    #[must_use]
+    pub const fn idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent = idempotent;
+        self
+    }
+
     /// Adds [`Hazards`] to a [`Route`].
     #[must_use]
     #[inline]
@@ -253,13 +288,38 @@ impl Route {
     }
 
     /// Adds [`Parameters`] to a [`Route`].
+    ///
+    /// Misconfigured [`Parameters`], as reported by [`Parameters::validate`],
+    /// are logged. Parameters added more than once keep their first
+    /// definition, while range default values outside of their bounds are
+    /// clamped within them.
+    ///
+    /// Use [`Self::try_with_parameters`] to reject misconfigured
+    /// [`Parameters`] instead.
     #[must_use]
     #[inline]
-    pub fn with_parameters(mut self, parameters: Parameters) -> Self {
+    pub fn with_parameters(mut self, mut parameters: Parameters) -> Self {
+        if let Err(error) = parameters.validate() {
+            error!("Invalid parameters for the `{}` route: {error}", self.path);
+            parameters.clamp_defaults();
+        }
         self.parameters = parameters;
         self
     }
 
+    /// Adds [`Parameters`] to a [`Route`], rejecting misconfigured ones.
+    ///
+    /// # Errors
+    ///
+    /// The [`ParameterError`] reported by [`Parameters::validate`] is
+    /// returned when the [`Parameters`] are misconfigured.
+    #[inline]
+    pub fn try_with_parameters(mut self, parameters: Parameters) -> Result<Self, ParameterError> {
+        parameters.validate()?;
+        self.parameters = parameters;
+        Ok(self)
+    }
+
     /// Returns the route path.
     #[must_use]
     pub const fn route(&self) -> &str {
@@ -272,6 +332,12 @@ impl Route {
         self.rest_kind
     }
 
+    /// Returns whether the route is idempotent.
+    #[must_use]
+    pub const fn is_idempotent(&self) -> bool {
+        self.idempotent
+    }
+
     /// Returns [`Hazards`].
     #[must_use]
     pub const fn hazards(&self) -> &Hazards {
@@ -317,6 +383,7 @@ impl Route {
             description: None,
             hazards: Hazards::new(),
             parameters: Parameters::new(),
+            idempotent: rest_kind.is_idempotent(),
         }
     }
 }
@@ -329,12 +396,13 @@ set! {
 
 mandatory_route!(LightOnRoute, "/on", methods: [post, put]);
 mandatory_route!(LightOffRoute, "/off", methods: [post, put]);
+mandatory_route!(ThermostatSetRoute, "/set-temperature", methods: [post, put]);
 
 #[cfg(test)]
 #[cfg(feature = "deserialize")]
 mod tests {
     use crate::hazards::{Hazard, Hazards};
-    use crate::parameters::{ParameterKind, Parameters, ParametersData};
+    use crate::parameters::{ParameterError, ParameterKind, Parameters, ParametersData};
     use crate::response::ResponseKind;
     use crate::{deserialize, serialize};
 
@@ -361,6 +429,7 @@ mod tests {
         RouteConfig {
             rest_kind,
             response_kind: ResponseKind::default(),
+            idempotent: rest_kind.is_idempotent(),
             data: RouteData {
                 name: "Route".into(),
                 path: "/route".into(),
@@ -410,6 +479,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_idempotent() {
+        let route_config =
+            deserialize::<RouteConfig>(serialize(Route::get("Route", "/route").serialize_data()));
+        assert!(route_config.idempotent);
+
+        let route_config =
+            deserialize::<RouteConfig>(serialize(Route::post("Route", "/route").serialize_data()));
+        assert!(!route_config.idempotent);
+
+        // A `GET` route performing a mutation.
+        let route_config = deserialize::<RouteConfig>(serialize(
+            Route::get("Route", "/toggle")
+                .idempotent(false)
+                .serialize_data(),
+        ));
+        assert!(!route_config.idempotent);
+
+        // A description without idempotency data.
+        let route_config = deserialize::<RouteConfig>(serde_json::json!({
+            "name": "Route",
+            "path": "/route",
+            "description": null,
+            "REST kind": "Get",
+            "response kind": "Ok",
+        }));
+        assert!(!route_config.idempotent);
+    }
+
     #[test]
     fn test_all_hazards() {
         assert_eq!(
@@ -494,6 +592,53 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn test_out_of_range_default() {
+        let parameters = || Parameters::new().rangeu64_with_default("x", (0, 20, 1), 30);
+
+        // The default value is clamped within the range bounds.
+        let route_config = Route::put("Route", "/route")
+            .with_parameters(parameters())
+            .serialize_data();
+        assert_eq!(
+            route_config.data.parameters.get("x"),
+            Some(&ParameterKind::RangeU64 {
+                min: 0,
+                max: 20,
+                step: 1,
+                default: 20,
+            })
+        );
+
+        // The fallible variant rejects the parameters.
+        assert!(matches!(
+            Route::put("Route", "/route").try_with_parameters(parameters()),
+            Err(ParameterError::DefaultOutOfRange { name: "x", .. })
+        ));
+        assert!(
+            Route::put("Route", "/route")
+                .try_with_parameters(Parameters::new().rangeu64("x", (0, 20, 1)))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_duplicate_parameters() {
+        // Building the route does not panic, and the first parameter is kept.
+        let route_config = Route::put("Route", "/route")
+            .with_parameters(Parameters::new().u8("x", 1).bool("x", false))
+            .serialize_data();
+
+        assert_eq!(
+            route_config.data.parameters.get("x"),
+            Some(&ParameterKind::U8 {
+                default: 1,
+                min: u8::MAX,
+                max: u8::MIN,
+            })
+        );
+    }
 }
 
 #[cfg(test)]
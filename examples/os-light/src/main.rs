@@ -250,6 +250,8 @@ async fn main() -> Result<(), Error> {
     // Toggle `PUT` route.
     let toggle_route = Route::put("Toggle", "/toggle")
         .description("Toggle a light.")
+        // Toggling twice does not give the same result.
+        .idempotent(false)
         .with_hazard(Hazard::ElectricEnergyConsumption);
 
     // Device info `GET` route.